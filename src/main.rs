@@ -1 +1,517 @@
-fn main() {}
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::rc::Rc;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use chrono::TimeDelta;
+
+use pepa::{
+    CachedProvider, Calculator, CsvTable, FileProvider, Locale, Localize, Notebook, QuoteCache,
+    Statement, UnitFamily, UnitStyle,
+};
+
+#[cfg(test)]
+mod main_tests;
+
+/// How often the watched file is checked for changes.
+const WATCH_INTERVAL: Duration = Duration::from_millis(250);
+
+const USAGE: &str = "Usage: pc [OPTIONS] [EXPRESSION... | FILE.calc]
+
+Evaluates the expression, every line of a .calc file,
+or every line of the standard input if no expression is given.
+
+Options:
+  --rates FILE        exchange rates table
+  --quotes FILE       quotes provider file (stock prices, currency pairs ...)
+  --holidays FILE     non-working days for the business day functions
+  --locale CODE       number format and language: en, cs
+  --unit-names        full unit names in the results: 5 kilometres
+  --json              one JSON object per statement
+  --annotate          write the results into the .calc file: 5 km in m  = 5000m
+  --watch             evaluate the .calc file again whenever it changes
+  --csv FILE          evaluate --expr for every row of the CSV file, columns are variables
+  --expr EXPR         expression of the CSV evaluation: price * qty * 1.21
+  --out FILE          CSV file with the results (default standard output)
+  --column NAME       name of the results column (default result)
+  --prefer FAMILIES   unit families for ambiguous units, comma separated: time,length
+  --offline           do not fetch any data, use only local and cached data
+  --cache FILE        quotes cache file
+  --cache-ttl SECS    age after which cached quotes are refreshed (default 3600)
+  --cache-info        print the cached quotes
+  --clear-cache       remove all cached quotes
+  -h, --help          print this help";
+
+enum Command {
+    Evaluate(Vec<String>),
+    File(PathBuf),
+    Csv(PathBuf),
+    CacheInfo,
+    ClearCache,
+    Help,
+}
+
+struct Options {
+    command: Command,
+    rates: Option<PathBuf>,
+    quotes: Option<PathBuf>,
+    holidays: Option<PathBuf>,
+    locale: Locale,
+    unit_style: UnitStyle,
+    json: bool,
+    annotate: bool,
+    watch: bool,
+    expr: Option<String>,
+    out: Option<PathBuf>,
+    column: String,
+    preferred_families: Vec<UnitFamily>,
+    offline: bool,
+    cache: PathBuf,
+    cache_ttl: TimeDelta,
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(env::args().skip(1).collect()) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let cache = QuoteCache::new(&options.cache, options.cache_ttl);
+
+    let res = match &options.command {
+        Command::Help => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Command::CacheInfo => cache_info(&cache),
+        Command::ClearCache => cache.clear().map_err(|e| e.to_string()),
+        Command::Evaluate(expression) => {
+            calculator(&options, cache).and_then(|mut c| evaluate(&mut c, &options, expression))
+        }
+        Command::File(path) => {
+            calculator(&options, cache).and_then(|mut c| evaluate_file(&mut c, &options, path))
+        }
+        Command::Csv(path) => {
+            calculator(&options, cache).and_then(|mut c| evaluate_csv(&mut c, &options, path))
+        }
+    };
+
+    match res {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse_args(args: Vec<String>) -> Result<Options, String> {
+    let mut options = Options {
+        command: Command::Evaluate(vec![]),
+        rates: None,
+        quotes: None,
+        holidays: None,
+        locale: Locale::En,
+        unit_style: UnitStyle::Symbol,
+        json: false,
+        annotate: false,
+        watch: false,
+        expr: None,
+        out: None,
+        column: String::from("result"),
+        preferred_families: vec![],
+        offline: false,
+        cache: default_cache_path(),
+        cache_ttl: TimeDelta::hours(1),
+    };
+
+    let mut expression = Vec::new();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("Missing value of {}", arg));
+
+        match arg.as_str() {
+            "--rates" => options.rates = Some(PathBuf::from(value()?)),
+            "--quotes" => options.quotes = Some(PathBuf::from(value()?)),
+            "--holidays" => options.holidays = Some(PathBuf::from(value()?)),
+            "--locale" => {
+                let code = value()?;
+                options.locale =
+                    Locale::from_code(&code).ok_or(format!("Invalid locale: '{}'", code))?;
+            }
+            "--unit-names" => options.unit_style = UnitStyle::Name,
+            "--json" if cfg!(feature = "json") => options.json = true,
+            "--json" => return Err(String::from("pc was built without the json feature")),
+            "--prefer" => {
+                for family in value()?.split(',') {
+                    options.preferred_families.push(
+                        family
+                            .trim()
+                            .parse()
+                            .map_err(|_| format!("Invalid unit family: '{}'", family))?,
+                    );
+                }
+            }
+            "--annotate" => options.annotate = true,
+            "--watch" => options.watch = true,
+            "--csv" => options.command = Command::Csv(PathBuf::from(value()?)),
+            "--expr" => options.expr = Some(value()?),
+            "--out" => options.out = Some(PathBuf::from(value()?)),
+            "--column" => options.column = value()?,
+            "--offline" => options.offline = true,
+            "--cache" => options.cache = PathBuf::from(value()?),
+            "--cache-ttl" => {
+                let secs = value()?;
+                let secs = secs
+                    .parse::<u32>()
+                    .map_err(|_| format!("Invalid cache TTL: '{}'", secs))?;
+                options.cache_ttl = TimeDelta::seconds(secs.into());
+            }
+            "--cache-info" => options.command = Command::CacheInfo,
+            "--clear-cache" => options.command = Command::ClearCache,
+            "-h" | "--help" => options.command = Command::Help,
+            _ => expression.push(arg),
+        }
+    }
+
+    if let (Command::Csv(_), None) = (&options.command, &options.expr) {
+        return Err(String::from("--csv requires --expr"));
+    }
+
+    if let Command::Evaluate(_) = options.command {
+        options.command = match &expression[..] {
+            [file] if file.ends_with(".calc") => Command::File(PathBuf::from(file)),
+            _ if options.annotate || options.watch => {
+                return Err(String::from("--annotate and --watch require a .calc file"))
+            }
+            _ => Command::Evaluate(expression),
+        };
+    }
+
+    Ok(options)
+}
+
+fn default_cache_path() -> PathBuf {
+    let dir = env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .unwrap_or_else(env::temp_dir);
+
+    dir.join("pepa-calculator").join("quotes.cache")
+}
+
+fn cache_info(cache: &QuoteCache) -> Result<(), String> {
+    println!(
+        "{} (ttl {}s)",
+        cache.path().display(),
+        cache.ttl().num_seconds()
+    );
+
+    for q in cache.entries().map_err(|e| e.to_string())? {
+        let Some(info) = q.cache else {
+            continue;
+        };
+
+        println!(
+            "{}\t{}{}\t{}\tstored {}{}",
+            q.symbol,
+            q.value,
+            q.unit
+                .map(|u| u.to_string_with_plural(&q.value))
+                .unwrap_or_default(),
+            q.provider,
+            info.stored.to_rfc3339(),
+            if info.expired { " (expired)" } else { "" }
+        );
+    }
+
+    Ok(())
+}
+
+fn calculator(options: &Options, cache: QuoteCache) -> Result<Calculator, String> {
+    let mut calculator = Calculator::new();
+    calculator.set_offline(options.offline);
+    calculator.set_locale(options.locale);
+    calculator.set_preferred_families(&options.preferred_families);
+    load_data(&mut calculator, options)?;
+
+    if let Some(quotes) = &options.quotes {
+        calculator.add_provider(Rc::new(CachedProvider::new(
+            Rc::new(FileProvider::new(quotes)),
+            cache,
+        )));
+    }
+
+    Ok(calculator)
+}
+
+/// Loads the exchange rates and holidays files, again when they change in watch mode.
+fn load_data(calculator: &mut Calculator, options: &Options) -> Result<(), String> {
+    if let Some(rates) = &options.rates {
+        calculator
+            .load_exchange_rates(rates)
+            .map_err(|e| e.to_string())?;
+    }
+
+    if let Some(holidays) = &options.holidays {
+        calculator
+            .load_holidays(holidays)
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+fn evaluate(
+    calculator: &mut Calculator,
+    options: &Options,
+    expression: &[String],
+) -> Result<(), String> {
+    if !expression.is_empty() {
+        compute(calculator, options, &expression.join(" "));
+        return Ok(());
+    }
+
+    for line in io::stdin().lock().lines() {
+        compute(calculator, options, &line.map_err(|e| e.to_string())?);
+    }
+
+    Ok(())
+}
+
+fn evaluate_file(
+    calculator: &mut Calculator,
+    options: &Options,
+    path: &Path,
+) -> Result<(), String> {
+    let data = [&options.rates, &options.holidays]
+        .into_iter()
+        .flatten()
+        .map(PathBuf::as_path)
+        .collect::<Vec<_>>();
+
+    let mut notebook = Notebook::default();
+    let mut annotated = None;
+
+    loop {
+        let file_modified = modified(&[path])?;
+        let data_modified = modified(&data)?;
+        match refresh(calculator, &mut notebook, &mut annotated, options, path) {
+            Ok(_) => {}
+            // the file is being replaced, it is read again when it is back
+            Err(_) if options.watch && modified(&[path])?.contains(&None) => {}
+            Err(e) => return Err(e),
+        }
+
+        if !options.watch {
+            return Ok(());
+        }
+
+        loop {
+            thread::sleep(WATCH_INTERVAL);
+
+            // an editor saving through a temporary file and a rename removes the file for a moment
+            let (data_now, file_now) = (modified(&data)?, modified(&[path])?);
+            if data_now.contains(&None) || file_now.contains(&None) {
+                continue;
+            }
+
+            if data_now != data_modified {
+                match load_data(calculator, options) {
+                    Ok(()) => {}
+                    Err(_) if modified(&data)?.contains(&None) => continue,
+                    Err(e) => return Err(e),
+                }
+                notebook.data_changed();
+                annotated = None;
+                break;
+            }
+
+            if file_now != file_modified {
+                break;
+            }
+        }
+
+        if !options.annotate && !options.json {
+            println!();
+        }
+    }
+}
+
+/// Modification times of the files, `None` for a missing file.
+fn modified(paths: &[&Path]) -> Result<Vec<Option<SystemTime>>, String> {
+    paths
+        .iter()
+        .map(|p| match fs::metadata(p).and_then(|m| m.modified()) {
+            Ok(t) => Ok(Some(t)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("{}: {}", p.display(), e)),
+        })
+        .collect()
+}
+
+/// Evaluates the changed lines of the file, returns false if the file has not changed
+/// since its last annotation.
+fn refresh(
+    calculator: &Calculator,
+    notebook: &mut Notebook,
+    annotated: &mut Option<String>,
+    options: &Options,
+    path: &Path,
+) -> Result<bool, String> {
+    let error = |e: io::Error| format!("{}: {}", path.display(), e);
+    let text = fs::read_to_string(path).map_err(error)?;
+
+    // own annotation of the file is not a change
+    if annotated.as_ref() == Some(&text) {
+        return Ok(false);
+    }
+
+    notebook.update(calculator, &text);
+
+    if options.annotate {
+        let text = notebook.annotate(options.locale, options.unit_style);
+        fs::write(path, &text).map_err(error)?;
+        *annotated = Some(text);
+    } else {
+        for statement in notebook.lines().iter().filter_map(|l| l.statement.as_ref()) {
+            print_statement(statement, options);
+        }
+    }
+
+    Ok(true)
+}
+
+fn evaluate_csv(calculator: &mut Calculator, options: &Options, path: &Path) -> Result<(), String> {
+    let table = CsvTable::from_file(path).map_err(|e| e.localize(options.locale))?;
+    let results = table.evaluate(calculator, options.expr.as_deref().unwrap_or_default());
+
+    // line of the row, the header is the first one
+    for (i, result) in results.iter().enumerate() {
+        if let Err(e) = result {
+            eprintln!(
+                "{}:{}: {}",
+                path.display(),
+                i + 2,
+                e.localize(options.locale)
+            );
+        }
+    }
+
+    let table = table.with_results(&options.column, &results);
+    match &options.out {
+        Some(out) => {
+            fs::write(out, table.to_string()).map_err(|e| format!("{}: {}", out.display(), e))
+        }
+        None => {
+            print!("{}", table);
+            Ok(())
+        }
+    }
+}
+
+fn compute(calculator: &mut Calculator, options: &Options, input: &str) {
+    let count = calculator.statements().len();
+    calculator.compute(input);
+
+    for statement in &calculator.statements()[count..] {
+        print_statement(statement, options);
+    }
+}
+
+fn print_statement(statement: &Statement, options: &Options) {
+    #[cfg(feature = "json")]
+    if options.json {
+        println!("{}", json_statement(statement, options));
+        return;
+    }
+
+    let locale = options.locale;
+    let mut notes = Vec::new();
+
+    if let Some(t) = statement.exchange_rates_timestamp {
+        notes.push(format!("exchange rates {}", t.to_rfc3339()));
+    }
+
+    if let Some(age) = statement.data_age() {
+        notes.push(format!(
+            "cached data {}s old{}",
+            age.num_seconds(),
+            if statement.is_stale() { ", stale" } else { "" }
+        ));
+    }
+
+    notes.extend(statement.warnings.iter().map(|w| w.localize(locale)));
+
+    let result = match &statement.result {
+        Some(Ok(n)) => locale.format(n, options.unit_style),
+        Some(Err(e)) => format!("{}: {}", locale.error_label(), e.localize(locale)),
+        None => String::new(),
+    };
+
+    if notes.is_empty() {
+        println!("{}", result);
+    } else {
+        println!("{}\t# {}", result, notes.join(", "));
+    }
+}
+
+/// Statement for scripts and editors, values are exact decimal strings:
+/// `{"request":"1 km in m","expression":"1→km→m","values":["1000"],"units":["m"],"formatted":"1000m","error":null,"warnings":[]}`
+#[cfg(feature = "json")]
+fn json_statement(statement: &Statement, options: &Options) -> serde_json::Value {
+    use pepa::NumericExpression;
+    use serde_json::json;
+
+    let locale = options.locale;
+    let error = |kind: &str, message: String| json!({ "kind": kind, "message": message });
+
+    let value = |n: &NumericExpression| match n {
+        NumericExpression::Number(n) => (json!(n), json!(null)),
+        NumericExpression::NumberWithUnit(n, u) => (json!(n), json!(u.to_string_with_plural(n))),
+        n => (json!(n.to_string()), json!(null)),
+    };
+
+    let (values, units) = match &statement.result {
+        Some(Ok(NumericExpression::MultipleNumbersWithUnit(values))) => values
+            .iter()
+            .map(|(n, u)| (json!(n), json!(u.map(|u| u.to_string_with_plural(n)))))
+            .unzip(),
+        // elements of a list, nested lists as text
+        Some(Ok(NumericExpression::List(l))) => l.iter().map(value).unzip(),
+        Some(Ok(n)) => {
+            let (v, u) = value(n);
+            (vec![v], vec![u])
+        }
+        _ => (vec![], vec![]),
+    };
+
+    json!({
+        "request": statement.request,
+        "expression": statement.expression.as_ref().ok().map(|e| e.explain()),
+        "values": values,
+        "units": units,
+        "formatted": match &statement.result {
+            Some(Ok(n)) => Some(locale.format(n, options.unit_style)),
+            _ => None,
+        },
+        "error": match (&statement.expression, &statement.result) {
+            (Err(e), _) => error(e.into(), e.localize(locale)),
+            (_, Some(Err(e))) => error(e.into(), e.localize(locale)),
+            _ => json!(null),
+        },
+        "warnings": statement
+            .warnings
+            .iter()
+            .map(|w| w.localize(locale))
+            .collect::<Vec<_>>(),
+    })
+}
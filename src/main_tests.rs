@@ -0,0 +1,120 @@
+use std::{env, fs};
+
+use pepa::{Calculator, Notebook};
+
+use crate::{load_data, modified, parse_args, refresh, Command};
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|a| a.to_string()).collect()
+}
+
+#[test]
+fn test_watch() {
+    let dir = env::temp_dir().join(format!("pepa-watch-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let (path, holidays) = (dir.join("notes.calc"), dir.join("holidays.txt"));
+    fs::write(&path, "1 km in m\nisworkday(2026-12-24)\n").unwrap();
+    fs::write(&holidays, "12-24").unwrap();
+
+    let options = parse_args(args(&[
+        "--annotate",
+        "--watch",
+        "--holidays",
+        holidays.to_str().unwrap(),
+        path.to_str().unwrap(),
+    ]))
+    .unwrap();
+    let mut calculator = Calculator::default();
+    load_data(&mut calculator, &options).unwrap();
+    let mut notebook = Notebook::default();
+    let mut annotated = None;
+
+    assert!(refresh(&calculator, &mut notebook, &mut annotated, &options, &path).unwrap());
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "1 km in m              = 1000m\nisworkday(2026-12-24)  = 0\n"
+    );
+
+    // own annotation is not a change
+    assert!(!refresh(&calculator, &mut notebook, &mut annotated, &options, &path).unwrap());
+
+    let text = fs::read_to_string(&path).unwrap().replace("1 km", "2 km");
+    fs::write(&path, text).unwrap();
+    assert!(refresh(&calculator, &mut notebook, &mut annotated, &options, &path).unwrap());
+    assert!(fs::read_to_string(&path)
+        .unwrap()
+        .starts_with("2 km in m              = 2000m\n"));
+
+    // changed holidays are loaded again and the lines using them recomputed
+    fs::write(&holidays, "12-25").unwrap();
+    load_data(&mut calculator, &options).unwrap();
+    notebook.data_changed();
+    assert!(refresh(&calculator, &mut notebook, &mut None, &options, &path).unwrap());
+    assert!(fs::read_to_string(&path)
+        .unwrap()
+        .ends_with("isworkday(2026-12-24)  = 1\n"));
+
+    // reloads do not add up in the calculator
+    assert!(calculator.statements().is_empty());
+
+    // a file replaced through a rename is missing for a moment, it is not an error
+    let saved = dir.join("notes.calc.tmp");
+    fs::rename(&path, &saved).unwrap();
+    assert_eq!(modified(&[&path]).unwrap(), [None]);
+    fs::rename(&saved, &path).unwrap();
+    assert!(modified(&[&path]).unwrap()[0].is_some());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_parse_args() {
+    let options = parse_args(args(&["--locale", "cs", "1", "+", "2"])).unwrap();
+    assert!(matches!(options.command, Command::Evaluate(e) if e == ["1", "+", "2"]));
+
+    let options = parse_args(args(&["--annotate", "notes.calc"])).unwrap();
+    assert!(matches!(options.command, Command::File(_)));
+
+    for a in [
+        &["--cache-ttl", "-1"][..],
+        &["--locale", "xx"],
+        &["--csv", "table.csv"],
+        &["--watch", "1 + 2"],
+    ] {
+        assert!(parse_args(args(a)).is_err(), "{:?}", a);
+    }
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_statement() {
+    use serde_json::json;
+
+    use crate::json_statement;
+
+    let options = parse_args(args(&["--json"])).unwrap();
+    let mut calculator = Calculator::default();
+
+    let statement = calculator.compute("1 km in m").unwrap();
+    assert_eq!(
+        json_statement(statement, &options),
+        json!({
+            "request": "1 km in m",
+            "expression": "1→km→m",
+            "values": ["1000"],
+            "units": ["m"],
+            "formatted": "1000m",
+            "error": null,
+            "warnings": [],
+        })
+    );
+
+    let statement = calculator.compute("1 / 0").unwrap();
+    let value = json_statement(statement, &options);
+    assert_eq!(value["request"], json!("1 / 0"));
+    assert_eq!(value["values"], json!([]));
+    assert_eq!(value["error"]["kind"], json!("division_by_zero"));
+
+    let statement = calculator.compute("max(1, 2) m").unwrap();
+    assert_eq!(json_statement(statement, &options)["units"], json!(["m"]));
+}
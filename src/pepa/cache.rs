@@ -0,0 +1,183 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::providers::{CacheInfo, DataProvider, Quote};
+use crate::units::Unit;
+use crate::{string, DataError, Decimal};
+
+/// Quotes stored on disk, entries older than TTL are refreshed from the provider.
+///
+/// Text format, one tab separated quote per line:
+/// `symbol value unit timestamp provider stored`, the unit is written with its family: `time:m`,
+/// `-` stands for no unit.
+#[derive(Debug, Clone)]
+pub struct QuoteCache {
+    path: PathBuf,
+    ttl: TimeDelta,
+}
+
+impl QuoteCache {
+    pub fn new(path: &Path, ttl: TimeDelta) -> QuoteCache {
+        QuoteCache {
+            path: path.to_path_buf(),
+            ttl,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn ttl(&self) -> TimeDelta {
+        self.ttl
+    }
+
+    /// All cached quotes, with the cache information filled in.
+    pub fn entries(&self) -> Result<Vec<Quote>, DataError> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(self.io_error(e)),
+        };
+
+        let now = Utc::now();
+        let mut entries = Vec::new();
+
+        for (i, line) in content.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || DataError::InvalidCache(i + 1, string!(line));
+
+            let [symbol, value, unit, timestamp, provider, stored] =
+                line.split('\t').collect::<Vec<_>>()[..]
+            else {
+                return Err(invalid());
+            };
+
+            let unit = match unit {
+                "-" => None,
+                unit => Some(Unit::from_id(unit).ok_or_else(invalid)?),
+            };
+
+            let parse_time = |t: &str| {
+                DateTime::parse_from_rfc3339(t)
+                    .map(|t| t.with_timezone(&Utc))
+                    .map_err(|_| invalid())
+            };
+
+            let stored = parse_time(stored)?;
+
+            entries.push(Quote {
+                symbol: string!(symbol),
+                value: value.parse::<Decimal>().map_err(|_| invalid())?,
+                unit,
+                timestamp: parse_time(timestamp)?,
+                provider: string!(provider),
+                cache: Some(CacheInfo {
+                    stored,
+                    expired: now - stored >= self.ttl,
+                }),
+            });
+        }
+
+        Ok(entries)
+    }
+
+    pub fn get(&self, symbol: &str) -> Result<Option<Quote>, DataError> {
+        Ok(self.entries()?.into_iter().find(|q| q.symbol == symbol))
+    }
+
+    pub fn store(&self, quote: &Quote) -> Result<(), DataError> {
+        let mut entries = self.entries()?;
+        entries.retain(|q| q.symbol != quote.symbol);
+
+        let mut quote = quote.clone();
+        quote.cache = Some(CacheInfo {
+            stored: Utc::now(),
+            expired: false,
+        });
+        entries.push(quote);
+
+        let mut content = string!("# symbol\tvalue\tunit\ttimestamp\tprovider\tstored\n");
+        for q in entries {
+            content += &format!(
+                "{}\t{}\t{}\t{}\t{}\t{}\n",
+                q.symbol,
+                q.value,
+                q.unit.map(|u| u.id()).unwrap_or(string!("-")),
+                q.timestamp.to_rfc3339(),
+                q.provider,
+                q.cache.map(|c| c.stored).unwrap_or(Utc::now()).to_rfc3339()
+            );
+        }
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(|e| self.io_error(e))?;
+        }
+
+        fs::write(&self.path, content).map_err(|e| self.io_error(e))
+    }
+
+    pub fn clear(&self) -> Result<(), DataError> {
+        match fs::remove_file(&self.path) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(self.io_error(e)),
+            _ => Ok(()),
+        }
+    }
+
+    fn io_error(&self, e: std::io::Error) -> DataError {
+        DataError::Io(self.path.display().to_string(), e.to_string())
+    }
+}
+
+/// Provider serving quotes from the cache, the wrapped provider is asked only for expired
+/// or missing entries. Expired entries are still served when the provider fails.
+#[derive(Debug, Clone)]
+pub struct CachedProvider {
+    provider: Rc<dyn DataProvider>,
+    cache: QuoteCache,
+}
+
+impl CachedProvider {
+    pub fn new(provider: Rc<dyn DataProvider>, cache: QuoteCache) -> CachedProvider {
+        CachedProvider { provider, cache }
+    }
+}
+
+impl DataProvider for CachedProvider {
+    fn name(&self) -> String {
+        self.provider.name()
+    }
+
+    fn quote(&self, symbol: &str) -> Result<Option<Quote>, DataError> {
+        let cached = self.cache.get(symbol)?;
+
+        if let Some(q) = &cached {
+            if q.cache.as_ref().is_some_and(|c| !c.expired) {
+                return Ok(cached);
+            }
+        }
+
+        match self.provider.quote(symbol) {
+            Ok(Some(q)) => {
+                self.cache.store(&q)?;
+                Ok(Some(q))
+            }
+            Ok(None) => Ok(None),
+            Err(e) => match cached {
+                Some(q) => Ok(Some(q)),
+                None => Err(e),
+            },
+        }
+    }
+
+    fn cached_quote(&self, symbol: &str) -> Result<Option<Quote>, DataError> {
+        self.cache.get(symbol)
+    }
+}
@@ -0,0 +1,135 @@
+use std::env;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use chrono::{TimeDelta, TimeZone, Utc};
+use rust_decimal_macros::dec;
+
+use crate::cache::{CachedProvider, QuoteCache};
+use crate::providers::{DataProvider, FileProvider, MockProvider};
+use crate::units::{Currency, Length, Time, Unit};
+use crate::Calculator;
+
+fn cache_path(name: &str) -> PathBuf {
+    let path = env::temp_dir()
+        .join(format!("pepa-cache-{}", std::process::id()))
+        .join(name);
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn mock() -> Rc<MockProvider> {
+    Rc::new(MockProvider::new().with_quote(
+        "AAPL",
+        dec!(230),
+        Some(Unit::Currency(Currency::UsDollar)),
+        Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap(),
+    ))
+}
+
+#[test]
+fn test_cache() {
+    let cache = QuoteCache::new(&cache_path("cache"), TimeDelta::hours(1));
+    let mock = mock();
+
+    // first run fetches from the provider
+    let mut calculator = Calculator::default();
+    calculator.add_provider(Rc::new(CachedProvider::new(mock.clone(), cache.clone())));
+    let statement = calculator.compute("10 AAPL").unwrap();
+    assert!(statement.result.as_ref().unwrap().is_ok());
+    assert_eq!(statement.data_age(), None);
+    assert_eq!(mock.requests(), 1);
+
+    // second run is served from the cache
+    let mut calculator = Calculator::default();
+    calculator.add_provider(Rc::new(CachedProvider::new(mock.clone(), cache.clone())));
+    let statement = calculator.compute("10 AAPL").unwrap();
+    assert_eq!(
+        statement
+            .result
+            .as_ref()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .values(),
+        vec![(dec!(2300), Some(Unit::Currency(Currency::UsDollar)))]
+    );
+    assert!(statement.data_age().unwrap() < TimeDelta::hours(1));
+    assert!(!statement.is_stale());
+    assert_eq!(mock.requests(), 1);
+
+    let entries = cache.entries().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].symbol, "AAPL");
+    assert_eq!(entries[0].provider, "mock");
+
+    cache.clear().unwrap();
+    assert!(cache.entries().unwrap().is_empty());
+    cache.clear().unwrap();
+}
+
+#[test]
+fn test_cache_expiry() {
+    let cache = QuoteCache::new(&cache_path("expiry"), TimeDelta::zero());
+    let mock = mock();
+
+    let provider = CachedProvider::new(mock.clone(), cache.clone());
+    provider.quote("AAPL").unwrap();
+    provider.quote("AAPL").unwrap();
+    assert_eq!(mock.requests(), 2);
+
+    // provider fails - expired entry is served
+    let failing = Rc::new(FileProvider::new(&cache_path("missing")));
+    let mut calculator = Calculator::default();
+    calculator.add_provider(Rc::new(CachedProvider::new(failing, cache.clone())));
+    let statement = calculator.compute("AAPL").unwrap();
+    assert!(statement.result.as_ref().unwrap().is_ok());
+    assert!(statement.is_stale());
+    assert!(statement.data_age().is_some());
+
+    cache.clear().unwrap();
+    let statement = calculator.compute("AAPL").unwrap();
+    assert!(statement.result.as_ref().unwrap().is_err());
+}
+
+#[test]
+fn test_cache_offline() {
+    let cache = QuoteCache::new(&cache_path("offline"), TimeDelta::zero());
+    let mock = mock();
+
+    let mut calculator = Calculator::default();
+    calculator.add_provider(Rc::new(CachedProvider::new(mock.clone(), cache.clone())));
+    calculator.set_offline(true);
+
+    let statement = calculator.compute("AAPL").unwrap();
+    assert!(statement.result.as_ref().unwrap().is_err());
+
+    cache.store(&mock.quote("AAPL").unwrap().unwrap()).unwrap();
+    assert_eq!(mock.requests(), 1);
+
+    let statement = calculator.compute("AAPL").unwrap();
+    assert!(statement.result.as_ref().unwrap().is_ok());
+    assert!(statement.is_stale());
+    assert_eq!(mock.requests(), 1);
+
+    cache.clear().unwrap();
+}
+
+#[test]
+fn test_cache_units() {
+    let cache = QuoteCache::new(&cache_path("units"), TimeDelta::hours(1));
+    let timestamp = Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap();
+
+    // `m` is a symbol of a metre and of a minute
+    for (symbol, unit) in [
+        ("DISTANCE", Unit::Length(Length::Meter(None))),
+        ("DURATION", Unit::Time(Time::Minute)),
+        ("PRICE", Unit::Currency(Currency::UsDollar)),
+    ] {
+        let mock = MockProvider::new().with_quote(symbol, dec!(5), Some(unit), timestamp);
+        cache.store(&mock.quote(symbol).unwrap().unwrap()).unwrap();
+        assert_eq!(cache.get(symbol).unwrap().unwrap().unit, Some(unit));
+    }
+
+    cache.clear().unwrap();
+}
@@ -0,0 +1,386 @@
+use itertools::Itertools;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::MathematicalOps;
+use rust_decimal_macros::dec;
+
+use crate::compute::{compute, invoke_operator, Bindings};
+use crate::context::Context;
+use crate::expression::{Expression, ExpressionToken, NumericExpression};
+use crate::operators::operators;
+use crate::units::Unit;
+use crate::{ComputeError, Decimal};
+
+/// Significant digits of an integral or a derivative, the rest is the error of the method.
+const SIGNIFICANT_DIGITS: u32 = 15;
+
+/// Halvings of an integral's interval, a non-smooth body does not need more.
+const MAX_DEPTH: usize = 14;
+
+/// Halvings always done, samples of a periodic body can miss its shape: `sin(x)` from 0 to 2π
+const MIN_DEPTH: usize = 3;
+
+const MAX_TERMS: i64 = 1_000_000;
+
+/// Relative difference of the one-sided limits still read as the same limit.
+const LIMIT_TOLERANCE: Decimal = dec!(0.0000000001);
+
+/// Functions of an expression body with a bound variable, evaluated for its values:
+/// `integrate(x^2, x, 0, 3)`, `derive(sin(x), x, 1)`, `limit(sin(x) / x, x, 0)`,
+/// `sum(k^2, k, 1, 100)`, `prod(k, k, 1, 5)`
+///
+/// `None` for other parameters, these are computed: `sum(1, 2, 3)`
+pub(super) fn invoke_function(
+    name: &str,
+    params: &ExpressionToken,
+    ctx: &Context,
+) -> Option<Result<NumericExpression, ComputeError>> {
+    let arity = match name {
+        "integrate" | "sum" | "prod" => 4,
+        "derive" | "limit" => 3,
+        _ => return None,
+    };
+
+    let ExpressionToken::List(params) = params else {
+        return None;
+    };
+
+    let [body, variable, bounds @ ..] = &params[..] else {
+        return None;
+    };
+
+    let [ExpressionToken::Symbol(variable)] = &variable.tokens[..] else {
+        return None;
+    };
+
+    if params.len() != arity {
+        return None;
+    }
+
+    let invalid =
+        || ComputeError::InvalidParametersForFunction(name.to_owned(), params.iter().join(", "));
+
+    let res = bounds
+        .iter()
+        .map(|b| compute(b, ctx))
+        .collect::<Result<Vec<_>, _>>()
+        .and_then(|bounds| {
+            let mut body = Body {
+                expression: body,
+                variable: vec![variable.clone()],
+                unit: bounds
+                    .iter()
+                    .find_map(|b| b.units().first().copied().flatten()),
+                bindings: Bindings::new(ctx),
+            };
+
+            let bounds = bounds
+                .iter()
+                .map(|b| body.bindings.magnitude(b, body.unit))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(invalid)?;
+
+            match (name, &bounds[..]) {
+                ("integrate", [a, b]) => body.integrate(*a, *b, invalid),
+                ("derive", [x]) => body.derive(*x, invalid),
+                ("limit", [x]) => body.limit(*x),
+                ("sum", [a, b]) => body.fold('+', Decimal::ZERO, *a, *b, invalid),
+                ("prod", [a, b]) => body.fold('*', Decimal::ONE, *a, *b, invalid),
+                _ => Err(invalid()),
+            }
+        });
+
+    Some(res)
+}
+
+/// Expression computed with the variable bound to a value in the unit of the bounds.
+struct Body<'a> {
+    expression: &'a Expression,
+    variable: Vec<String>,
+    unit: Option<Unit>,
+    bindings: Bindings<'a>,
+}
+
+impl Body<'_> {
+    fn at(&mut self, x: Decimal) -> Result<NumericExpression, ComputeError> {
+        let value = NumericExpression::with_unit(x, self.unit);
+        self.bindings
+            .compute(self.expression, &self.variable, &[value])
+    }
+
+    /// Value of the body in the unit of its first value.
+    fn value_at(&mut self, x: Decimal, unit: Option<Unit>) -> Result<Decimal, ComputeError> {
+        let n = self.at(x)?;
+        self.bindings
+            .magnitude(&n, unit)
+            .ok_or(ComputeError::InvalidExpression(n.to_string()))
+    }
+
+    /// Adaptive Simpson's rule, the result has the unit of the body or of the bounds:
+    /// `integrate(5 km, x, 0, 2)`, `integrate(2, x, 0 km, 1 km)`. Both would be a compound unit.
+    fn integrate(
+        &mut self,
+        a: Decimal,
+        b: Decimal,
+        invalid: impl Fn() -> ComputeError,
+    ) -> Result<NumericExpression, ComputeError> {
+        let first = self.at(a)?;
+        let unit = first.units().first().copied().flatten();
+        let result_unit = match (unit, self.unit) {
+            (unit, None) => unit,
+            (None, bounds) => bounds,
+            _ => return Err(invalid()),
+        };
+        let mut largest = Decimal::ZERO;
+        let mut f = |x: Decimal| {
+            let v = self.value_at(x, unit)?;
+            largest = largest.max(v.abs());
+            Ok(v)
+        };
+
+        let middle = checked(a.checked_add(b))? / Decimal::TWO;
+        let (fa, fm, fb) = (f(a)?, f(middle)?, f(b)?);
+        let width = checked(b.checked_sub(a))?;
+        let whole = simpson_rule(width, (fa, fm, fb))?;
+        let tolerance = dec!(0.000000000000000001) * whole.abs().max(Decimal::ONE);
+
+        let (v, error) = simpson(&mut f, (a, b), (fa, fm, fb), whole, tolerance, 0)?;
+        let scale = checked(largest.checked_mul(width.abs()))?;
+        // the error of the halvings is larger than the integral, not even its first digit is known
+        if error >= v.abs().max(scale * Decimal::new(1, SIGNIFICANT_DIGITS)) {
+            return Err(ComputeError::NoIntegral(self.expression.to_string()));
+        }
+
+        Ok(NumericExpression::with_unit(
+            round_to_error(round(v, scale), error),
+            result_unit,
+        ))
+    }
+
+    /// Central differences improved by Richardson extrapolation, errors of the steps cancel out.
+    ///
+    /// A body in the unit of the variable changes by a number: `derive(2x, x, 1 km)` => `2`
+    fn derive(
+        &mut self,
+        x: Decimal,
+        invalid: impl Fn() -> ComputeError,
+    ) -> Result<NumericExpression, ComputeError> {
+        let first = self.at(x)?;
+        let unit = first.units().first().copied().flatten();
+        let result_unit = match (unit, self.unit) {
+            (unit, None) => unit,
+            (Some(unit), Some(variable)) if unit == variable => None,
+            _ => return Err(invalid()),
+        };
+        let scale = self.value_at(x, unit)?.abs() / x.abs().max(Decimal::ONE);
+        let mut f = |x: Decimal| self.value_at(x, unit);
+
+        // rows of the extrapolations, each with a half step of the previous one
+        let mut h = dec!(0.01) * x.abs().max(Decimal::ONE);
+        let mut table: Vec<Decimal> = Vec::new();
+
+        for _ in 0..5 {
+            let (right, left) = (checked(x.checked_add(h))?, checked(x.checked_sub(h))?);
+            let difference = checked(f(right)?.checked_sub(f(left)?))?;
+            let mut row = vec![checked(difference.checked_div(Decimal::TWO * h))?];
+            let mut factor = Decimal::ONE;
+            for previous in table.iter() {
+                factor *= dec!(4);
+                let last = row[row.len() - 1];
+                let change = checked(last.checked_sub(*previous))? / (factor - Decimal::ONE);
+                row.push(checked(last.checked_add(change))?);
+            }
+
+            table = row;
+            h /= Decimal::TWO;
+        }
+
+        Ok(NumericExpression::with_unit(
+            round(table[table.len() - 1], scale),
+            result_unit,
+        ))
+    }
+
+    /// Values of the body nearing the point from both sides, extrapolated to a zero step:
+    /// `limit(sin(x) / x, x, 0)` => `1`. A side without values is skipped: `limit(sqrt(x)^2 / x, x, 0)`
+    fn limit(&mut self, x: Decimal) -> Result<NumericExpression, ComputeError> {
+        let no_limit = || ComputeError::NoLimit(self.expression.to_string());
+        let h = dec!(0.01) * x.abs().max(Decimal::ONE);
+
+        let points = [checked(x.checked_add(h))?, checked(x.checked_sub(h))?];
+
+        // the unit of the body is taken from the side with values
+        let unit = points
+            .into_iter()
+            .find_map(|p| {
+                self.at(p)
+                    .ok()
+                    .map(|n| n.units().first().copied().flatten())
+            })
+            .ok_or_else(no_limit)?;
+
+        let sides = [h, -h]
+            .into_iter()
+            .filter_map(|h| self.one_sided_limit(x, h, unit))
+            .collect::<Vec<_>>();
+
+        let value = match sides[..] {
+            [v] => v,
+            [right, left] if equal(right, left) => right / Decimal::TWO + left / Decimal::TWO,
+            _ => return Err(no_limit()),
+        };
+
+        let scale = self.value_at(points[0], unit).unwrap_or(value).abs();
+        Ok(NumericExpression::with_unit(round(value, scale), unit))
+    }
+
+    /// Richardson extrapolation of the values for halved steps, `None` if they do not converge.
+    fn one_sided_limit(
+        &mut self,
+        x: Decimal,
+        mut h: Decimal,
+        unit: Option<Unit>,
+    ) -> Option<Decimal> {
+        let mut table: Vec<Decimal> = Vec::new();
+        let mut previous = None;
+
+        for _ in 0..8 {
+            let mut row = vec![self.value_at(x + h, unit).ok()?];
+            let mut factor = Decimal::ONE;
+            for previous in table.iter() {
+                factor = factor.checked_mul(Decimal::TWO)?;
+                let last = row[row.len() - 1];
+                row.push(last.checked_add((last - previous).checked_div(factor - Decimal::ONE)?)?);
+            }
+
+            previous = table.last().copied();
+            table = row;
+            h /= Decimal::TWO;
+        }
+
+        let last = table[table.len() - 1];
+        previous.filter(|p| equal(*p, last)).map(|_| last)
+    }
+
+    /// Sum or product of the values of the body for the integers from `a` to `b`.
+    fn fold(
+        &mut self,
+        operator: char,
+        empty: Decimal,
+        a: Decimal,
+        b: Decimal,
+        invalid: impl Fn() -> ComputeError,
+    ) -> Result<NumericExpression, ComputeError> {
+        let (Some(a), Some(b)) = (integer(a), integer(b)) else {
+            return Err(invalid());
+        };
+
+        if b - a >= MAX_TERMS {
+            return Err(invalid());
+        }
+
+        let o = &operators()[&operator];
+        let mut res = None;
+
+        for k in a..=b {
+            let n = self.at(Decimal::from(k))?;
+            res = Some(match res {
+                Some(r) => invoke_operator(o, vec![r, n], self.bindings.context())?,
+                None => n,
+            });
+        }
+
+        Ok(res.unwrap_or(NumericExpression::Number(empty)))
+    }
+}
+
+/// Integral of the interval and the estimate of its error.
+fn simpson(
+    f: &mut impl FnMut(Decimal) -> Result<Decimal, ComputeError>,
+    (a, b): (Decimal, Decimal),
+    (fa, fm, fb): (Decimal, Decimal, Decimal),
+    whole: Decimal,
+    tolerance: Decimal,
+    depth: usize,
+) -> Result<(Decimal, Decimal), ComputeError> {
+    let m = checked(a.checked_add(b))? / Decimal::TWO;
+    let (lm, rm) = (
+        checked(a.checked_add(m))? / Decimal::TWO,
+        checked(m.checked_add(b))? / Decimal::TWO,
+    );
+    let (flm, frm) = (f(lm)?, f(rm)?);
+
+    let left = simpson_rule(checked(m.checked_sub(a))?, (fa, flm, fm))?;
+    let right = simpson_rule(checked(b.checked_sub(m))?, (fm, frm, fb))?;
+    let both = checked(left.checked_add(right))?;
+    let difference = checked(both.checked_sub(whole))?;
+
+    if depth >= MAX_DEPTH || (depth >= MIN_DEPTH && difference.abs() <= dec!(15) * tolerance) {
+        return Ok((
+            checked(both.checked_add(difference / dec!(15)))?,
+            difference.abs(),
+        ));
+    }
+
+    let tolerance = tolerance / Decimal::TWO;
+    let (l, l_error) = simpson(f, (a, m), (fa, flm, fm), left, tolerance, depth + 1)?;
+    let (r, r_error) = simpson(f, (m, b), (fm, frm, fb), right, tolerance, depth + 1)?;
+    Ok((
+        checked(l.checked_add(r))?,
+        checked(l_error.checked_add(r_error))?,
+    ))
+}
+
+/// Simpson's rule for the values at the ends and the middle of an interval.
+fn simpson_rule(
+    width: Decimal,
+    (fa, fm, fb): (Decimal, Decimal, Decimal),
+) -> Result<Decimal, ComputeError> {
+    checked(
+        dec!(4)
+            .checked_mul(fm)
+            .and_then(|m| m.checked_add(fa))
+            .and_then(|s| s.checked_add(fb))
+            .and_then(|s| s.checked_mul(width / dec!(6))),
+    )
+}
+
+/// Significant digits of the larger of the value and the scale of the computation, the error is
+/// relative to the values of the body: `integrate(sin(x), x, 0, 2*PI)` => `0`
+fn round(v: Decimal, scale: Decimal) -> Decimal {
+    let scale = scale.max(v.abs());
+    let Some(magnitude) = scale.checked_log10().and_then(|l| l.floor().to_i64()) else {
+        return v.normalize();
+    };
+
+    match u32::try_from(i64::from(SIGNIFICANT_DIGITS) - 1 - magnitude) {
+        Ok(dp) => v.round_dp(dp),
+        Err(_) => v.round_sf(SIGNIFICANT_DIGITS).unwrap_or(v),
+    }
+    .normalize()
+}
+
+/// Digits above the error of the method: `integrate(sqrt(x), x, 0, 1)` => `0.66666666`
+fn round_to_error(v: Decimal, error: Decimal) -> Decimal {
+    let Some(magnitude) = error.checked_log10().and_then(|l| l.floor().to_i64()) else {
+        return v;
+    };
+
+    match u32::try_from(-1 - magnitude) {
+        Ok(dp) if dp < v.scale() => v.round_dp(dp).normalize(),
+        Ok(_) => v,
+        Err(_) => v.round_dp(0).normalize(),
+    }
+}
+
+fn equal(a: Decimal, b: Decimal) -> bool {
+    a.checked_sub(b)
+        .is_some_and(|d| d.abs() <= a.abs().max(b.abs()).max(Decimal::ONE) * LIMIT_TOLERANCE)
+}
+
+fn checked(n: Option<Decimal>) -> Result<Decimal, ComputeError> {
+    n.ok_or(ComputeError::ArithmeticOverflow)
+}
+
+fn integer(n: Decimal) -> Option<i64> {
+    n.to_i64().filter(|_| n.fract().is_zero())
+}
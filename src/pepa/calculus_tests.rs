@@ -0,0 +1,132 @@
+use crate::{Calculator, ComputeError};
+
+fn compute(expression: &str) -> Result<String, ComputeError> {
+    let mut calculator = Calculator::default();
+    let statement = calculator.compute(expression).unwrap();
+
+    match &statement.result {
+        Some(r) => r.as_ref().map(|n| n.to_string()).map_err(|e| e.clone()),
+        None => panic!("{:?}: no result", expression),
+    }
+}
+
+#[test]
+fn test_integrate() {
+    for (expression, expected) in [
+        ("integrate(x^2, x, 0, 3)", "9"),
+        ("integrate(x^2, x, 3, 0)", "-9"),
+        ("integrate(z^2, z, 0, 1)", "0.33333333333333"),
+        ("integrate(sin(x), x, 0, PI)", "2"),
+        ("integrate(sin(x), x, 0, 2 * PI)", "0"),
+        ("integrate(1 / x, x, 1, E)", "1"),
+        ("integrate(E^x, x, 0, 1)", "1.71828182845905"),
+        ("integrate(x^2, x, 0, 3) + 1", "10"),
+        ("integrate(5 km, x, 0, 2)", "10km"),
+        ("integrate(2, x, 0 km, 1 km)", "2km"),
+        // rounded to the error of the method
+        ("integrate(sqrt(x), x, 0, 1)", "0.66666666"),
+    ] {
+        assert_eq!(compute(expression).unwrap(), expected, "{}", expression);
+    }
+
+    assert!(matches!(
+        compute("integrate(1 / x, x, 0, 1)"),
+        Err(ComputeError::DivisionByZero)
+    ));
+    // km times km is not a unit
+    assert!(matches!(
+        compute("integrate(x, x, 0, 1 km)"),
+        Err(ComputeError::InvalidParametersForFunction(..))
+    ));
+    assert!(matches!(
+        compute("integrate(sin(x), x, 0, 1000000)"),
+        Err(ComputeError::NoIntegral(_))
+    ));
+
+    for expression in [
+        "integrate(x^2, x, 0, 100000000000000)",
+        "integrate(x, x, 0, 79228162514264337593543950335)",
+        "derive(x, x, 79228162514264337593543950335)",
+        "limit(x, x, 79228162514264337593543950335)",
+    ] {
+        assert!(
+            matches!(compute(expression), Err(ComputeError::ArithmeticOverflow)),
+            "{}",
+            expression
+        );
+    }
+}
+
+#[test]
+fn test_derive() {
+    for (expression, expected) in [
+        ("derive(x^2, x, 3)", "6"),
+        ("derive(x^3, x, 2)", "12"),
+        ("derive(sin(x), x, 1)", "0.54030230586814"),
+        ("derive(sin(x), x, PI / 2)", "0"),
+        ("derive(ln(x), x, 0.5)", "2"),
+        ("derive(E^x, x, 1)", "2.71828182845905"),
+        ("derive(2x, x, 1 km)", "2"),
+    ] {
+        assert_eq!(compute(expression).unwrap(), expected, "{}", expression);
+    }
+
+    assert!(matches!(
+        compute("derive(5 km, x, 1 h)"),
+        Err(ComputeError::InvalidParametersForFunction(..))
+    ));
+}
+
+#[test]
+fn test_limit() {
+    for (expression, expected) in [
+        ("limit(sin(x) / x, x, 0)", "1"),
+        ("limit((x^2 - 1) / (x - 1), x, 1)", "2"),
+        ("limit((1 + x)^(1 / x), x, 0)", "2.71828182845905"),
+        ("limit(x^2, x, 3)", "9"),
+        ("limit(x, x, 2 km)", "2km"),
+        // only one side has values
+        ("limit(sqrt(x)^2 / x, x, 0)", "1"),
+    ] {
+        assert_eq!(compute(expression).unwrap(), expected, "{}", expression);
+    }
+
+    for expression in [
+        "limit(1 / x, x, 0)",
+        "limit(1 / x^2, x, 0)",
+        "limit(abs(x) / x, x, 0)",
+    ] {
+        assert!(
+            matches!(compute(expression), Err(ComputeError::NoLimit(_))),
+            "{}",
+            expression
+        );
+    }
+}
+
+#[test]
+fn test_sum_and_prod() {
+    for (expression, expected) in [
+        ("sum(k^2, k, 1, 100)", "338350"),
+        ("sum(k, k, 1, 0)", "0"),
+        ("sum(k * 2 km, k, 1, 3)", "12km"),
+        ("sum((k, 2 * k), k, 1, 3)", "(6, 12)"),
+        ("prod(k, k, 1, 5)", "120"),
+        ("prod(k, k, 1, 0)", "1"),
+        // values, not a body with a bound variable
+        ("sum(1, 2, 3)", "6"),
+        ("prod(2, 3, 4)", "24"),
+        ("prod((2, 3), 4)", "24"),
+    ] {
+        assert_eq!(compute(expression).unwrap(), expected, "{}", expression);
+    }
+
+    assert!(matches!(
+        compute("sum(k^2, k, 1.5, 3)"),
+        Err(ComputeError::InvalidParametersForFunction(..))
+    ));
+    assert!(matches!(
+        compute("derive(x, z, 1)"),
+        Err(ComputeError::UnknownSymbol(s)) if s == "x"
+    ));
+}
@@ -0,0 +1,476 @@
+use std::fmt::{Display, Formatter};
+
+use itertools::Itertools;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::MathematicalOps;
+
+use crate::expression::NumericExpression;
+use crate::units::{Angle, Unit};
+use crate::{ComputeError, Decimal};
+
+/// Significant digits of the sine and cosine, the rest is their error: `polar(2, 90°)` => `2i`
+const SIGNIFICANT_DIGITS: u32 = 27;
+
+/// Significant digits of a shown magnitude and phase, errors of the operations are not shown:
+/// `polar(10, 30°) * polar(2, 15°)` => `20∠45°`
+const SHOWN_DIGITS: u32 = 24;
+
+/// Complex number: `3+4i`, shown in the polar form with the phase in an angle unit: `5∠90°`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Complex {
+    re: Decimal,
+    im: Decimal,
+    /// unit of the phase of the polar form, the rectangular form if `None`
+    polar: Option<Angle>,
+}
+
+impl Complex {
+    /// Imaginary unit: `i`, `j`
+    pub const I: Complex = Complex {
+        re: Decimal::ZERO,
+        im: Decimal::ONE,
+        polar: None,
+    };
+
+    pub fn new(re: Decimal, im: Decimal) -> Complex {
+        Complex {
+            re: re.normalize(),
+            im: im.normalize(),
+            polar: None,
+        }
+    }
+
+    /// Number of the magnitude and the phase in radians, shown in the polar form.
+    pub fn from_polar(magnitude: Decimal, phase: Decimal, unit: Angle) -> Complex {
+        let re = round(magnitude * phase.cos(), magnitude.abs(), SIGNIFICANT_DIGITS);
+        let im = round(magnitude * phase.sin(), magnitude.abs(), SIGNIFICANT_DIGITS);
+        Complex::new(re, im).to_polar(unit)
+    }
+
+    pub fn re(&self) -> Decimal {
+        self.re
+    }
+
+    pub fn im(&self) -> Decimal {
+        self.im
+    }
+
+    /// Magnitude, scaled by the larger part so the squares do not overflow.
+    pub fn abs(&self) -> Result<Decimal, ComputeError> {
+        let (a, b) = (
+            self.re.abs().max(self.im.abs()),
+            self.re.abs().min(self.im.abs()),
+        );
+        if a.is_zero() {
+            return Ok(Decimal::ZERO);
+        }
+
+        let ratio = b / a;
+        let root = (Decimal::ONE + ratio * ratio)
+            .sqrt()
+            .unwrap_or(Decimal::ONE);
+
+        a.checked_mul(root)
+            .map(|m| m.normalize())
+            .ok_or(ComputeError::ArithmeticOverflow)
+    }
+
+    /// Phase in the angle unit, from -π to π radians: `arg(1 + i)` => `45°`
+    pub fn arg(&self, unit: Angle) -> Decimal {
+        let phase = atan2(self.im, self.re) / unit.reference_unit_multiplier();
+        round(phase, Decimal::ONE, SHOWN_DIGITS)
+    }
+
+    pub fn conj(&self) -> Complex {
+        Complex {
+            im: -self.im,
+            ..*self
+        }
+    }
+
+    pub fn to_polar(self, unit: Angle) -> Complex {
+        Complex {
+            polar: Some(unit),
+            ..self
+        }
+    }
+
+    pub fn to_rectangular(self) -> Complex {
+        Complex {
+            polar: None,
+            ..self
+        }
+    }
+
+    pub fn add(&self, other: &Complex) -> Result<Complex, ComputeError> {
+        self.with(other, |a, b| {
+            Some((a.re.checked_add(b.re)?, a.im.checked_add(b.im)?))
+        })
+    }
+
+    pub fn sub(&self, other: &Complex) -> Result<Complex, ComputeError> {
+        self.with(other, |a, b| {
+            Some((a.re.checked_sub(b.re)?, a.im.checked_sub(b.im)?))
+        })
+    }
+
+    pub fn mul(&self, other: &Complex) -> Result<Complex, ComputeError> {
+        self.with(other, |a, b| {
+            Some((
+                a.re.checked_mul(b.re)?
+                    .checked_sub(a.im.checked_mul(b.im)?)?,
+                a.re.checked_mul(b.im)?
+                    .checked_add(a.im.checked_mul(b.re)?)?,
+            ))
+        })
+    }
+
+    pub fn div(&self, other: &Complex) -> Result<Complex, ComputeError> {
+        if other.re.is_zero() && other.im.is_zero() {
+            return Err(ComputeError::DivisionByZero);
+        }
+
+        self.with(other, |a, b| {
+            let d =
+                b.re.checked_mul(b.re)?
+                    .checked_add(b.im.checked_mul(b.im)?)?;
+            let re =
+                a.re.checked_mul(b.re)?
+                    .checked_add(a.im.checked_mul(b.im)?)?;
+            let im =
+                a.im.checked_mul(b.re)?
+                    .checked_sub(a.re.checked_mul(b.im)?)?;
+            Some((re.checked_div(d)?, im.checked_div(d)?))
+        })
+    }
+
+    /// Integer powers are exact, others are `exp(w * ln(z))`: `i^0.5`
+    pub fn pow(&self, w: &Complex) -> Result<Complex, ComputeError> {
+        if w.im.is_zero() && w.re.fract().is_zero() {
+            if let Some(n) = w.re.to_i64() {
+                return self.powi(n);
+            }
+        }
+
+        if self.re.is_zero() && self.im.is_zero() {
+            return if w.re > Decimal::ZERO {
+                Ok(*self)
+            } else {
+                Err(ComputeError::DivisionByZero)
+            };
+        }
+
+        let ln = Complex::new(
+            self.abs()?
+                .checked_ln()
+                .ok_or(ComputeError::ArithmeticOverflow)?,
+            atan2(self.im, self.re),
+        );
+        let exponent = w.mul(&ln)?;
+        let magnitude = exponent
+            .re
+            .checked_exp()
+            .ok_or(ComputeError::ArithmeticOverflow)?;
+
+        Ok(Complex {
+            polar: self.polar.or(w.polar),
+            ..Complex::from_polar(magnitude, exponent.im, Angle::Radian)
+        })
+    }
+
+    /// Principal square root, its real part is not negative: `sqrt(-4)` => `2i`
+    pub fn sqrt(&self) -> Result<Complex, ComputeError> {
+        let r = self.abs()?;
+        let half = |v: Option<Decimal>| {
+            v.map(|v| (v / Decimal::TWO).sqrt().unwrap_or(Decimal::ZERO))
+                .ok_or(ComputeError::ArithmeticOverflow)
+        };
+        let im = half(r.checked_sub(self.re))?;
+
+        Ok(Complex {
+            polar: self.polar,
+            ..Complex::new(
+                half(r.checked_add(self.re))?,
+                if self.im < Decimal::ZERO { -im } else { im },
+            )
+        })
+    }
+
+    /// Real number if there is no imaginary part and no polar form: `i * i` => `-1`
+    pub fn to_numeric(self) -> NumericExpression {
+        self.with_unit(None)
+    }
+
+    /// Number of the unit, real if there is no imaginary part: `(1 + i) * 2 km` => `(2+2i)km`
+    pub fn with_unit(self, unit: Option<Unit>) -> NumericExpression {
+        if self.im.is_zero() && self.polar.is_none() {
+            NumericExpression::with_unit(self.re, unit)
+        } else {
+            NumericExpression::Complex(self, unit)
+        }
+    }
+
+    /// Number in the locale format: `3+4i`, `5∠90°`
+    pub(crate) fn format(&self, number: impl Fn(&Decimal) -> String) -> String {
+        // a magnitude out of range is shown in the rectangular form
+        if let (Some(a), Ok(magnitude)) = (self.polar, self.abs()) {
+            let phase = self.arg(a);
+            let unit = Unit::Angle(a);
+            return format!(
+                "{}∠{}{}",
+                number(&round(magnitude, Decimal::ZERO, SHOWN_DIGITS)),
+                number(&phase),
+                unit.to_string_with_plural(&phase)
+            );
+        }
+
+        let imaginary = |v: Decimal| match v {
+            v if v == Decimal::ONE => String::from("i"),
+            v if v == Decimal::NEGATIVE_ONE => String::from("-i"),
+            v => format!("{}i", number(&v)),
+        };
+
+        match (self.re.is_zero(), self.im.is_zero()) {
+            (_, true) => number(&self.re),
+            (true, false) => imaginary(self.im),
+            (false, false) if self.im < Decimal::ZERO => {
+                format!("{}-{}", number(&self.re), imaginary(-self.im))
+            }
+            (false, false) => format!("{}+{}", number(&self.re), imaginary(self.im)),
+        }
+    }
+
+    fn powi(&self, n: i64) -> Result<Complex, ComputeError> {
+        let mut base = *self;
+        let mut res = Complex::new(Decimal::ONE, Decimal::ZERO);
+        let mut exponent = n.unsigned_abs();
+
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                res = res.mul(&base)?;
+            }
+            exponent /= 2;
+            if exponent > 0 {
+                base = base.mul(&base)?;
+            }
+        }
+
+        let res = Complex {
+            polar: self.polar,
+            ..res
+        };
+
+        if n < 0 {
+            return Complex::new(Decimal::ONE, Decimal::ZERO).div(&res);
+        }
+
+        Ok(res)
+    }
+
+    /// Result of the parts of both numbers, it is in the polar form if any of them is.
+    fn with(
+        &self,
+        other: &Complex,
+        f: impl Fn(&Complex, &Complex) -> Option<(Decimal, Decimal)>,
+    ) -> Result<Complex, ComputeError> {
+        let (re, im) = f(self, other).ok_or(ComputeError::ArithmeticOverflow)?;
+        Ok(Complex {
+            polar: self.polar.or(other.polar),
+            ..Complex::new(re, im)
+        })
+    }
+}
+
+impl Display for Complex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.format(|n| n.to_string()))
+    }
+}
+
+/// Operation with a complex number, numbers are its real parts: `3 + 4i`
+///
+/// A single unit is kept, the same units are added or divided: `(1 + i) * 2 km`, `(1 + i) km / 2 km`
+pub(super) fn invoke_operator(
+    operator: char,
+    params: &[NumericExpression],
+) -> Result<NumericExpression, ComputeError> {
+    let units = params.iter().map(unit).collect::<Vec<_>>();
+    let invalid = || ComputeError::InvalidComplexValue(params.iter().join(", "));
+    let unit = match (operator, &units[..]) {
+        (_, [u]) => *u,
+        ('+' | '-', [a, b]) if a == b || a.is_none() || b.is_none() => a.or(*b),
+        ('*', [a, b]) if a.is_none() || b.is_none() => a.or(*b),
+        ('/', [a, b]) if a == b => None,
+        ('/', [a, None]) => *a,
+        ('^', [None, None]) => None,
+        _ => return Err(invalid()),
+    };
+
+    let params = params.iter().map(complex).collect::<Result<Vec<_>, _>>()?;
+
+    let res = match (operator, &params[..]) {
+        ('+', [a]) => *a,
+        ('-', [a]) => Complex {
+            re: -a.re,
+            im: -a.im,
+            ..*a
+        },
+        ('+', [a, b]) => a.add(b)?,
+        ('-', [a, b]) => a.sub(b)?,
+        ('*', [a, b]) => a.mul(b)?,
+        ('/', [a, b]) => a.div(b)?,
+        ('^', [a, b]) => a.pow(b)?,
+        (_, [_]) => return Err(ComputeError::UnsupportedUnaryOperator),
+        (o, p) => {
+            return Err(ComputeError::InvalidNumberOfParametersForOperator(
+                o,
+                p.len(),
+            ))
+        }
+    };
+
+    Ok(res.with_unit(unit))
+}
+
+/// Functions of complex numbers, `None` if the name or the parameters are not of a complex
+/// function. A square root of a negative number is imaginary: `sqrt(-4)` => `2i`
+pub(super) fn invoke_function(
+    name: &str,
+    params: &[NumericExpression],
+) -> Option<Result<NumericExpression, ComputeError>> {
+    use NumericExpression::{Complex as C, Number as N, NumberWithUnit as U};
+
+    let z = |n: &NumericExpression| complex(n).ok();
+
+    let res = match (name, params) {
+        ("sqrt", [N(n)]) if *n < Decimal::ZERO => {
+            Ok(C(Complex::new(Decimal::ZERO, n.abs().sqrt()?), None))
+        }
+        ("sqrt", [C(a, None)]) => a.sqrt().map(Complex::to_numeric),
+        ("sqr", [C(a, None)]) => a.powi(2).map(Complex::to_numeric),
+        ("pow", [a, b]) if is_complex(a) || is_complex(b) => {
+            return Some(invoke_operator('^', params));
+        }
+        ("abs", [C(a, u)]) => a.abs().map(|m| NumericExpression::with_unit(m, *u)),
+        ("arg", [a @ (N(_) | C(..))]) => {
+            Ok(U(z(a)?.arg(Angle::Degree), Unit::Angle(Angle::Degree)))
+        }
+        ("conj", [a @ (N(_) | C(..))]) => Ok(z(a)?.conj().with_unit(unit(a))),
+        ("re", [a @ (N(_) | C(..))]) => Ok(NumericExpression::with_unit(z(a)?.re, unit(a))),
+        ("im", [a @ (N(_) | C(..))]) => Ok(NumericExpression::with_unit(z(a)?.im, unit(a))),
+        ("polar", [a @ (N(_) | C(..))]) => {
+            let z = z(a)?;
+            Ok(C(z.to_polar(z.polar.unwrap_or(Angle::Degree)), unit(a)))
+        }
+        // magnitude and phase: polar(10, 30°), a phase without a unit is in radians
+        ("polar", [N(magnitude), N(phase)]) => Ok(C(
+            Complex::from_polar(*magnitude, *phase, Angle::Radian),
+            None,
+        )),
+        ("polar", [N(magnitude), U(phase, Unit::Angle(a))]) => Ok(C(
+            Complex::from_polar(*magnitude, *phase * a.reference_unit_multiplier(), *a),
+            None,
+        )),
+        ("rectangular", [a @ (N(_) | C(..))]) => Ok(z(a)?.to_rectangular().with_unit(unit(a))),
+        (_, params) if params.iter().any(is_complex) => Err(
+            ComputeError::InvalidParametersForFunction(name.to_owned(), params.iter().join(", ")),
+        ),
+        _ => return None,
+    };
+
+    Some(res)
+}
+
+/// Whether the symbol is the imaginary unit: `i`, `j`
+pub(super) fn is_imaginary_unit(symbol: &str) -> bool {
+    matches!(symbol, "i" | "j")
+}
+
+fn is_complex(n: &NumericExpression) -> bool {
+    matches!(n, NumericExpression::Complex(..))
+}
+
+/// Number without its unit, the unit is kept by the operation.
+fn complex(n: &NumericExpression) -> Result<Complex, ComputeError> {
+    match n {
+        NumericExpression::Number(v) | NumericExpression::NumberWithUnit(v, _) => {
+            Ok(Complex::new(*v, Decimal::ZERO))
+        }
+        NumericExpression::Rational(r, _) => Ok(Complex::new(r.to_decimal(), Decimal::ZERO)),
+        NumericExpression::Complex(c, _) => Ok(*c),
+        n => Err(ComputeError::InvalidComplexValue(n.to_string())),
+    }
+}
+
+fn unit(n: &NumericExpression) -> Option<Unit> {
+    match n {
+        NumericExpression::Complex(_, u) => *u,
+        n => n.units().first().copied().flatten(),
+    }
+}
+
+/// Arctangent of `y / x` in the quadrant of the point.
+fn atan2(y: Decimal, x: Decimal) -> Decimal {
+    let Some(t) = y.checked_div(x) else {
+        return match y {
+            y if y > Decimal::ZERO => Decimal::HALF_PI,
+            y if y < Decimal::ZERO => -Decimal::HALF_PI,
+            _ => Decimal::ZERO,
+        };
+    };
+
+    match atan(t) {
+        a if x > Decimal::ZERO => a,
+        a if y < Decimal::ZERO => a - Decimal::PI,
+        a => a + Decimal::PI,
+    }
+}
+
+/// Taylor series of the arctangent, the angle is halved until the series converges quickly.
+fn atan(x: Decimal) -> Decimal {
+    if x < Decimal::ZERO {
+        return -atan(-x);
+    }
+
+    if x > Decimal::ONE {
+        return Decimal::HALF_PI - atan(Decimal::ONE / x);
+    }
+
+    // atan(x) = 2 * atan(x / (1 + sqrt(1 + x^2)))
+    let mut x = x;
+    let mut factor = Decimal::ONE;
+    for _ in 0..3 {
+        x /= Decimal::ONE + (Decimal::ONE + x * x).sqrt().unwrap_or(Decimal::ONE);
+        factor *= Decimal::TWO;
+    }
+
+    let square = x * x;
+    let mut power = x;
+    let mut sum = Decimal::ZERO;
+    for k in 0u32.. {
+        let term = power / Decimal::from(2 * k + 1);
+        if term.is_zero() {
+            break;
+        }
+
+        sum += if k % 2 == 0 { term } else { -term };
+        power *= square;
+    }
+
+    factor * sum
+}
+
+/// Significant digits of the larger of the value and the scale of the computation.
+fn round(v: Decimal, scale: Decimal, digits: u32) -> Decimal {
+    let scale = scale.max(v.abs());
+    let Some(magnitude) = scale.checked_log10().and_then(|l| l.floor().to_i64()) else {
+        return v.normalize();
+    };
+
+    match u32::try_from(i64::from(digits) - 1 - magnitude) {
+        Ok(dp) => v.round_dp(dp),
+        Err(_) => v.round_sf(digits).unwrap_or(v),
+    }
+    .normalize()
+}
@@ -0,0 +1,179 @@
+use crate::{Calculator, Complex, ComputeError, Decimal, Locale, Localize};
+
+fn compute(expression: &str) -> Result<String, ComputeError> {
+    let mut calculator = Calculator::default();
+    let statement = calculator.compute(expression).unwrap();
+
+    match &statement.result {
+        Some(r) => r.as_ref().map(|n| n.to_string()).map_err(|e| e.clone()),
+        None => panic!("{:?}: no result", expression),
+    }
+}
+
+#[test]
+fn test_arithmetic() {
+    for (expression, expected) in [
+        ("i", "i"),
+        ("2i", "2i"),
+        ("3 + 4i", "3+4i"),
+        ("3 - 4j", "3-4i"),
+        ("-i", "-i"),
+        ("(3 + 4i) * 2", "6+8i"),
+        ("(1 + i) * (1 - i)", "2"),
+        ("i * i", "-1"),
+        ("(1 + i)^2", "2i"),
+        ("i^-1", "-i"),
+        ("1 / i", "-i"),
+        ("(3 + 4i) / (1 - 2i)", "-1+2i"),
+        ("(2 + 3i) - (2 + 3i)", "0"),
+        ("E^(i * PI)", "-1"),
+        (
+            "i^0.5",
+            "0.70710678118654752440084436+0.70710678118654752440084436i",
+        ),
+        ("(1, 2) * i", "(i, 2i)"),
+        ("sum(k * i, k, 1, 3)", "6i"),
+        // a bound variable hides the imaginary unit
+        ("sum(i, i, 1, 3)", "6"),
+        ("prod(j + 1, j, 1, 3)", "24"),
+    ] {
+        assert_eq!(compute(expression).unwrap(), expected, "{}", expression);
+    }
+
+    assert!(matches!(
+        compute("(1 + i) / 0"),
+        Err(ComputeError::DivisionByZero)
+    ));
+}
+
+#[test]
+fn test_units() {
+    for (expression, expected) in [
+        ("(1 + i) * 2 km", "(2+2i)km"),
+        ("2 km * i", "(2i)km"),
+        ("(1 + i) * 2 km in m", "(2000+2000i)m"),
+        ("(2+2i)km", "(2+2i)km"),
+        ("(1 + i) km + 1 km", "(2+i)km"),
+        ("(1 + i) km / 2 km", "0.5+0.5i"),
+        ("abs((3 + 4i) km)", "5km"),
+        ("re((3 + 4i) km)", "3km"),
+        ("(1 + i) in km", "(1+i)km"),
+    ] {
+        assert_eq!(compute(expression).unwrap(), expected, "{}", expression);
+    }
+
+    for expression in ["(1 + i) km + 1 h", "(1 + i) km * 2 km", "1 / ((2i) km)"] {
+        assert!(
+            matches!(
+                compute(expression),
+                Err(ComputeError::InvalidComplexValue(_))
+            ),
+            "{}",
+            expression
+        );
+    }
+}
+
+#[test]
+fn test_functions() {
+    for (expression, expected) in [
+        ("sqrt(-4)", "2i"),
+        ("sqrt(-2)", "1.4142135623730950488016887242i"),
+        ("sqrt(2i)", "1+i"),
+        ("sqrt(-4) * sqrt(-4)", "-4"),
+        ("sqrt(4)", "2"),
+        ("abs(3 + 4i)", "5"),
+        ("abs(-5)", "5"),
+        ("arg(i)", "90°"),
+        ("arg(-1)", "180°"),
+        ("arg(-1 - i)", "-135°"),
+        ("arg(1 + i) in rad", "0.7853981633974483096156608465rad"),
+        ("conj(3 + 4i)", "3-4i"),
+        ("re(3 + 4i)", "3"),
+        ("im(3 + 4i)", "4"),
+        ("im(5)", "0"),
+        ("sqr(1 + i)", "2i"),
+        ("pow(i, 2)", "-1"),
+    ] {
+        assert_eq!(compute(expression).unwrap(), expected, "{}", expression);
+    }
+
+    assert!(matches!(
+        compute("abs(79228162514264337593543950335 + 79228162514264337593543950335i)"),
+        Err(ComputeError::ArithmeticOverflow)
+    ));
+    assert!(matches!(
+        compute("sin(i)"),
+        Err(ComputeError::InvalidParametersForFunction(..))
+    ));
+    // real numbers stay real
+    assert!(matches!(
+        compute("ln(-1)"),
+        Err(ComputeError::InvalidParametersForFunction(..))
+    ));
+}
+
+#[test]
+fn test_polar() {
+    for (expression, expected) in [
+        ("(3 + 4i) in polar", "5∠53.1301023541559787031444°"),
+        ("(1 + i) in polar", "1.41421356237309504880169∠45°"),
+        ("-1 in polar", "1∠180°"),
+        ("(1 + i) in gon", "1.41421356237309504880169∠50gon"),
+        ("i in rad", "1∠1.57079632679489661923132rad"),
+        ("polar(2, 90°)", "2∠90°"),
+        ("polar(2, 90°) in rectangular", "2i"),
+        ("rectangular(polar(2, 90°))", "2i"),
+        (
+            "polar(230, 120°) in rectangular",
+            "-115+199.185842870420888755656329i",
+        ),
+        ("polar(10, 30°) * polar(2, 15°)", "20∠45°"),
+        ("polar(10, 30°) / polar(2, 15°)", "5∠15°"),
+        // the shown form is parsed back
+        ("5∠90°", "5∠90°"),
+        ("5∠-90°", "5∠-90°"),
+        ("2∠1.5rad", "2∠1.5rad"),
+        (
+            "5∠53.1301023541559787031444°",
+            "5∠53.1301023541559787031444°",
+        ),
+        ("1∠90° + 1", "1.41421356237309504880169∠45°"),
+    ] {
+        assert_eq!(compute(expression).unwrap(), expected, "{}", expression);
+    }
+}
+
+#[test]
+fn test_values() {
+    let z = Complex::new(Decimal::from(3), Decimal::from(4));
+
+    assert_eq!(z.abs().unwrap(), Decimal::from(5));
+    assert_eq!(z.conj().im(), Decimal::from(-4));
+    assert_eq!(
+        z.mul(&Complex::I).unwrap(),
+        Complex::new(Decimal::from(-4), Decimal::from(3))
+    );
+    assert_eq!(
+        Complex::I.mul(&Complex::I).unwrap().re(),
+        Decimal::NEGATIVE_ONE
+    );
+}
+
+#[test]
+fn test_format() {
+    let mut calculator = Calculator::default();
+    calculator.set_locale(Locale::Cs);
+    let statement = calculator.compute("1,5 - 2,5i").unwrap();
+
+    assert_eq!(
+        statement
+            .result
+            .as_ref()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .localize(Locale::Cs),
+        "1,5-2,5i"
+    );
+}
@@ -2,21 +2,44 @@ use itertools::Itertools;
 use rust_decimal::Decimal;
 
 use crate::ComputeError;
+use crate::context::Context;
+use crate::calculus;
+use crate::complex;
+use crate::datetime;
+use crate::equations;
 use crate::expression::{Expression, ExpressionToken, NumericExpression};
-use crate::functions::Function;
-use crate::units::Unit;
+use crate::functions::{functions, Fce, Function, UnitRule};
+use crate::lists;
+use crate::matrix;
+use crate::operators::Operator;
+use crate::rational;
+use crate::units::{Angle, Speed, Unit};
 use crate::utils::flatten_lines;
 
-pub(super) fn compute(ex: &Expression) -> Result<NumericExpression, ComputeError> {
+pub(super) fn compute(ex: &Expression, ctx: &Context) -> Result<NumericExpression, ComputeError> {
     let mut variables = Vec::new();
     let mut result: Option<NumericExpression> = None;
     let mut operator = None;
-    let mut function = None;
+    let mut function: Option<&Fce> = None;
     let mut conversion_chain = None;
 
     for t in ex.tokens.iter() {
         let mut invoke = false;
 
+        // parameters with unknowns or bound variables are not computed: solve(x^2 - 2, x)
+        let solved = function.and_then(|f| {
+            equations::invoke_function(f.name(), t, ctx)
+                .or_else(|| calculus::invoke_function(f.name(), t, ctx))
+        });
+        if let Some(n) = solved {
+            let n = n?;
+            variables.clear();
+            variables.push(n.clone());
+            result = Some(n);
+            function = None;
+            continue;
+        }
+
         match t {
             ExpressionToken::Operator(o) => operator = Some(o),
             ExpressionToken::Function(f) => function = Some(f),
@@ -30,7 +53,7 @@ pub(super) fn compute(ex: &Expression) -> Result<NumericExpression, ComputeError
                 invoke = true;
             }
             ExpressionToken::Expression(ex) => {
-                let n = compute(ex)?;
+                let n = compute(ex, ctx)?;
                 variables.push(n.clone());
                 if result.is_none() {
                     // initial result = first operand
@@ -40,7 +63,17 @@ pub(super) fn compute(ex: &Expression) -> Result<NumericExpression, ComputeError
                 invoke = true;
             }
             ExpressionToken::Generator(g) => {
-                let n = NumericExpression::with_unit((g.fce)(), None); // Unit: None
+                let n = (g.fce)(ctx);
+                variables.push(n.clone());
+                if result.is_none() {
+                    // initial result = first operand
+                    result = Some(n.clone());
+                }
+
+                invoke = true;
+            }
+            ExpressionToken::Symbol(symbol) => {
+                let n = ctx.resolve(symbol)?;
                 variables.push(n.clone());
                 if result.is_none() {
                     // initial result = first operand
@@ -50,9 +83,46 @@ pub(super) fn compute(ex: &Expression) -> Result<NumericExpression, ComputeError
                 invoke = true;
             }
             ExpressionToken::List(list) => {
-                for ex in list {
-                    let n = compute(ex)?;
-                    variables.push(n);
+                let values = list
+                    .iter()
+                    .map(|ex| compute(ex, ctx))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                // arguments of a function, otherwise a list value: (1, 2, 3) * 2
+                if function.is_some() {
+                    variables.extend(values);
+                } else {
+                    let n = NumericExpression::List(values);
+                    variables.push(n.clone());
+                    if result.is_none() {
+                        // initial result = first operand
+                        result = Some(n);
+                    }
+                }
+
+                invoke = true;
+            }
+            ExpressionToken::Matrix(rows) => {
+                let rows = rows
+                    .iter()
+                    .map(|r| r.iter().map(|ex| compute(ex, ctx)).collect())
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let n = matrix::from_elements(rows)?;
+                variables.push(n.clone());
+                if result.is_none() {
+                    // initial result = first operand
+                    result = Some(n);
+                }
+
+                invoke = true;
+            }
+            ExpressionToken::Equation(left, right) => {
+                let n = equations::solve_equation(left, right, ctx)?;
+                variables.push(n.clone());
+                if result.is_none() {
+                    // initial result = first operand
+                    result = Some(n);
                 }
 
                 invoke = true;
@@ -69,7 +139,7 @@ pub(super) fn compute(ex: &Expression) -> Result<NumericExpression, ComputeError
 
         // if function is set
         if let Some(f) = function {
-            let n = invoke_fce(f, variables.clone())?;
+            let n = invoke_fce(f, variables.clone(), ctx)?;
             variables.clear();
             variables.push(n.clone());
             result = Some(n.clone());
@@ -79,18 +149,7 @@ pub(super) fn compute(ex: &Expression) -> Result<NumericExpression, ComputeError
 
         // if operation is set
         if let Some(o) = operator {
-            let (converted, _) = convert_variables(variables.clone())?;
-
-            let n = match converted.len() {
-                1 => invoke_unary(o.unary_action, &converted[0]),
-                2 => invoke_binary(o.binary_action, &converted[0], &converted[1]),
-                x => {
-                    return Err(ComputeError::InvalidNumberOfParametersForOperator(
-                        o.representation,
-                        x,
-                    ));
-                }
-            }?;
+            let n = invoke_operator(o, variables.clone(), ctx)?;
 
             variables.clear();
             variables.push(n.clone());
@@ -104,13 +163,15 @@ pub(super) fn compute(ex: &Expression) -> Result<NumericExpression, ComputeError
             if variables.len() == 1 {
                 let chain = flatten_lines(chain);
                 let mut values = Vec::new();
+                let mut single = None;
+                let mut lists = Vec::new();
 
                 for chain_variant in chain.clone() {
                     let mut v = variables[0].clone();
 
                     let mut ok = true;
                     for unit in chain_variant.clone() {
-                        let Ok(v_converted) = v.convert_to(&unit, true) else {
+                        let Ok(v_converted) = v.convert_to(&unit, true, ctx) else {
                             ok = false;
                             break;
                         };
@@ -118,10 +179,33 @@ pub(super) fn compute(ex: &Expression) -> Result<NumericExpression, ComputeError
                     }
 
                     if ok {
+                        if is_datetime(&v) || is_complex(&v) {
+                            // date or complex number has a single value: 15:00 Prague in New York
+                            single = Some(v);
+                            break;
+                        }
+
+                        if let NumericExpression::List(l) = v {
+                            lists.push(l);
+                            continue;
+                        }
+
                         values.extend(v.values());
                     }
                 }
 
+                if let Some(v) = single {
+                    variables = vec![v];
+                    conversion_chain = None;
+                    continue;
+                }
+
+                if !lists.is_empty() {
+                    variables = vec![lists::merge(lists)];
+                    conversion_chain = None;
+                    continue;
+                }
+
                 if values.is_empty() {
                     return Err(ComputeError::OperatorsConversionError(
                         variables,
@@ -150,7 +234,7 @@ pub(super) fn compute(ex: &Expression) -> Result<NumericExpression, ComputeError
 
                     let mut ok = true;
                     for unit in chain_variant.clone() {
-                        let Ok(v_converted) = v.convert_to(&unit, true) else {
+                        let Ok(v_converted) = v.convert_to(&unit, true, ctx) else {
                             ok = false;
                             break;
                         };
@@ -193,15 +277,137 @@ pub(super) fn compute(ex: &Expression) -> Result<NumericExpression, ComputeError
 
     // if function is at the end - invoke it with operands
     if let Some(f) = function {
-        let r = invoke_fce(f, variables)?;
+        let r = invoke_fce(f, variables, ctx)?;
         result = Some(r);
     }
 
     result.ok_or(ComputeError::InvalidExpression(ex.to_string()))
 }
 
+/// Context of an expression computed repeatedly with different values of some names: unknowns of
+/// an equation, variable of an integral. Data used by the computations are added to the parent.
+pub(super) struct Bindings<'a> {
+    ctx: Context,
+    parent: &'a Context,
+}
+
+impl<'a> Bindings<'a> {
+    pub(super) fn new(parent: &'a Context) -> Bindings<'a> {
+        Bindings {
+            ctx: parent.clone(),
+            parent,
+        }
+    }
+
+    pub(super) fn compute(
+        &mut self,
+        ex: &Expression,
+        names: &[String],
+        values: &[NumericExpression],
+    ) -> Result<NumericExpression, ComputeError> {
+        for (name, n) in names.iter().zip(values) {
+            self.ctx.variables.insert(name.clone(), n.clone());
+        }
+
+        compute(ex, &self.ctx)
+    }
+
+    pub(super) fn context(&self) -> &Context {
+        &self.ctx
+    }
+
+    /// Number of a result in the unit, `None` if it can not be converted.
+    pub(super) fn magnitude(&self, n: &NumericExpression, unit: Option<Unit>) -> Option<Decimal> {
+        let n = match unit {
+            Some(u) => n.convert_to(&u, false, &self.ctx).ok()?,
+            None => n.clone(),
+        };
+
+        n.values().first().map(|(v, _)| *v)
+    }
+}
+
+impl Drop for Bindings<'_> {
+    fn drop(&mut self) {
+        self.parent.merge_usage(&self.ctx);
+    }
+}
+
+fn is_datetime(n: &NumericExpression) -> bool {
+    matches!(n, NumericExpression::DateTime(_))
+}
+
+fn is_list(n: &NumericExpression) -> bool {
+    matches!(n, NumericExpression::List(_))
+}
+
+fn is_matrix(n: &NumericExpression) -> bool {
+    matches!(n, NumericExpression::Matrix(_))
+}
+
+fn is_complex(n: &NumericExpression) -> bool {
+    matches!(n, NumericExpression::Complex(..))
+}
+
+pub(super) fn invoke_operator(
+    o: &Operator,
+    variables: Vec<NumericExpression>,
+    ctx: &Context,
+) -> Result<NumericExpression, ComputeError> {
+    if variables.iter().any(is_list) {
+        return lists::broadcast(&variables, |v| invoke_operator(o, v, ctx));
+    }
+
+    // the unit rule of `pow`, there are no units of areas or other powers: (5 km)^2
+    if o.representation == '^' {
+        let power = Function {
+            representation: o.representation.to_string(),
+            ..functions()["pow"].clone()
+        };
+        apply_unit_rule(&power, variables.clone(), ctx)?;
+    }
+
+    if let Some(r) = rational::invoke_operator(o.representation, &variables) {
+        return r;
+    }
+
+    let variables = rational::to_decimals(variables);
+
+    if variables.iter().any(is_matrix) {
+        return matrix::invoke_operator(o.representation, &variables);
+    }
+
+    if variables.iter().any(is_complex) {
+        return complex::invoke_operator(o.representation, &variables);
+    }
+
+    if variables.iter().any(is_datetime) {
+        return datetime::invoke_operator(o.representation, &variables, ctx);
+    }
+
+    if let Some(r) = temperature_operator(o.representation, &variables) {
+        return r;
+    }
+
+    if let Some(r) = speed_operator(o, &variables, ctx) {
+        return r;
+    }
+
+    let (converted, _) = convert_variables(variables, ctx)?;
+
+    match converted.len() {
+        1 => invoke_unary(o.unary_action, &converted[0]),
+        2 => invoke_binary(o.binary_action, &converted[0], &converted[1], ctx),
+        x => Err(ComputeError::InvalidNumberOfParametersForOperator(
+            o.representation,
+            x,
+        )),
+    }
+}
+
 fn convert_variables(
     variables: Vec<NumericExpression>,
+    ctx: &Context,
 ) -> Result<(Vec<NumericExpression>, Vec<Unit>), ComputeError> {
     let mut to: Vec<Unit> = Vec::new();
 
@@ -220,7 +426,7 @@ fn convert_variables(
             let mut ok = true;
 
             for variable in variables.iter() {
-                if variable.convert_to(u, false).is_err() {
+                if variable.convert_to(u, false, ctx).is_err() {
                     ok = false;
                     break;
                 };
@@ -249,7 +455,7 @@ fn convert_variables(
             let u = to[0];
 
             for variable in variables.iter() {
-                let Ok(v) = variable.convert_to(&u, false) else {
+                let Ok(v) = variable.convert_to(&u, false, ctx) else {
                     ok = false;
                     break;
                 };
@@ -271,7 +477,7 @@ fn convert_variables(
             for variable in variables.iter() {
                 let mut res = Vec::new();
                 for u in to.iter() {
-                    if let Ok(v) = variable.convert_to(u, false) {
+                    if let Ok(v) = variable.convert_to(u, false, ctx) {
                         res.extend(v.values());
                     };
                 }
@@ -299,6 +505,88 @@ fn convert_variables(
     }
 }
 
+/// Sum or difference with an absolute temperature, which is shifted by a difference in its degrees:
+/// `20 °C + 9 Δ°F` => `25 °C`, `20 °C - 5 °C` => `15 Δ°C`. Kelvins are a difference with an
+/// absolute temperature, except for the minuend, which is absolute: `300 K - 20 °C` => `6.85 Δ°C`.
+/// Absolute results below absolute zero are an error.
+///
+/// `None` for other values and operators, these are computed as numbers with units.
+fn temperature_operator(
+    operator: char,
+    variables: &[NumericExpression],
+) -> Option<Result<NumericExpression, ComputeError>> {
+    let [NumericExpression::NumberWithUnit(a, Unit::Temperature(t1)), NumericExpression::NumberWithUnit(b, Unit::Temperature(t2))] =
+        variables
+    else {
+        return None;
+    };
+
+    let invalid = || {
+        Some(Err(ComputeError::InvalidTemperatureOperation(
+            variables.iter().join(&format!(" {} ", operator)),
+        )))
+    };
+    let (res, unit) = match (operator, t1.is_absolute(), t2.is_absolute()) {
+        ('+', true, true) => return invalid(),
+        ('+', true, false) => (a.checked_add(t2.conversion(*b, t1.difference())?), *t1),
+        ('+', false, true) => (t1.conversion(*a, t2.difference())?.checked_add(*b), *t2),
+        ('-', false, true) if t1.is_difference() => return invalid(),
+        // kelvins minus an absolute temperature are an absolute minuend: 300 K - 20 °C => 6.85 Δ°C
+        ('-', false, true) => (t1.conversion(*a, *t2)?.checked_sub(*b), t2.difference()),
+        ('-', _, true) => (a.checked_sub(t2.conversion(*b, *t1)?), t1.difference()),
+        ('-', true, false) => (a.checked_sub(t2.conversion(*b, t1.difference())?), *t1),
+        _ => return None,
+    };
+    if let Some(n) = res {
+        if unit.is_absolute() && n < unit.from_reference_unit(Decimal::ZERO) {
+            return invalid();
+        }
+    }
+
+    Some(
+        res.map(|n| NumericExpression::with_unit(n.normalize(), Some(Unit::Temperature(unit))))
+            .ok_or(ComputeError::ArithmeticOverflow),
+    )
+}
+
+/// Length divided by time is a speed, a speed multiplied by time is a length:
+/// `150 km / 3 h` => `50km/h`, `50 km/h * 30 min` => `25km`, `100 km / 50 km/h` => `2h`
+///
+/// `None` for other values and operators.
+fn speed_operator(
+    o: &Operator,
+    variables: &[NumericExpression],
+    ctx: &Context,
+) -> Option<Result<NumericExpression, ComputeError>> {
+    let [NumericExpression::NumberWithUnit(a, u1), NumericExpression::NumberWithUnit(b, u2)] =
+        variables
+    else {
+        return None;
+    };
+
+    let (a, b, unit) = match (o.representation, u1, u2) {
+        ('/', Unit::Length(l), Unit::Time(t)) => (*a, *b, Speed::new(*l, *t)?.to_unit()),
+        ('*', Unit::Speed(s), Unit::Time(_)) => (
+            *a,
+            u2.conversion(b, &s.time.to_unit(), ctx)?,
+            s.length.to_unit(),
+        ),
+        ('*', Unit::Time(_), Unit::Speed(s)) => (
+            u1.conversion(a, &s.time.to_unit(), ctx)?,
+            *b,
+            s.length.to_unit(),
+        ),
+        ('/', Unit::Length(_), Unit::Speed(s)) => (
+            u1.conversion(a, &s.length.to_unit(), ctx)?,
+            *b,
+            s.time.to_unit(),
+        ),
+        _ => return None,
+    };
+
+    Some((o.binary_action)(a, b).map(|n| NumericExpression::NumberWithUnit(n.normalize(), unit)))
+}
+
 fn invoke_unary(
     f: fn(Decimal) -> Result<Decimal, ComputeError>,
     p: &NumericExpression,
@@ -317,6 +605,7 @@ fn invoke_binary(
     f: fn(Decimal, Decimal) -> Result<Decimal, ComputeError>,
     p1: &NumericExpression,
     p2: &NumericExpression,
+    ctx: &Context,
 ) -> Result<NumericExpression, ComputeError> {
     let mut res = Vec::new();
 
@@ -330,7 +619,7 @@ fn invoke_binary(
                 Some(u1) => match u2 {
                     None => res.push((f(n1, n2)?, Some(u1))),
                     Some(u2) => {
-                        let Some(n1) = u1.conversion(&n1, &u2) else {
+                        let Some(n1) = u1.conversion(&n1, &u2, ctx) else {
                             continue;
                         };
                         res.push((f(n1, n2)?, Some(u2)));
@@ -352,10 +641,60 @@ fn invoke_binary(
 }
 
 fn invoke_fce(
-    f: &Function,
+    fce: &Fce,
     variables: Vec<NumericExpression>,
+    ctx: &Context,
 ) -> Result<NumericExpression, ComputeError> {
-    let (converted, _) = convert_variables(variables.clone())?;
+    let variables = if fce.is_aggregate() {
+        lists::flatten(variables)
+    } else {
+        variables
+    };
+
+    if let Some(r) = rational::invoke_function(fce.name(), &variables) {
+        return r;
+    }
+
+    let variables = rational::to_decimals(variables);
+
+    if let Some(r) = matrix::invoke_function(fce.name(), &variables) {
+        return r;
+    }
+
+    if let Some(r) = complex::invoke_function(fce.name(), &variables) {
+        return r;
+    }
+
+    if let Some(n) = fce.fce_values().and_then(|f| f(&variables, ctx)) {
+        return Ok(n);
+    }
+
+    let invalid = || {
+        ComputeError::InvalidParametersForFunction(
+            fce.name().to_owned(),
+            variables.iter().join(", "),
+        )
+    };
+
+    // elements of a list are the parameters: weekday((2026-12-24, 2026-12-25))
+    if variables.iter().any(is_list) {
+        let res = lists::broadcast(&variables, |v| invoke_fce(fce, v, ctx));
+        return match fce {
+            Fce::Decimal(_) => res,
+            Fce::Values(_) => res.map_err(|_| invalid()),
+        };
+    }
+
+    let Fce::Decimal(f) = fce else {
+        return Err(invalid());
+    };
+
+    if variables.iter().any(is_datetime) {
+        return Err(invalid());
+    }
+
+    let (variables, keep_unit) = apply_unit_rule(f, variables, ctx)?;
+    let (converted, _) = convert_variables(variables.clone(), ctx)?;
     let converted = converted.iter().map(|x| x.values()).collect::<Vec<_>>();
 
     // create combination of arguments
@@ -372,7 +711,7 @@ fn invoke_fce(
 
     for line in lines {
         // validate line
-        if let Ok((converted_line, units)) = convert_variables(line.clone()) {
+        if let Ok((converted_line, units)) = convert_variables(line.clone(), ctx) {
             let params = converted_line.iter().map(|n| n.values()[0].0).collect();
             let n = if (f.params_validation)(&params) {
                 (f.fce)(params)
@@ -383,7 +722,7 @@ fn invoke_fce(
                 ));
             };
 
-            res.push((n, units.first().cloned()))
+            res.push((n, units.first().cloned().filter(|_| keep_unit)))
         }
     }
 
@@ -398,3 +737,79 @@ fn invoke_fce(
 
     Ok(NumericExpression::with_multiple_units(res))
 }
+
+/// Parameters of a function checked for the unit rule of the function, angles are converted to
+/// radians. The result keeps the unit of the parameters if the rule allows it.
+fn apply_unit_rule(
+    f: &Function,
+    variables: Vec<NumericExpression>,
+    ctx: &Context,
+) -> Result<(Vec<NumericExpression>, bool), ComputeError> {
+    let has_unit = |n: &NumericExpression| n.units().iter().any(Option::is_some);
+    let invalid = |n: &NumericExpression| {
+        ComputeError::InvalidUnitForFunction(f.representation.to_owned(), n.to_string())
+    };
+
+    match f.units {
+        UnitRule::Preserve => {}
+        UnitRule::Sum => {
+            let absolute = |n: &&NumericExpression| {
+                n.units()
+                    .iter()
+                    .any(|u| matches!(u, Some(Unit::Temperature(t)) if t.is_absolute()))
+            };
+
+            if variables.len() > 1 {
+                if let Some(n) = variables.iter().find(absolute) {
+                    return Err(invalid(n));
+                }
+            }
+        }
+        UnitRule::Exponent => {
+            if let [base, exponent] = &variables[..] {
+                if has_unit(exponent) {
+                    return Err(invalid(exponent));
+                }
+
+                // there are no units of areas or other powers: km * km
+                if has_unit(base) && exponent.values().iter().any(|(e, _)| *e != Decimal::ONE) {
+                    return Err(invalid(base));
+                }
+            }
+        }
+        UnitRule::Product => {
+            if let Some(n) = variables.iter().filter(|n| has_unit(n)).nth(1) {
+                return Err(invalid(n));
+            }
+        }
+        UnitRule::Dimensionless => {
+            if let Some(n) = variables.iter().find(|n| has_unit(n)) {
+                return Err(invalid(n));
+            }
+        }
+        UnitRule::Angle => {
+            let radian = Unit::Angle(Angle::Radian);
+            let variables = variables
+                .iter()
+                .map(|n| match has_unit(n) {
+                    false => Ok(n.clone()),
+                    true => n
+                        .convert_to(&radian, false, ctx)
+                        .ok()
+                        .filter(|r| r.units() == [Some(radian)])
+                        .and_then(|r| {
+                            r.values()
+                                .first()
+                                .map(|(v, _)| NumericExpression::Number(*v))
+                        })
+                        .ok_or_else(|| invalid(n)),
+                })
+                .collect::<Result<_, _>>()?;
+
+            return Ok((variables, false));
+        }
+        UnitRule::Number => return Ok((variables, false)),
+    }
+
+    Ok((variables, true))
+}
@@ -118,6 +118,11 @@ fn test_division() {
     ]);
 }
 
+#[test]
+fn test_division_errors() {
+    test_errors(&["1 / 0", "79228162514264337593543950335 * 2"]);
+}
+
 #[test]
 fn test_pow() {
     test_computation(&[
@@ -194,6 +199,16 @@ fn test_ln() {
     test_computation(&[("ln(E)", dec!(1.0))]);
 }
 
+#[test]
+fn test_ln_errors() {
+    test_errors(&[
+        "ln(0)",
+        "log(-1)",
+        "sqr(79228162514264337593543950335)",
+        "pow(10, 100)",
+    ]);
+}
+
 #[test]
 fn test_random() {
     let mut numbers = HashSet::new();
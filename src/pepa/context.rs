@@ -0,0 +1,167 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use chrono::{DateTime, Utc};
+
+use crate::complex::{is_imaginary_unit, Complex};
+use crate::exchange_rates::ExchangeRates;
+use crate::expression::NumericExpression;
+use crate::holidays::Holidays;
+use crate::providers::{DataProvider, Quote};
+use crate::units::{Currency, TimeZone, Unit, UnitFamily};
+use crate::{ComputeError, Decimal};
+
+/// Data shared by all statements of a calculator, passed through the computation.
+#[derive(Debug, Clone, Default)]
+pub struct Context {
+    pub exchange_rates: Option<ExchangeRates>,
+    pub providers: Vec<Rc<dyn DataProvider>>,
+    /// disables fetching of data, providers serve only already cached data
+    pub offline: bool,
+    pub holidays: Holidays,
+    /// families picked for ambiguous units, in order: `m` as a minute with `[Time]`
+    pub preferred_families: Vec<UnitFamily>,
+    /// values bound to names, resolved before the data providers: columns of a table row
+    pub variables: HashMap<String, NumericExpression>,
+    /// zone of `now`, `today` and of dates without a zone, the system zone if not set
+    pub time_zone: Option<TimeZone>,
+    /// current time of `now`, `today` and `timestamp()`, the system clock if not set
+    pub now: Option<DateTime<Utc>>,
+    exchange_rates_used: Cell<bool>,
+    holidays_used: Cell<bool>,
+    quotes_used: RefCell<Vec<Quote>>,
+}
+
+impl Context {
+    pub fn local_zone(&self) -> TimeZone {
+        self.time_zone.unwrap_or_else(TimeZone::local)
+    }
+
+    pub fn now(&self) -> DateTime<Utc> {
+        self.now.unwrap_or_else(Utc::now)
+    }
+
+    pub fn convert_currency(&self, v: Decimal, from: Currency, to: Currency) -> Option<Decimal> {
+        if from == to {
+            return Some(v);
+        }
+
+        if let Some(converted) = self
+            .exchange_rates
+            .as_ref()
+            .and_then(|r| r.convert(v, from, to))
+        {
+            self.exchange_rates_used.set(true);
+            return Some(converted);
+        }
+
+        // exchange rate not in the table, ask the providers
+        if let Ok(Some(rate)) = self.quote(&format!("{}/{}", from.code(), to.code())) {
+            return Some(v * rate.value);
+        }
+
+        if let Ok(Some(rate)) = self.quote(&format!("{}/{}", to.code(), from.code())) {
+            if !rate.value.is_zero() {
+                return Some(v / rate.value);
+            }
+        }
+
+        None
+    }
+
+    /// Non-working days of the business day functions.
+    pub(crate) fn holidays(&self) -> &Holidays {
+        self.holidays_used.set(true);
+        &self.holidays
+    }
+
+    /// Resolves a symbol which is not a number, unit, function or constant.
+    pub fn resolve(&self, symbol: &str) -> Result<NumericExpression, ComputeError> {
+        if let Some(n) = self.resolve_local(symbol) {
+            return Ok(n);
+        }
+
+        // currency pair from the exchange rates table
+        if let Some((from, to)) = symbol.split_once('/') {
+            if let (Some(from), Some(to)) = (Currency::from_code(from), Currency::from_code(to)) {
+                if let Some(rate) = self.convert_currency(Decimal::ONE, from, to) {
+                    return Ok(NumericExpression::with_unit(rate, Some(Unit::Currency(to))));
+                }
+            }
+        }
+
+        match self.quote(symbol)? {
+            Some(q) => Ok(NumericExpression::with_unit(q.value, q.unit)),
+            None => Err(ComputeError::UnknownSymbol(symbol.to_owned())),
+        }
+    }
+
+    /// Symbol known without the data providers: a bound variable or the imaginary unit.
+    pub(crate) fn resolve_local(&self, symbol: &str) -> Option<NumericExpression> {
+        if let Some(n) = self.variables.get(symbol) {
+            return Some(n.clone());
+        }
+
+        // imaginary unit, a bound variable of the name hides it: sum(i, i, 1, 3)
+        if is_imaginary_unit(symbol) {
+            return Some(NumericExpression::Complex(Complex::I, None));
+        }
+
+        None
+    }
+
+    fn quote(&self, symbol: &str) -> Result<Option<Quote>, ComputeError> {
+        for provider in self.providers.iter() {
+            let quote = if self.offline {
+                provider.cached_quote(symbol)
+            } else {
+                provider.quote(symbol)
+            }
+            .map_err(|e| ComputeError::DataProviderError(provider.name(), e))?;
+
+            if let Some(quote) = quote {
+                self.quotes_used.borrow_mut().push(quote.clone());
+                return Ok(Some(quote));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Clears the usage flags before a new statement is computed.
+    pub(super) fn reset_usage(&self) {
+        self.exchange_rates_used.set(false);
+        self.holidays_used.set(false);
+        self.quotes_used.borrow_mut().clear();
+    }
+
+    pub(super) fn exchange_rates_used(&self) -> bool {
+        self.exchange_rates_used.get()
+    }
+
+    pub(super) fn holidays_used(&self) -> bool {
+        self.holidays_used.get()
+    }
+
+    pub(super) fn quotes_used(&self) -> Vec<Quote> {
+        self.quotes_used.borrow().clone()
+    }
+
+    /// Adds the data used by a copy of the context: bound values of an equation.
+    pub(super) fn merge_usage(&self, other: &Context) {
+        if other.exchange_rates_used() {
+            self.exchange_rates_used.set(true);
+        }
+
+        if other.holidays_used() {
+            self.holidays_used.set(true);
+        }
+
+        for quote in other.quotes_used() {
+            if !self.quotes_used.borrow().contains(&quote) {
+                self.quotes_used.borrow_mut().push(quote);
+            }
+        }
+    }
+}
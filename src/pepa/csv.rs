@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+
+use rust_decimal::Decimal;
+
+use crate::expression::NumericExpression;
+use crate::pepa::Calculator;
+use crate::units::Unit;
+use crate::{string, ComputeError, DataError};
+
+/// Table of a CSV file, the first line is the header with the column names.
+///
+/// Columns are bound as variables when an expression is computed for a row. Spaces of the names
+/// are replaced by `_` and a unit in brackets applies to all values of the column:
+/// ```text
+/// trip,distance [km],fuel price
+/// Prague - Brno,205,1.65
+/// ```
+/// `distance in mi`, `fuel_price * 1.21`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CsvTable {
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl CsvTable {
+    pub fn from_file(path: &Path) -> Result<CsvTable, DataError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| DataError::Io(path.display().to_string(), e.to_string()))?;
+
+        CsvTable::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> Result<CsvTable, DataError> {
+        let mut lines = content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| fields(line).ok_or(DataError::InvalidCsv(i + 1, string!(line))));
+
+        Ok(CsvTable {
+            header: lines.next().transpose()?.unwrap_or_default(),
+            rows: lines.collect::<Result<_, _>>()?,
+        })
+    }
+
+    pub fn header(&self) -> &[String] {
+        &self.header
+    }
+
+    pub fn rows(&self) -> &[Vec<String>] {
+        &self.rows
+    }
+
+    /// Computes the expression for every row, a row which can not be computed has its own error.
+    ///
+    /// The expression is parsed once, only the columns it uses are read: a text column does not
+    /// fail the rows.
+    pub fn evaluate(
+        &self,
+        calculator: &mut Calculator,
+        expression: &str,
+    ) -> Vec<Result<NumericExpression, ComputeError>> {
+        let columns = self.header.iter().map(|h| column(h)).collect::<Vec<_>>();
+        let names = columns.iter().map(|(n, _)| n.as_str()).collect::<Vec<_>>();
+        let statement = calculator.prepare_with(expression, &names);
+
+        let symbols = statement
+            .expression
+            .as_ref()
+            .map(|e| e.symbols())
+            .unwrap_or_default();
+        let used = columns
+            .iter()
+            .enumerate()
+            .filter(|(_, (name, _))| symbols.contains(name))
+            .map(|(i, (name, unit))| (i, name, unit.map(|u| (u, column_unit(calculator, u)))))
+            .collect::<Vec<_>>();
+
+        self.rows
+            .iter()
+            .map(|row| {
+                let mut variables = HashMap::new();
+
+                for (i, name, unit) in used.iter() {
+                    let cell = row.get(*i).map(|c| c.trim()).unwrap_or_default();
+
+                    let Some(n) = value(calculator, cell, *unit) else {
+                        return Err(ComputeError::InvalidColumnValue(
+                            string!(*name),
+                            string!(cell),
+                        ));
+                    };
+                    variables.insert(string!(*name), n);
+                }
+
+                let statement = calculator.compute_prepared(&statement, variables);
+                statement
+                    .result
+                    .unwrap_or(Err(ComputeError::InvalidExpression(string!(expression))))
+            })
+            .collect()
+    }
+
+    /// Table with a new column of the results, rows with an error have an empty value.
+    ///
+    /// Results with the same unit are written as numbers with the unit in the header: `price [EUR]`
+    pub fn with_results(
+        mut self,
+        name: &str,
+        results: &[Result<NumericExpression, ComputeError>],
+    ) -> CsvTable {
+        let mut units = results.iter().flatten().map(|n| match n {
+            NumericExpression::NumberWithUnit(v, u) => Some(u.to_string_with_plural(v)),
+            _ => None,
+        });
+        let unit = units
+            .next()
+            .flatten()
+            .filter(|u| units.all(|other| other.as_ref() == Some(u)));
+
+        self.header.push(match &unit {
+            Some(u) => format!("{} [{}]", name, u),
+            None => string!(name),
+        });
+
+        for (row, result) in self.rows.iter_mut().zip(results) {
+            row.push(match result {
+                Ok(NumericExpression::NumberWithUnit(v, _)) if unit.is_some() => v.to_string(),
+                Ok(n) => n.to_string(),
+                Err(_) => String::new(),
+            });
+        }
+
+        self
+    }
+}
+
+impl Display for CsvTable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in std::iter::once(&self.header).chain(self.rows.iter()) {
+            let row = row
+                .iter()
+                .map(|field| {
+                    if field.contains([',', '"', '\n']) {
+                        format!("\"{}\"", field.replace('"', "\"\""))
+                    } else {
+                        field.clone()
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            writeln!(f, "{}", row.join(","))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Variable name and unit of a header: `distance [km]` => `distance`, `km`
+fn column(header: &str) -> (String, Option<&str>) {
+    let header = header.trim();
+
+    let (name, unit) = match header.strip_suffix(']').and_then(|h| h.rsplit_once('[')) {
+        Some((name, unit)) => (name.trim(), Some(unit.trim())),
+        None => (header, None),
+    };
+
+    (name.split_whitespace().collect::<Vec<_>>().join("_"), unit)
+}
+
+/// Unit of a column header parsed once: `km`, `None` if it is not a single unit.
+fn column_unit(calculator: &Calculator, unit: &str) -> Option<Unit> {
+    match calculator.compute_line(&format!("1 {}", unit))?.result {
+        Some(Ok(NumericExpression::NumberWithUnit(_, u))) => Some(u),
+        _ => None,
+    }
+}
+
+/// Value of a cell, plain numbers are not passed through the parser.
+fn value(
+    calculator: &Calculator,
+    cell: &str,
+    unit: Option<(&str, Option<Unit>)>,
+) -> Option<NumericExpression> {
+    if cell.is_empty() {
+        return None;
+    }
+
+    if let Ok(n) = cell.parse::<Decimal>() {
+        match unit {
+            None => return Some(NumericExpression::Number(n)),
+            Some((_, Some(u))) => return Some(NumericExpression::NumberWithUnit(n, u)),
+            Some((_, None)) => {}
+        }
+    }
+
+    let line = format!("{} {}", cell, unit.map(|(u, _)| u).unwrap_or_default());
+    calculator.compute_line(&line)?.result?.ok()
+}
+
+/// Fields of a line, quoted fields may contain commas and `""` quotes.
+fn fields(line: &str) -> Option<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+
+    if quoted {
+        return None;
+    }
+
+    fields.push(field);
+    Some(fields)
+}
@@ -0,0 +1,107 @@
+use crate::{Calculator, ComputeError, CsvTable, DataError};
+
+const TRIPS: &str = "\
+trip,distance [km],duration [h],fuel price
+Prague - Brno,205,2.5,1.65
+\"Brno, \"\"center\"\"\",3.5,0.25,1.70
+Unknown,,1,abc
+";
+
+#[test]
+fn test_parse() {
+    let table = CsvTable::parse(TRIPS).unwrap();
+
+    assert_eq!(
+        table.header(),
+        ["trip", "distance [km]", "duration [h]", "fuel price"]
+    );
+    assert_eq!(table.rows().len(), 3);
+    assert_eq!(table.rows()[1][0], "Brno, \"center\"");
+
+    assert!(matches!(
+        CsvTable::parse("a,b\n\"1,2\n"),
+        Err(DataError::InvalidCsv(2, _))
+    ));
+}
+
+#[test]
+fn test_evaluate() {
+    let table = CsvTable::parse(TRIPS).unwrap();
+    let mut calculator = Calculator::default();
+
+    let results = table.evaluate(&mut calculator, "distance in m");
+    assert_eq!(results[0].as_ref().unwrap().to_string(), "205000m");
+    assert_eq!(results[1].as_ref().unwrap().to_string(), "3500.0m");
+    assert!(matches!(
+        &results[2],
+        Err(ComputeError::InvalidColumnValue(c, v)) if c == "distance" && v.is_empty()
+    ));
+
+    let results = table.evaluate(&mut calculator, "fuel_price * 1.21");
+    assert_eq!(results[0].as_ref().unwrap().to_string(), "1.9965");
+    assert!(matches!(
+        &results[2],
+        Err(ComputeError::InvalidColumnValue(c, v)) if c == "fuel_price" && v == "abc"
+    ));
+
+    let results = table.evaluate(&mut calculator, "duration in min");
+    assert_eq!(results[2].as_ref().unwrap().to_string(), "60.0m");
+
+    // rows are not kept as statements
+    assert!(calculator.statements().is_empty());
+}
+
+#[test]
+fn test_columns() {
+    let table = CsvTable::parse("city,days,m,rate [EUR]\nPrague,3,2,100\nBrno,x,1,90\n").unwrap();
+    let mut calculator = Calculator::default();
+
+    // columns named like units, the text column is not used
+    let results = table.evaluate(&mut calculator, "m * 2 days");
+    assert_eq!(results[0].as_ref().unwrap().to_string(), "12");
+    assert!(matches!(
+        &results[1],
+        Err(ComputeError::InvalidColumnValue(c, v)) if c == "days" && v == "x"
+    ));
+
+    let results = table.evaluate(&mut calculator, "m * rate");
+    assert_eq!(results[1].as_ref().unwrap().to_string(), "90.00EUR");
+
+    // the conversion target is a unit
+    let results = table.evaluate(&mut calculator, "m * 1 km in m");
+    assert_eq!(results[0].as_ref().unwrap().to_string(), "2000m");
+
+    // a referenced text column
+    let results = table.evaluate(&mut calculator, "city * 2");
+    assert!(matches!(
+        &results[0],
+        Err(ComputeError::InvalidColumnValue(c, v)) if c == "city" && v == "Prague"
+    ));
+}
+
+#[test]
+fn test_with_results() {
+    let table = CsvTable::parse(TRIPS).unwrap();
+    let mut calculator = Calculator::default();
+
+    let results = table.evaluate(&mut calculator, "distance in m");
+    assert_eq!(
+        table.clone().with_results("meters", &results).to_string(),
+        "\
+trip,distance [km],duration [h],fuel price,meters [m]
+Prague - Brno,205,2.5,1.65,205000
+\"Brno, \"\"center\"\"\",3.5,0.25,1.70,3500.0
+Unknown,,1,abc,
+"
+    );
+
+    let results = table.evaluate(&mut calculator, "fuel_price * 2");
+    assert_eq!(
+        table
+            .with_results("price", &results)
+            .header()
+            .last()
+            .unwrap(),
+        "price"
+    );
+}
@@ -0,0 +1,483 @@
+use std::fmt::{Display, Formatter};
+
+use chrono::{
+    DateTime, Datelike, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, TimeZone, Utc,
+};
+use chrono_tz::Tz;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal_macros::dec;
+
+use crate::context::Context;
+use crate::expression::NumericExpression;
+use crate::holidays::Holidays;
+use crate::units::{Time, Unit};
+use crate::{ComputeError, Decimal};
+
+/// Calendar date, date with time of day or time of day, optionally in a time zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DateTimeValue {
+    Date(NaiveDate),
+    DateTime(NaiveDateTime),
+    Time(NaiveTime),
+    Zoned(DateTime<Tz>),
+    /// time of day in a zone, placed on the current date of the zone
+    ZonedTime(DateTime<Tz>),
+}
+
+impl DateTimeValue {
+    /// Parses ISO 8601 literals: `2026-12-24`, `2026-12-24T18:00`, `2026-12-24T18:00:30`, `18:00`,
+    /// `Z` suffix places the value in UTC: `2026-12-24T18:00Z`, a time of day is placed on the
+    /// current date again when it is converted to a zone.
+    pub fn parse(s: &str) -> Option<DateTimeValue> {
+        if let Some(utc) = s.strip_suffix('Z') {
+            return DateTimeValue::parse(utc)?.in_zone(Tz::UTC, Utc::now());
+        }
+
+        if let Ok(d) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+            return Some(DateTimeValue::Date(d));
+        }
+
+        for format in [
+            "%Y-%m-%dT%H:%M",
+            "%Y-%m-%dT%H:%M:%S",
+            "%Y-%m-%dT%H:%M:%S%.f",
+        ] {
+            if let Ok(dt) = NaiveDateTime::parse_from_str(s, format) {
+                return Some(DateTimeValue::DateTime(dt));
+            }
+        }
+
+        for format in ["%H:%M", "%H:%M:%S", "%H:%M:%S%.f"] {
+            if let Ok(t) = NaiveTime::parse_from_str(s, format) {
+                return Some(DateTimeValue::Time(t));
+            }
+        }
+
+        None
+    }
+
+    /// Splits a literal followed by units: `15:00Prague` => (15:00, `Prague`)
+    pub(super) fn parse_prefix(s: &str) -> Option<(DateTimeValue, &str)> {
+        let mut end = 0;
+        let mut chars = s.char_indices().peekable();
+
+        while let Some((i, c)) = chars.next() {
+            let next_is_digit = chars.peek().is_some_and(|(_, c)| c.is_ascii_digit());
+            if !(c.is_ascii_digit() || ("-:.".contains(c) || c == 'T') && next_is_digit) {
+                break;
+            }
+            end = i + c.len_utf8();
+        }
+
+        // UTC designator, not a start of a zone name: `18:00Z`, but `18:00Zurich`
+        let rest = &s[end..];
+        if rest.starts_with('Z') && !rest[1..].starts_with(char::is_alphabetic) {
+            end += 1;
+        }
+
+        let (literal, rest) = s.split_at(end);
+        if !literal.contains([':', '-']) {
+            return None;
+        }
+
+        DateTimeValue::parse(literal).map(|v| (v, rest))
+    }
+
+    pub fn date(&self) -> Option<NaiveDate> {
+        match self {
+            DateTimeValue::Date(d) => Some(*d),
+            DateTimeValue::DateTime(dt) => Some(dt.date()),
+            DateTimeValue::Zoned(dt) => Some(dt.date_naive()),
+            DateTimeValue::Time(_) | DateTimeValue::ZonedTime(_) => None,
+        }
+    }
+
+    fn naive(&self) -> Option<NaiveDateTime> {
+        match self {
+            DateTimeValue::Date(d) => Some(d.and_time(NaiveTime::MIN)),
+            DateTimeValue::DateTime(dt) => Some(*dt),
+            _ => None,
+        }
+    }
+
+    fn zoned(&self) -> Option<DateTime<Tz>> {
+        match self {
+            DateTimeValue::Zoned(dt) | DateTimeValue::ZonedTime(dt) => Some(*dt),
+            _ => None,
+        }
+    }
+
+    /// Date stays a date when whole days are added.
+    pub fn add(&self, delta: TimeDelta) -> Option<DateTimeValue> {
+        match self {
+            DateTimeValue::Date(d)
+                if delta.subsec_nanos() == 0 && delta.num_seconds() % 86400 == 0 =>
+            {
+                d.checked_add_signed(delta).map(DateTimeValue::Date)
+            }
+            DateTimeValue::Date(_) | DateTimeValue::DateTime(_) => self
+                .naive()?
+                .checked_add_signed(delta)
+                .map(DateTimeValue::DateTime),
+            DateTimeValue::Time(t) => Some(DateTimeValue::Time(t.overflowing_add_signed(delta).0)),
+            // zoned values are shifted in absolute time, DST changes are respected
+            DateTimeValue::Zoned(dt) => dt.checked_add_signed(delta).map(DateTimeValue::Zoned),
+            DateTimeValue::ZonedTime(dt) => {
+                dt.checked_add_signed(delta).map(DateTimeValue::ZonedTime)
+            }
+        }
+    }
+
+    /// Same day of the month, the last day if the month is shorter: 2026-01-31 + 1 month = 2026-02-28
+    pub fn add_months(&self, months: i32) -> Option<DateTimeValue> {
+        let delta = Months::new(months.unsigned_abs());
+
+        self.map_date(|d| {
+            if months < 0 {
+                d.checked_sub_months(delta)
+            } else {
+                d.checked_add_months(delta)
+            }
+        })
+    }
+
+    /// Moves the date by working days, a non-working day is first moved to the next working day.
+    pub fn add_workdays(&self, workdays: i64, holidays: &Holidays) -> Option<DateTimeValue> {
+        let step = if workdays < 0 { -1 } else { 1 };
+
+        self.map_date(|mut d| {
+            while !holidays.is_workday(d) {
+                d = d.checked_add_signed(TimeDelta::days(step))?;
+            }
+
+            // whole weeks are skipped at once, then shortened by the holidays in them
+            let mut remaining = workdays.abs();
+            while remaining >= 5 {
+                let next = d.checked_add_signed(TimeDelta::weeks(step * (remaining / 5)))?;
+                let skipped = match step {
+                    1 => holidays.workdays(d.succ_opt()?, next),
+                    _ => holidays.workdays(next, d.pred_opt()?),
+                };
+
+                if skipped == 0 {
+                    break;
+                }
+                d = next;
+                remaining -= skipped;
+            }
+
+            for _ in 0..remaining {
+                d = d.checked_add_signed(TimeDelta::days(step))?;
+                while !holidays.is_workday(d) {
+                    d = d.checked_add_signed(TimeDelta::days(step))?;
+                }
+            }
+
+            Some(d)
+        })
+    }
+
+    /// Changes the date, the time of day is kept.
+    fn map_date(&self, f: impl Fn(NaiveDate) -> Option<NaiveDate>) -> Option<DateTimeValue> {
+        match self {
+            DateTimeValue::Date(d) => f(*d).map(DateTimeValue::Date),
+            DateTimeValue::DateTime(dt) => {
+                f(dt.date()).map(|d| DateTimeValue::DateTime(d.and_time(dt.time())))
+            }
+            DateTimeValue::Zoned(dt) => {
+                let naive = f(dt.date_naive())?.and_time(dt.time());
+                dt.timezone()
+                    .from_local_datetime(&naive)
+                    .earliest()
+                    .map(DateTimeValue::Zoned)
+            }
+            DateTimeValue::Time(_) | DateTimeValue::ZonedTime(_) => None,
+        }
+    }
+
+    /// Difference of two dates in days, in seconds if any of them has a time of day.
+    pub fn difference(&self, other: &DateTimeValue) -> Option<NumericExpression> {
+        let delta = match (self, other) {
+            (DateTimeValue::Date(a), DateTimeValue::Date(b)) => {
+                return Some(NumericExpression::with_unit(
+                    Decimal::from((*a - *b).num_days()),
+                    Some(Unit::Time(Time::Day)),
+                ));
+            }
+            (DateTimeValue::Time(a), DateTimeValue::Time(b)) => *a - *b,
+            _ => match (self.zoned(), other.zoned()) {
+                (Some(a), Some(b)) => a - b,
+                (None, None) => self.naive()? - other.naive()?,
+                _ => return None,
+            },
+        };
+
+        let nanos = Decimal::from(delta.num_nanoseconds()?);
+        Some(NumericExpression::with_unit(
+            nanos / dec!(1_000_000_000),
+            Some(Unit::Time(Time::Second(None))),
+        ))
+    }
+
+    /// Seconds since the Unix epoch, value without a zone is taken as UTC.
+    pub fn unix_seconds(&self) -> Option<Decimal> {
+        let dt = match self {
+            DateTimeValue::Date(_) | DateTimeValue::DateTime(_) => self.naive()?.and_utc(),
+            DateTimeValue::Zoned(dt) | DateTimeValue::ZonedTime(dt) => dt.with_timezone(&Utc),
+            DateTimeValue::Time(_) => return None,
+        };
+
+        Some(
+            Decimal::from(dt.timestamp())
+                + Decimal::from(dt.timestamp_subsec_nanos()) / dec!(1_000_000_000),
+        )
+    }
+
+    /// Instant in UTC from seconds since the Unix epoch.
+    pub fn from_unix_seconds(seconds: Decimal) -> Option<DateTimeValue> {
+        let whole = seconds.floor();
+        let nanos = ((seconds - whole) * dec!(1_000_000_000)).round();
+
+        DateTime::from_timestamp(whole.to_i64()?, nanos.to_u32()?)
+            .map(|dt| DateTimeValue::Zoned(dt.with_timezone(&Tz::UTC)))
+    }
+
+    /// Value without a zone is placed into the zone, zoned value is converted to the zone. Time of
+    /// day is placed on the date of `now` in its zone, the DST offsets are of that date.
+    pub fn in_zone(&self, zone: Tz, now: DateTime<Utc>) -> Option<DateTimeValue> {
+        // local time skipped by a DST change does not exist
+        let localize = |naive: NaiveDateTime, zone: Tz| zone.from_local_datetime(&naive).earliest();
+        let today = |zone: Tz| now.with_timezone(&zone).date_naive();
+
+        match self {
+            DateTimeValue::Date(_) | DateTimeValue::DateTime(_) => {
+                localize(self.naive()?, zone).map(DateTimeValue::Zoned)
+            }
+            DateTimeValue::Time(t) => {
+                localize(today(zone).and_time(*t), zone).map(DateTimeValue::ZonedTime)
+            }
+            DateTimeValue::Zoned(dt) => Some(DateTimeValue::Zoned(dt.with_timezone(&zone))),
+            DateTimeValue::ZonedTime(dt) => {
+                let from = dt.timezone();
+                localize(today(from).and_time(dt.time()), from)
+                    .map(|dt| DateTimeValue::ZonedTime(dt.with_timezone(&zone)))
+            }
+        }
+    }
+}
+
+impl Display for DateTimeValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DateTimeValue::Date(d) => write!(f, "{}", d.format("%Y-%m-%d")),
+            DateTimeValue::DateTime(dt) => write!(f, "{}", dt.format("%Y-%m-%dT%H:%M:%S%.f")),
+            DateTimeValue::Time(t) => write!(f, "{}", t.format("%H:%M:%S%.f")),
+            DateTimeValue::Zoned(dt) => write!(
+                f,
+                "{} {}",
+                dt.format("%Y-%m-%dT%H:%M:%S%.f"),
+                dt.timezone().name()
+            ),
+            DateTimeValue::ZonedTime(dt) => {
+                write!(f, "{} {}", dt.format("%H:%M:%S%.f"), dt.timezone().name())
+            }
+        }
+    }
+}
+
+/// Written as the displayed text: `2026-12-24T15:00:00 Europe/Prague`
+#[cfg(feature = "serde")]
+impl serde::Serialize for DateTimeValue {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for DateTimeValue {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        let invalid = || serde::de::Error::custom(format!("invalid date: '{}'", s));
+
+        match s.split_once(' ') {
+            None => DateTimeValue::parse(&s).ok_or_else(invalid),
+            Some((value, zone)) => {
+                let zone = zone.parse::<Tz>().map_err(|_| invalid())?;
+                DateTimeValue::parse(value)
+                    .and_then(|v| v.in_zone(zone, Utc::now()))
+                    .ok_or_else(invalid)
+            }
+        }
+    }
+}
+
+/// Duration of a time quantity, other units are ignored: `5m` (minute or metre) is 5 minutes.
+pub(super) fn duration(n: &NumericExpression) -> Option<TimeDelta> {
+    n.values().iter().find_map(|(v, u)| match u {
+        Some(Unit::Time(t)) => {
+            let nanos = v * t.reference_unit_multiplier() * dec!(1_000_000_000);
+            Some(TimeDelta::nanoseconds(nanos.round().to_i64()?))
+        }
+        _ => None,
+    })
+}
+
+/// Instant of a number counted from an epoch, plain numbers are Unix seconds: `1700000000 ms`
+pub(super) fn from_epoch(n: &NumericExpression) -> Option<DateTimeValue> {
+    let [(v, unit)] = n.values()[..] else {
+        return None;
+    };
+
+    let seconds = match unit {
+        None => v,
+        Some(Unit::Epoch(e)) => e.to_reference_unit(v)?,
+        Some(Unit::Time(t)) => v * t.reference_unit_multiplier(),
+        _ => return None,
+    };
+
+    DateTimeValue::from_unix_seconds(seconds)
+}
+
+/// Whole number of calendar months of a quantity in months, years ...
+fn calendar_months(n: &NumericExpression) -> Option<i32> {
+    n.values().iter().find_map(|(v, u)| match u {
+        Some(Unit::Time(t)) => {
+            let months = v * t.calendar_months()?;
+            months.fract().is_zero().then_some(months.to_i32()?)
+        }
+        _ => None,
+    })
+}
+
+/// Whole number of working days of a quantity in workdays.
+fn workdays(n: &NumericExpression) -> Option<i64> {
+    n.values().iter().find_map(|(v, u)| match u {
+        Some(Unit::Time(Time::Workday)) => v.fract().is_zero().then_some(v.to_i64()?),
+        _ => None,
+    })
+}
+
+/// Adds the quantity to the date, months and years by the calendar.
+fn shift(
+    d: &DateTimeValue,
+    n: &NumericExpression,
+    sign: i32,
+    ctx: &Context,
+) -> Option<DateTimeValue> {
+    if n.units().contains(&Some(Unit::Time(Time::Workday))) {
+        return d.add_workdays(i64::from(sign) * workdays(n)?, ctx.holidays());
+    }
+
+    if let Some(months) = calendar_months(n) {
+        return d.add_months(sign * months);
+    }
+
+    let delta = duration(n)?;
+    d.add(if sign < 0 { -delta } else { delta })
+}
+
+pub(super) fn invoke_operator(
+    operator: char,
+    variables: &[NumericExpression],
+    ctx: &Context,
+) -> Result<NumericExpression, ComputeError> {
+    let invalid = || {
+        ComputeError::InvalidDateTimeOperation(
+            variables
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(&operator.to_string()),
+        )
+    };
+
+    let [left, right] = variables else {
+        return Err(invalid());
+    };
+
+    let res = match (left, operator, right) {
+        (NumericExpression::DateTime(a), '-', NumericExpression::DateTime(b)) => {
+            // value without a zone is in the zone of the calculation: now - today
+            let zone = ctx.local_zone().0;
+            let promote = |v: &DateTimeValue, other: &DateTimeValue| match other.zoned() {
+                Some(_) if v.zoned().is_none() => v.in_zone(zone, ctx.now()),
+                _ => Some(*v),
+            };
+            let (a, b) = (
+                promote(a, b).ok_or_else(invalid)?,
+                promote(b, a).ok_or_else(invalid)?,
+            );
+
+            return a.difference(&b).ok_or_else(invalid);
+        }
+        (NumericExpression::DateTime(a), '+', d) | (d, '+', NumericExpression::DateTime(a)) => {
+            shift(a, d, 1, ctx)
+        }
+        (NumericExpression::DateTime(a), '-', d) => shift(a, d, -1, ctx),
+        _ => None,
+    };
+
+    res.map(NumericExpression::DateTime).ok_or_else(invalid)
+}
+
+fn date_param(params: &[NumericExpression]) -> Option<NaiveDate> {
+    match params {
+        [NumericExpression::DateTime(v)] => v.date(),
+        _ => None,
+    }
+}
+
+pub(super) fn weekday(params: &[NumericExpression], _: &Context) -> Option<NumericExpression> {
+    let d = date_param(params)?;
+    Some(NumericExpression::Number(
+        d.weekday().number_from_monday().into(),
+    ))
+}
+
+pub(super) fn iso_week(params: &[NumericExpression], _: &Context) -> Option<NumericExpression> {
+    let d = date_param(params)?;
+    Some(NumericExpression::Number(d.iso_week().week().into()))
+}
+
+pub(super) fn leap_year(params: &[NumericExpression], _: &Context) -> Option<NumericExpression> {
+    let d = date_param(params)?;
+    Some(NumericExpression::Number(
+        (is_leap_year(d.year().into()) as u8).into(),
+    ))
+}
+
+/// Number of working days between the dates, both included, negative if the first date is later.
+pub(super) fn workdays_between(
+    params: &[NumericExpression],
+    ctx: &Context,
+) -> Option<NumericExpression> {
+    let [NumericExpression::DateTime(from), NumericExpression::DateTime(to)] = params else {
+        return None;
+    };
+    let (from, to) = (from.date()?, to.date()?);
+    let (first, last, sign) = if from <= to {
+        (from, to, 1)
+    } else {
+        (to, from, -1)
+    };
+
+    let count = ctx.holidays().workdays(first, last);
+
+    Some(NumericExpression::with_unit(
+        Decimal::from(count * sign),
+        Some(Unit::Time(Time::Workday)),
+    ))
+}
+
+pub(super) fn is_workday(params: &[NumericExpression], ctx: &Context) -> Option<NumericExpression> {
+    let d = date_param(params)?;
+    Some(NumericExpression::Number(
+        (ctx.holidays().is_workday(d) as u8).into(),
+    ))
+}
+
+pub(super) fn is_leap_year(year: Decimal) -> bool {
+    year.to_i32()
+        .and_then(|y| NaiveDate::from_ymd_opt(y, 2, 29))
+        .is_some()
+}
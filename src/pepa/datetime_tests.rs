@@ -0,0 +1,112 @@
+use chrono::{Days, Local};
+
+use crate::Calculator;
+
+fn test_results(tests: &[(&str, &str)]) {
+    for (expression, expected) in tests {
+        let mut calculator = Calculator::default();
+        let statement = calculator.compute(expression).unwrap();
+
+        match &statement.result {
+            Some(Ok(n)) => assert_eq!(n.to_string(), *expected, "{}", expression),
+            r => panic!("{:?}: {:?}", expression, r),
+        }
+    }
+}
+
+#[test]
+fn test_literals() {
+    test_results(&[
+        ("2026-12-24", "2026-12-24"),
+        ("2026-12-24T18:00", "2026-12-24T18:00:00"),
+        ("2026-12-24T18:00:30.5", "2026-12-24T18:00:30.500"),
+    ]);
+}
+
+#[test]
+fn test_arithmetic() {
+    test_results(&[
+        ("2026-12-24 + 2 days", "2026-12-26"),
+        ("2 days + 2026-12-24", "2026-12-26"),
+        ("2026-12-24 - 24 days", "2026-11-30"),
+        ("2026-12-24 + 36 hours", "2026-12-25T12:00:00"),
+        ("2026-12-24T18:00 + 90 min", "2026-12-24T19:30:00"),
+        ("2026-12-24 - 2026-12-01", "23d"),
+        ("2026-12-24 - 2026-12-01 in hours", "552.0h"),
+        ("2026-12-24T12:00 - 2026-12-24", "43200s"),
+        ("(2026-12-24 + 1 day) - 2026-12-24", "1d"),
+        // spaces around the hyphens make a subtraction
+        ("2030 - 12 - 24", "1994"),
+        ("2030-12 - 24", "1994"),
+    ]);
+}
+
+#[test]
+fn test_calendar_units() {
+    test_results(&[
+        ("2026-12-24 + 3 weeks", "2027-01-14"),
+        ("2026-12-24 + 1 fortnight", "2027-01-07"),
+        ("2026-01-31 + 1 month", "2026-02-28"),
+        ("2026-03-31 - 1 month", "2026-02-28"),
+        ("2024-02-29 + 1 year", "2025-02-28"),
+        ("2026-12-24T18:00 + 2 years", "2028-12-24T18:00:00"),
+        ("2026-12-24 + 1 decade", "2036-12-24"),
+        ("2026-12-24 - 1 century", "1926-12-24"),
+        ("2026-12-24 + (3 / 2) years", "2028-06-24"),
+        ("2026-12-24 + (1 / 3) month", "2027-01-03T03:29:42"),
+        ("2026-12-24 + 1 julian year", "2027-12-24T06:00:00"),
+        (
+            "2026-12-24 Prague + 6 months",
+            "2027-06-24T00:00:00 Europe/Prague",
+        ),
+    ]);
+}
+
+#[test]
+fn test_keywords() {
+    let today = Local::now().date_naive();
+
+    test_results(&[
+        ("tomorrow - today", "1d"),
+        ("today - yesterday in hours", "24.0h"),
+        ("today + 1 day - tomorrow", "0d"),
+        (
+            "2 days + today",
+            &(today + Days::new(2)).format("%Y-%m-%d").to_string(),
+        ),
+    ]);
+}
+
+#[test]
+fn test_functions() {
+    test_results(&[
+        ("weekday(2026-12-24)", "4"),
+        ("weekday(2026-12-24 + 3 days)", "7"),
+        ("week(2026-12-24)", "52"),
+        ("week(2027-01-01)", "53"),
+        ("leapyear(2024-05-01)", "1"),
+        ("leapyear(2026-05-01)", "0"),
+        ("leapyear(2000)", "1"),
+        ("leapyear(1900)", "0"),
+    ]);
+}
+
+#[test]
+fn test_errors() {
+    for expression in [
+        "2026-12-24 + 5",
+        "2026-12-24 * 2 days",
+        "2026-12-24 + 2026-12-24",
+        "2026-12-24 + 5 km",
+        "2026-12-24 in hours",
+        "weekday(5)",
+        "leapyear(2024.5)",
+    ] {
+        let mut calculator = Calculator::default();
+        let statement = calculator.compute(expression).unwrap();
+
+        if let Some(Ok(n)) = &statement.result {
+            panic!("{:?} = {} expected to fail", expression, n);
+        }
+    }
+}
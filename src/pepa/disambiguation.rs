@@ -0,0 +1,76 @@
+use crate::expression::NumericExpression;
+use crate::units::{Unit, UnitFamily};
+use crate::Warning;
+
+/// Families tried after the preferred ones, SI symbols first: `m` is a metre, `t` a tonne.
+const FALLBACK: [UnitFamily; 10] = [
+    UnitFamily::Length,
+    UnitFamily::Mass,
+    UnitFamily::Volume,
+    UnitFamily::Speed,
+    UnitFamily::Time,
+    UnitFamily::Temperature,
+    UnitFamily::Angle,
+    UnitFamily::Currency,
+    UnitFamily::TimeZone,
+    UnitFamily::Epoch,
+];
+
+/// Picks one interpretation of a result carrying several units of an ambiguous abbreviation.
+///
+/// Operands and conversion targets already removed the interpretations they do not allow:
+/// `5m + 1km` is a length, `5m in s` a time. What is left is decided by the preferred families.
+pub(crate) fn disambiguate(
+    n: NumericExpression,
+    preferred: &[UnitFamily],
+) -> (NumericExpression, Option<Warning>) {
+    if let NumericExpression::List(l) = n {
+        let mut warning = None;
+        let l = l
+            .into_iter()
+            .map(|n| {
+                let (n, w) = disambiguate(n, preferred);
+                warning = warning.take().or(w);
+                n
+            })
+            .collect();
+
+        return (NumericExpression::List(l), warning);
+    }
+
+    let NumericExpression::MultipleNumbersWithUnit(values) = &n else {
+        return (n, None);
+    };
+
+    let units = values.iter().filter_map(|(_, u)| *u).collect::<Vec<_>>();
+
+    let Some(chosen) = preferred
+        .iter()
+        .chain(FALLBACK.iter())
+        .find_map(|f| units.iter().find(|u| u.family() == *f))
+        .copied()
+    else {
+        return (n, None);
+    };
+
+    let Some((v, _)) = values.iter().find(|(_, u)| *u == Some(chosen)) else {
+        return (n, None);
+    };
+
+    let warning = Warning::AmbiguousUnit {
+        symbol: chosen.to_string_with_plural(v),
+        chosen,
+        alternatives: units.iter().filter(|u| **u != chosen).copied().collect(),
+    };
+
+    (NumericExpression::NumberWithUnit(*v, chosen), Some(warning))
+}
+
+/// Families of the units, for the warning message: `time, volume`
+pub(crate) fn families(units: &[Unit]) -> String {
+    units
+        .iter()
+        .map(|u| u.family().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,80 @@
+use crate::units::{Length, Time};
+use crate::{Calculator, NumericExpression, Unit, UnitFamily, Warning};
+
+fn compute(calculator: &mut Calculator, expression: &str) -> (String, Vec<Warning>) {
+    let statement = calculator.compute(expression).unwrap();
+
+    match &statement.result {
+        Some(Ok(n)) => (n.to_string(), statement.warnings.clone()),
+        r => panic!("{:?}: {:?}", expression, r),
+    }
+}
+
+#[test]
+fn test_context() {
+    // operands and conversion targets decide, no warning
+    for (expression, expected) in [
+        ("5m + 1km", "1.005km"),
+        ("5m + 1 h", "1.0833333333333333333333333333h"),
+        ("5m in s", "300.0s"),
+        ("5m in ft", "16.404199475065616797900262467ft"),
+    ] {
+        let (result, warnings) = compute(&mut Calculator::default(), expression);
+        assert_eq!(result, expected, "{}", expression);
+        assert!(warnings.is_empty(), "{}: {:?}", expression, warnings);
+    }
+}
+
+#[test]
+fn test_default_family() {
+    let mut calculator = Calculator::default();
+
+    let (result, warnings) = compute(&mut calculator, "5m + 10m");
+    assert_eq!(result, "15m");
+    assert_eq!(
+        warnings,
+        vec![Warning::AmbiguousUnit {
+            symbol: String::from("m"),
+            chosen: Unit::Length(Length::Meter(None)),
+            alternatives: vec![Unit::Time(Time::Minute)],
+        }]
+    );
+    assert_eq!(
+        warnings[0].to_string(),
+        "Ambiguous unit 'm' read as length, also time"
+    );
+
+    let statement = calculator.compute("5m").unwrap();
+    assert_eq!(
+        statement.result.as_ref().unwrap().as_ref().unwrap(),
+        &NumericExpression::NumberWithUnit(5.into(), Unit::Length(Length::Meter(None)))
+    );
+}
+
+#[test]
+fn test_preferred_family() {
+    let mut calculator = Calculator::default();
+    calculator.set_preferred_families(&[UnitFamily::Time]);
+
+    let (result, warnings) = compute(&mut calculator, "5m * 3");
+    assert_eq!(result, "15m");
+    assert!(matches!(
+        warnings[..],
+        [Warning::AmbiguousUnit {
+            chosen: Unit::Time(Time::Minute),
+            ..
+        }]
+    ));
+
+    // context still wins over the preference
+    let (result, warnings) = compute(&mut calculator, "5m + 1km");
+    assert_eq!(result, "1.005km");
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn test_family_names() {
+    assert_eq!(UnitFamily::TimeZone.to_string(), "time_zone");
+    assert_eq!("Length".parse::<UnitFamily>(), Ok(UnitFamily::Length));
+    assert_eq!(Unit::Time(Time::Minute).family(), UnitFamily::Time);
+}
@@ -0,0 +1,596 @@
+use itertools::Itertools;
+use rust_decimal::MathematicalOps;
+use rust_decimal_macros::dec;
+
+use crate::compute;
+use crate::compute::Bindings;
+use crate::context::Context;
+use crate::expression::{Expression, ExpressionToken, NumericExpression};
+use crate::matrix::Matrix;
+use crate::operators::operators;
+use crate::units::Unit;
+use crate::{ComputeError, Decimal};
+
+/// Halvings of a bracketed root, more than enough for the precision of `Decimal`.
+const BISECTION_STEPS: usize = 120;
+
+/// Relative difference still read as equal, the last digits of inexact divisions differ.
+const TOLERANCE: Decimal = dec!(0.000000000001);
+
+/// Values of the unknown checking the linearity of a residual and whether it is zero for all.
+const SAMPLES: [Decimal; 5] = [
+    Decimal::ONE,
+    Decimal::TWO,
+    Decimal::ZERO,
+    dec!(-3.5),
+    dec!(10.25),
+];
+
+/// Solves an equation for its single unknown: `2x + 3 = 11` => `x = 4`
+pub(super) fn solve_equation(
+    left: &Expression,
+    right: &Expression,
+    ctx: &Context,
+) -> Result<NumericExpression, ComputeError> {
+    let equation = ExpressionToken::Equation(left.clone(), right.clone());
+    Equations::new(&[Expression::from_tokens(vec![equation])], vec![], ctx)?.solve()
+}
+
+/// `solve` of equations and of expressions equal to zero, trailing names are the unknowns:
+/// `solve(x^2 - 2, x)`, `solve(x + z = 10, x - z = 2)`
+///
+/// `None` for parameters without an unknown, these are computed: `solve([[2, 1], [1, 3]], [5, 10])`
+pub(super) fn invoke_function(
+    name: &str,
+    params: &ExpressionToken,
+    ctx: &Context,
+) -> Option<Result<NumericExpression, ComputeError>> {
+    if name != "solve" {
+        return None;
+    }
+
+    let params = match params {
+        ExpressionToken::List(l) => l.clone(),
+        ExpressionToken::Expression(e) => vec![e.clone()],
+        ExpressionToken::Equation(..) | ExpressionToken::Symbol(_) => {
+            vec![Expression::from_tokens(vec![params.clone()])]
+        }
+        _ => return None,
+    };
+
+    let mut names = params
+        .iter()
+        .skip(1)
+        .rev()
+        .map_while(|p| match &p.tokens[..] {
+            [ExpressionToken::Symbol(s)] => Some(s.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    names.reverse();
+
+    let equations = &params[..params.len() - names.len()];
+    if names.is_empty()
+        && !equations
+            .iter()
+            .any(|e| is_equation(e) || has_unknowns(e, ctx))
+    {
+        return None;
+    }
+
+    Some(Equations::new(equations, names, ctx).and_then(|mut e| e.solve()))
+}
+
+/// Roots of a periodic residual are endless, only the ones nearest to zero are kept: `sin(x) = 0.5`
+const MAX_ROOTS: usize = 6;
+
+/// Residuals `left - right` of the equations, computed with the unknowns bound to values.
+struct Equations<'a> {
+    residuals: Vec<Expression>,
+    /// Added and subtracted terms of both sides, the scale of a residual: `x^2 = 100000000000000`
+    terms: Vec<Vec<Expression>>,
+    unknowns: Vec<String>,
+    text: String,
+    bindings: Bindings<'a>,
+}
+
+impl<'a> Equations<'a> {
+    fn new(
+        equations: &[Expression],
+        unknowns: Vec<String>,
+        ctx: &'a Context,
+    ) -> Result<Equations<'a>, ComputeError> {
+        let text = equations.iter().join(", ");
+
+        let unknowns = if unknowns.is_empty() {
+            let mut names = Vec::new();
+            equations.iter().for_each(|e| symbols(e, &mut names));
+            names.retain(|s| is_unknown(s, ctx));
+            names
+        } else {
+            unknowns
+        };
+
+        if unknowns.is_empty() {
+            return Err(ComputeError::InvalidEquation(text));
+        }
+
+        Ok(Equations {
+            residuals: equations.iter().map(residual).collect(),
+            terms: equations.iter().map(terms).collect(),
+            unknowns,
+            text,
+            bindings: Bindings::new(ctx),
+        })
+    }
+
+    fn solve(&mut self) -> Result<NumericExpression, ComputeError> {
+        match (self.residuals.len(), self.unknowns.len()) {
+            (1, 1) => self.solve_single(),
+            (e, u) if e == u => self.solve_linear_system(),
+            _ => Err(ComputeError::InvalidEquation(self.text.clone())),
+        }
+    }
+
+    /// Exact root of a linear equation, otherwise all roots bracketed by sign changes.
+    ///
+    /// The unknown has the unit of the residual if it can be used with it: `x + 30 min = 2 h`,
+    /// see [`Equations::unit_by_terms`] for an unknown multiplied by a value with a unit.
+    fn solve_single(&mut self) -> Result<NumericExpression, ComputeError> {
+        let unit = match self.unit_by_terms() {
+            Some(unit) => unit,
+            None => self.unit_by_probe()?,
+        };
+
+        let reference = self
+            .residual(0, &[NumericExpression::with_unit(Decimal::ONE, unit)])
+            .map(|r| first_unit(&r))
+            .unwrap_or_default();
+
+        // an identity does not determine the unknown: x = x
+        if SAMPLES.iter().all(|x| self.is_root(*x, unit, reference)) {
+            return Err(ComputeError::NoSolution(self.text.clone()));
+        }
+
+        let mut f = |x: Decimal| {
+            self.residual_value(0, &[NumericExpression::with_unit(x, unit)], reference)
+        };
+
+        let linear = linear_root(&mut f)?;
+        let mut roots = match linear.filter(|root| self.is_root(*root, unit, reference)) {
+            Some(root) => vec![root],
+            None => {
+                let mut f = |x: Decimal| {
+                    self.residual_value(0, &[NumericExpression::with_unit(x, unit)], reference)
+                };
+                roots(&mut f)?
+            }
+        };
+        // a pole changes the sign too: 1 / x
+        roots.retain(|root| self.is_root(*root, unit, reference));
+
+        if roots.len() > MAX_ROOTS {
+            roots.sort_by_key(|x| x.abs());
+            roots.truncate(MAX_ROOTS);
+            roots.sort();
+        }
+
+        if roots.is_empty() {
+            return Err(ComputeError::NoSolution(self.text.clone()));
+        }
+
+        Ok(NumericExpression::Solution(
+            roots
+                .into_iter()
+                .map(|x| {
+                    (
+                        self.unknowns[0].clone(),
+                        NumericExpression::with_unit(x, unit),
+                    )
+                })
+                .collect(),
+        ))
+    }
+
+    /// Unit of the residual computed with numbers, if the unknown can have it.
+    fn unit_by_probe(&mut self) -> Result<Option<Unit>, ComputeError> {
+        let probe = [
+            Decimal::ONE,
+            Decimal::TWO,
+            dec!(0.5),
+            Decimal::NEGATIVE_ONE,
+            Decimal::ZERO,
+        ]
+        .iter()
+        .map(|x| self.residual(0, &[NumericExpression::Number(*x)]))
+        .find_or_last(|r| r.is_ok())
+        .unwrap_or_else(|| Err(ComputeError::InvalidEquation(self.text.clone())))
+        .map_err(|e| match e {
+            // the unknown would need a compound unit without a name: `x * 3 h = 150 USD`
+            ComputeError::OperatorsConversionError(..) | ComputeError::UnitConversionError(..) => {
+                ComputeError::UnsupportedUnitOfUnknown(self.text.clone())
+            }
+            e => e,
+        })?;
+
+        Ok(first_unit(&probe).filter(|u| {
+            let n = NumericExpression::with_unit(Decimal::ONE, Some(*u));
+            self.residual(0, &[n]).is_ok()
+        }))
+    }
+
+    /// Unit of an unknown multiplied by a value with a unit, the terms are computed with a number
+    /// for it: none for the unit of the other terms, `x * AAPL = 460 USD` => `x = 2`, or their
+    /// quotient, `x * 3 h = 150 km` => `x = 50km/h`
+    ///
+    /// `None` without units on both the term of the unknown and the other terms.
+    fn unit_by_terms(&mut self) -> Option<Option<Unit>> {
+        let one = [NumericExpression::Number(Decimal::ONE)];
+        let (mut coefficient, mut other) = (None, None);
+
+        for term in self.terms[0].iter() {
+            let mut names = Vec::new();
+            symbols(term, &mut names);
+
+            let value = self.bindings.compute(term, &self.unknowns, &one).ok();
+            let Some(u) = value.as_ref().and_then(first_unit) else {
+                continue;
+            };
+
+            if names.contains(&self.unknowns[0]) {
+                coefficient.get_or_insert(u);
+            } else {
+                other.get_or_insert(u);
+            }
+        }
+
+        let (coefficient, other) = (coefficient?, other?);
+        if coefficient.family() == other.family() {
+            return Some(None);
+        }
+
+        let quotient = compute::invoke_operator(
+            &operators()[&'/'],
+            vec![
+                NumericExpression::with_unit(Decimal::ONE, Some(other)),
+                NumericExpression::with_unit(Decimal::ONE, Some(coefficient)),
+            ],
+            self.bindings.context(),
+        )
+        .ok()?;
+
+        let unit = first_unit(&quotient)?;
+        let n = NumericExpression::with_unit(Decimal::ONE, Some(unit));
+        self.residual(0, &[n]).is_ok().then_some(Some(unit))
+    }
+
+    /// Coefficients of the unknowns are the changes of the residuals: `x + z = 10, x - z = 2`
+    ///
+    /// The unknowns have the unit of the first residual if they can be used with it:
+    /// `x + z = 10 km, x - z = 2 km` => `x = 6km, z = 4km`
+    fn solve_linear_system(&mut self) -> Result<NumericExpression, ComputeError> {
+        let n = self.unknowns.len();
+        let text = self.text.clone();
+        let non_linear = || ComputeError::InvalidEquation(text.clone());
+
+        let zeros = vec![NumericExpression::Number(Decimal::ZERO); n];
+        let unit = (0..n)
+            .find_map(|i| self.residual(i, &zeros).ok().and_then(|r| first_unit(&r)))
+            .filter(|u| {
+                let ones = vec![NumericExpression::with_unit(Decimal::ONE, Some(*u)); n];
+                (0..n).all(|i| self.residual(i, &ones).is_ok())
+            });
+
+        let mut rows = Vec::with_capacity(n);
+        let mut constants = Vec::with_capacity(n);
+
+        for i in 0..n {
+            let zero = self.residual(
+                i,
+                &vec![NumericExpression::with_unit(Decimal::ZERO, unit); n],
+            )?;
+            let reference = first_unit(&zero);
+            let r0 = self
+                .bindings
+                .magnitude(&zero, reference)
+                .ok_or_else(non_linear)?;
+
+            let mut at = |point: Vec<Decimal>| {
+                let values = point
+                    .into_iter()
+                    .map(|x| NumericExpression::with_unit(x, unit))
+                    .collect_vec();
+                self.residual_value(i, &values, reference)
+            };
+
+            let row = (0..n)
+                .map(|j| {
+                    let point = (0..n).map(|k| Decimal::from(u8::from(k == j)));
+                    let r = at(point.collect()).ok_or_else(non_linear)?;
+                    r.checked_sub(r0).ok_or(ComputeError::ArithmeticOverflow)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // a point with all the unknowns checks the linearity
+            let point = (0..n).map(|k| Decimal::from(k + 2)).collect::<Vec<_>>();
+            let expected = row
+                .iter()
+                .zip(&point)
+                .try_fold(r0, |sum, (a, x)| sum.checked_add(a.checked_mul(*x)?))
+                .ok_or(ComputeError::ArithmeticOverflow)?;
+            match at(point) {
+                Some(r) if equal(r, expected)? => {}
+                _ => return Err(non_linear()),
+            }
+
+            rows.push(row);
+            constants.push(vec![-r0]);
+        }
+
+        let a = Matrix::from_rows(rows).ok_or_else(non_linear)?;
+        let b = Matrix::from_rows(constants).ok_or_else(non_linear)?;
+        let x = a.solve(&b).map_err(|e| match e {
+            ComputeError::SingularMatrix => ComputeError::NoSolution(text.clone()),
+            e => e,
+        })?;
+
+        Ok(NumericExpression::Solution(
+            self.unknowns
+                .iter()
+                .enumerate()
+                .map(|(i, name)| {
+                    (
+                        name.clone(),
+                        NumericExpression::with_unit(x.get(i, 0), unit),
+                    )
+                })
+                .collect(),
+        ))
+    }
+
+    fn residual(
+        &mut self,
+        i: usize,
+        values: &[NumericExpression],
+    ) -> Result<NumericExpression, ComputeError> {
+        self.bindings
+            .compute(&self.residuals[i], &self.unknowns, values)
+    }
+
+    /// The residual is zero relative to the terms of the equation, the substituted root is checked
+    /// as a whole: `x^2 = 100000000000000` is not linear even if its constant hides the square
+    fn is_root(&mut self, x: Decimal, unit: Option<Unit>, reference: Option<Unit>) -> bool {
+        let values = [NumericExpression::with_unit(x, unit)];
+        let Some(r) = self.residual_value(0, &values, reference) else {
+            return false;
+        };
+
+        let scale = (0..self.terms[0].len())
+            .map(|t| {
+                let value = self
+                    .bindings
+                    .compute(&self.terms[0][t], &self.unknowns, &values)
+                    .ok()?;
+                self.bindings.magnitude(&value, reference).map(|v| v.abs())
+            })
+            .try_fold(Decimal::ZERO, |sum, v| sum.checked_add(v?));
+
+        scale.is_some_and(|s| r.abs() <= s * TOLERANCE)
+    }
+
+    /// Residual as a number in the unit, `None` if it can not be computed: `1 / x` for `x = 0`
+    fn residual_value(
+        &mut self,
+        i: usize,
+        values: &[NumericExpression],
+        unit: Option<Unit>,
+    ) -> Option<Decimal> {
+        let r = self.residual(i, values).ok()?;
+        self.bindings.magnitude(&r, unit)
+    }
+}
+
+/// Root of a residual changing by the same amount for the same step of the unknown, `None` for
+/// other residuals.
+fn linear_root(
+    f: &mut impl FnMut(Decimal) -> Option<Decimal>,
+) -> Result<Option<Decimal>, ComputeError> {
+    let (Some(a), Some(b)) = (f(SAMPLES[0]), f(SAMPLES[1])) else {
+        return Ok(None);
+    };
+    let slope = checked(b.checked_sub(a))?;
+    if slope.is_zero() {
+        return Ok(None);
+    }
+
+    for x in &SAMPLES[2..] {
+        let Some(v) = f(*x) else {
+            return Ok(None);
+        };
+        let expected = checked((x - Decimal::ONE).checked_mul(slope))?;
+        if !equal(v, checked(a.checked_add(expected))?)? {
+            return Ok(None);
+        }
+    }
+
+    let root = checked(
+        a.checked_div(slope)
+            .and_then(|q| Decimal::ONE.checked_sub(q)),
+    )?;
+    Ok(Some(polish(root, f)))
+}
+
+/// Roots between the points where the residual changes its sign, found by bisection.
+fn roots(f: &mut impl FnMut(Decimal) -> Option<Decimal>) -> Result<Vec<Decimal>, ComputeError> {
+    // 0, ±0.001, ±0.0015, ±0.002 … ±950000000000000
+    let positive = (-3..=14)
+        .flat_map(|e| (2..20).map(move |m| Decimal::from(m) / Decimal::TWO * Decimal::TEN.powi(e)))
+        .collect::<Vec<_>>();
+    let points = positive
+        .iter()
+        .rev()
+        .map(|x| -x)
+        .chain([Decimal::ZERO])
+        .chain(positive.iter().copied())
+        .map(|x| (x, f(x)))
+        .collect::<Vec<_>>();
+
+    let mut roots = Vec::new();
+    for ((a, fa), (b, fb)) in points.iter().tuple_windows() {
+        let (Some(fa), Some(fb)) = (fa, fb) else {
+            continue;
+        };
+
+        if fa.is_zero() {
+            roots.push(*a);
+        } else if fa.is_sign_negative() != fb.is_sign_negative() && !fb.is_zero() {
+            if let Some(root) = bisect(*a, *b, *fa, f)? {
+                roots.push(polish(root, f));
+            }
+        }
+    }
+
+    if let Some((x, Some(v))) = points.last() {
+        if v.is_zero() {
+            roots.push(*x);
+        }
+    }
+
+    Ok(roots.into_iter().map(|x| x.normalize()).unique().collect())
+}
+
+/// `None` if the residual can not be computed inside of the bracket.
+fn bisect(
+    mut a: Decimal,
+    mut b: Decimal,
+    mut fa: Decimal,
+    f: &mut impl FnMut(Decimal) -> Option<Decimal>,
+) -> Result<Option<Decimal>, ComputeError> {
+    for _ in 0..BISECTION_STEPS {
+        let middle = checked(a.checked_add(b))? / Decimal::TWO;
+        if middle == a || middle == b {
+            break;
+        }
+
+        let Some(fm) = f(middle) else {
+            return Ok(None);
+        };
+        if fm.is_zero() {
+            return Ok(Some(middle));
+        }
+
+        if fm.is_sign_negative() == fa.is_sign_negative() {
+            (a, fa) = (middle, fm);
+        } else {
+            b = middle;
+        }
+    }
+
+    Ok(Some(a))
+}
+
+/// Shortest rounding of the root which is not a worse solution: `3.0000000000000000000000000003` => `3`
+fn polish(root: Decimal, f: &mut impl FnMut(Decimal) -> Option<Decimal>) -> Decimal {
+    let Some(error) = f(root).map(|v| v.abs()) else {
+        return root.normalize();
+    };
+
+    (0..root.scale())
+        .map(|dp| root.round_dp(dp))
+        .find(|x| f(*x).is_some_and(|v| v.abs() <= error))
+        .unwrap_or(root)
+        .normalize()
+}
+
+fn equal(a: Decimal, b: Decimal) -> Result<bool, ComputeError> {
+    let difference = checked(a.checked_sub(b))?;
+    Ok(difference.abs() <= a.abs().max(b.abs()).max(Decimal::ONE) * TOLERANCE)
+}
+
+fn checked(n: Option<Decimal>) -> Result<Decimal, ComputeError> {
+    n.ok_or(ComputeError::ArithmeticOverflow)
+}
+
+/// `left - right`, an expression is equal to zero: `solve(x^2 - 2)`
+fn residual(equation: &Expression) -> Expression {
+    let [ExpressionToken::Equation(left, right)] = &equation.tokens[..] else {
+        return equation.clone();
+    };
+
+    Expression::from_tokens(vec![
+        ExpressionToken::Expression(left.clone()),
+        ExpressionToken::Operator(operators()[&'-'].clone()),
+        ExpressionToken::Expression(right.clone()),
+    ])
+}
+
+/// Terms added or subtracted on the sides of an equation: `2x + 3 = 11` => `2x`, `3`, `11`
+fn terms(equation: &Expression) -> Vec<Expression> {
+    let sides = match &equation.tokens[..] {
+        [ExpressionToken::Equation(left, right)] => vec![left, right],
+        _ => vec![equation],
+    };
+
+    let mut terms = Vec::new();
+    for side in sides {
+        let mut term = Vec::new();
+        for t in side.tokens.iter() {
+            let sign = matches!(t, ExpressionToken::Operator(o) if "+-".contains(o.representation));
+            // unary signs stay in the term: `-x + 3`
+            let after_operand = term
+                .last()
+                .is_some_and(|t| !matches!(t, ExpressionToken::Operator(_)));
+            if sign && after_operand {
+                terms.push(Expression::from_tokens(std::mem::take(&mut term)));
+            } else {
+                term.push(t.clone());
+            }
+        }
+        terms.push(Expression::from_tokens(term));
+    }
+
+    terms.retain(|t| !t.tokens.is_empty());
+    terms
+}
+
+fn first_unit(n: &NumericExpression) -> Option<Unit> {
+    n.values().first().and_then(|(_, u)| *u)
+}
+
+fn is_equation(ex: &Expression) -> bool {
+    matches!(ex.tokens[..], [ExpressionToken::Equation(..)])
+}
+
+fn has_unknowns(ex: &Expression, ctx: &Context) -> bool {
+    let mut names = Vec::new();
+    symbols(ex, &mut names);
+    names.iter().any(|s| is_unknown(s, ctx))
+}
+
+/// Single letters not bound to a value are unknowns without asking the data providers, longer
+/// names are unknowns if no provider has them: `x + AAPL = 460 USD`
+fn is_unknown(symbol: &str, ctx: &Context) -> bool {
+    if ctx.resolve_local(symbol).is_some() {
+        return false;
+    }
+
+    symbol.chars().count() == 1
+        || matches!(ctx.resolve(symbol), Err(ComputeError::UnknownSymbol(_)))
+}
+
+/// Names of the symbols in the order of their first use.
+fn symbols(ex: &Expression, names: &mut Vec<String>) {
+    for t in ex.tokens.iter() {
+        match t {
+            ExpressionToken::Symbol(s) if !names.contains(s) => names.push(s.clone()),
+            ExpressionToken::Expression(e) => symbols(e, names),
+            ExpressionToken::List(l) => l.iter().for_each(|e| symbols(e, names)),
+            ExpressionToken::Matrix(rows) => rows.iter().flatten().for_each(|e| symbols(e, names)),
+            ExpressionToken::Equation(l, r) => {
+                symbols(l, names);
+                symbols(r, names);
+            }
+            _ => {}
+        }
+    }
+}
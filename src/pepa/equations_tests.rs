@@ -0,0 +1,185 @@
+use crate::{Calculator, ComputeError, Locale, Localize, ParserError};
+
+fn compute(expression: &str) -> Result<String, ComputeError> {
+    let mut calculator = Calculator::default();
+    let statement = calculator.compute(expression).unwrap();
+
+    match &statement.result {
+        Some(r) => r.as_ref().map(|n| n.to_string()).map_err(|e| e.clone()),
+        None => panic!("{:?}: no result", expression),
+    }
+}
+
+#[test]
+fn test_linear() {
+    for (expression, expected) in [
+        ("2x + 3 = 11", "x = 4"),
+        ("x = 5", "x = 5"),
+        ("11 = 2x + 3", "x = 4"),
+        ("x / 3 = 1", "x = 3"),
+        ("(x - 1) * 4 = 2", "x = 1.5"),
+        ("solve(2x + 3 = 11)", "x = 4"),
+        ("solve(2 * n - 8, n)", "n = 4"),
+        ("solve(3 * x - 1)", "x = 0.3333333333333333333333333333"),
+        // single letters are unknowns, not units
+        ("t * 2 = 6", "t = 3"),
+        ("d + 1 = 2", "d = 1"),
+        ("s = 2", "s = 2"),
+        ("a + 1 = 3", "a = 2"),
+        ("y - 1 = 0", "y = 1"),
+    ] {
+        assert_eq!(compute(expression).unwrap(), expected, "{}", expression);
+    }
+}
+
+#[test]
+fn test_non_linear() {
+    for (expression, expected) in [
+        ("x^2 = 4", "x = -2, x = 2"),
+        (
+            "x^2 = 2",
+            "x = -1.4142135623730950488016887242, x = 1.4142135623730950488016887242",
+        ),
+        (
+            "solve(x^2 - 2, x)",
+            "x = -1.4142135623730950488016887242, x = 1.4142135623730950488016887242",
+        ),
+        ("x^3 - 6*x^2 + 11x - 6 = 0", "x = 1, x = 2, x = 3"),
+        ("1 / x = 4", "x = 0.25"),
+        ("ln(x) = 1", "x = 2.718281828459045235360287471"),
+        (
+            "x^2 - 0.3 = 0.01",
+            "x = -0.5567764362830021922119471299, x = 0.5567764362830021922119471299",
+        ),
+        ("x^2 = 100000000000000", "x = -10000000, x = 10000000"),
+        ("solve(t^2 - 4, t)", "t = -2, t = 2"),
+        // periodic roots nearest to zero
+        (
+            "sin(x) = 0.5",
+            "x = -5.759586531581287603848179536, x = -3.6651914291880921115397506138, \
+             x = 0.5235987755982988730771072305, x = 2.6179938779914943653855361527, \
+             x = 6.806784082777885350002393997, x = 8.901179185171080842310822919",
+        ),
+    ] {
+        assert_eq!(compute(expression).unwrap(), expected, "{}", expression);
+    }
+
+    assert!(matches!(
+        compute("x^2 + 1 = 0"),
+        Err(ComputeError::NoSolution(_))
+    ));
+    // a pole is not a root
+    assert!(matches!(
+        compute("1 / x = 0"),
+        Err(ComputeError::NoSolution(_))
+    ));
+}
+
+#[test]
+fn test_systems() {
+    for (expression, expected) in [
+        ("solve(x + z = 10, x - z = 2)", "x = 6, z = 4"),
+        (
+            "solve(x + z + w = 6, 2z + 5w = -4, 2x + 5z - w = 27)",
+            "x = 5, z = 3, w = -2",
+        ),
+        ("solve(3p + q = 1, p - 2q = 5)", "p = 1, q = -2"),
+        ("solve(p + q = 2, p - q, p, q)", "p = 1, q = 1"),
+    ] {
+        assert_eq!(compute(expression).unwrap(), expected, "{}", expression);
+    }
+
+    assert!(matches!(
+        compute("solve(p + q = 2, 2p + 2q = 4)"),
+        Err(ComputeError::NoSolution(_))
+    ));
+    assert!(matches!(
+        compute("solve(p * q = 2, p + q = 3)"),
+        Err(ComputeError::InvalidEquation(_))
+    ));
+    assert!(matches!(
+        compute("solve(p + q = 2)"),
+        Err(ComputeError::InvalidEquation(_))
+    ));
+
+    // matrices are not equations
+    assert_eq!(
+        compute("solve([[2, 1], [1, 3]], [5, 10])").unwrap(),
+        "[1, 3]"
+    );
+}
+
+#[test]
+fn test_units() {
+    for (expression, expected) in [
+        ("x * 3 = 150 km", "x = 50km"),
+        ("x + 30 min = 2 h", "x = 1.5h"),
+        ("2x + 1 km = 3000 m", "x = 1000m"),
+        ("10 EUR + x = 25 EUR", "x = 15.00EUR"),
+        ("x * 3 h = 150 km", "x = 50km/h"),
+        ("3 h * x + 10 km = 160 km", "x = 50km/h"),
+        ("2 km * x = 6 km", "x = 3"),
+        ("solve(x + z = 10 km, x - z = 2 km)", "x = 6km, z = 4km"),
+        ("solve(x + z = 10 km, x - z = 2000 m)", "x = 6km, z = 4km"),
+    ] {
+        assert_eq!(compute(expression).unwrap(), expected, "{}", expression);
+    }
+
+    // compound units other than speeds are not supported
+    assert!(matches!(
+        compute("x * 3 h = 150 EUR"),
+        Err(ComputeError::UnsupportedUnitOfUnknown(_))
+    ));
+}
+
+#[test]
+fn test_errors() {
+    assert!(matches!(
+        compute("2 + 3 = 5"),
+        Err(ComputeError::InvalidEquation(_))
+    ));
+    assert!(matches!(
+        compute("1 / 0"),
+        Err(ComputeError::DivisionByZero)
+    ));
+    assert!(matches!(
+        compute("x^2 = 79228162514264337593543950335"),
+        Err(ComputeError::ArithmeticOverflow)
+    ));
+    assert!(matches!(compute("x = x"), Err(ComputeError::NoSolution(_))));
+
+    let mut calculator = Calculator::default();
+    let statement = calculator.compute("a = b = c").unwrap();
+    assert!(matches!(
+        statement.expression,
+        Err(ParserError::InvalidToken(_))
+    ));
+    for expression in ["= 5", "x ="] {
+        let statement = calculator.compute(expression).unwrap();
+        assert!(
+            matches!(statement.expression, Err(ParserError::EmptyExpression)),
+            "{}",
+            expression
+        );
+    }
+}
+
+#[test]
+fn test_format() {
+    let mut calculator = Calculator::default();
+    calculator.set_locale(Locale::Cs);
+    let statement = calculator
+        .compute("solve(x + z = 10,5; x - z = 2)")
+        .unwrap();
+
+    assert_eq!(
+        statement
+            .result
+            .as_ref()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .localize(Locale::Cs),
+        "x = 6,25; z = 4,25"
+    );
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+
+use crate::units::Currency;
+use crate::{string, DataError, Decimal};
+
+/// Offline table of exchange rates, all rates are relative to the base currency:
+/// `rate(c)` is the amount of `c` worth one unit of the base currency.
+///
+/// Text format, one record per line, `#` starts a comment:
+/// ```text
+/// timestamp 2026-10-18T12:00:00Z
+/// base EUR
+/// CZK 24.35
+/// USD 1.07
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExchangeRates {
+    pub timestamp: DateTime<Utc>,
+    pub base: Currency,
+    rates: HashMap<Currency, Decimal>,
+}
+
+impl ExchangeRates {
+    pub fn new(timestamp: DateTime<Utc>, base: Currency) -> ExchangeRates {
+        ExchangeRates {
+            timestamp,
+            base,
+            rates: HashMap::from([(base, Decimal::ONE)]),
+        }
+    }
+
+    pub fn with_rate(mut self, currency: Currency, rate: Decimal) -> ExchangeRates {
+        self.rates.insert(currency, rate);
+        self
+    }
+
+    pub fn rate(&self, currency: Currency) -> Option<Decimal> {
+        self.rates.get(&currency).copied()
+    }
+
+    pub fn convert(&self, v: Decimal, from: Currency, to: Currency) -> Option<Decimal> {
+        let from = self.rate(from)?;
+        let to = self.rate(to)?;
+
+        if from.is_zero() {
+            return None;
+        }
+
+        Some(v / from * to)
+    }
+
+    pub fn from_file(path: &Path) -> Result<ExchangeRates, DataError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| DataError::Io(path.display().to_string(), e.to_string()))?;
+
+        ExchangeRates::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> Result<ExchangeRates, DataError> {
+        let mut timestamp = None;
+        let mut base = None;
+        let mut rates = Vec::new();
+
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || DataError::InvalidExchangeRates(i + 1, string!(line));
+
+            let Some((key, value)) = line.split_once(char::is_whitespace) else {
+                return Err(invalid());
+            };
+            let value = value.trim();
+
+            match key {
+                "timestamp" => {
+                    let t = DateTime::parse_from_rfc3339(value).map_err(|_| invalid())?;
+                    timestamp = Some(t.with_timezone(&Utc));
+                }
+                "base" => base = Some(Currency::from_code(value).ok_or_else(invalid)?),
+                code => {
+                    let currency = Currency::from_code(code).ok_or_else(invalid)?;
+                    let rate = value.parse::<Decimal>().map_err(|_| invalid())?;
+                    rates.push((currency, rate));
+                }
+            }
+        }
+
+        let Some(timestamp) = timestamp else {
+            return Err(DataError::InvalidExchangeRates(
+                0,
+                string!("missing timestamp"),
+            ));
+        };
+
+        let Some(base) = base else {
+            return Err(DataError::InvalidExchangeRates(0, string!("missing base")));
+        };
+
+        let mut table = ExchangeRates::new(timestamp, base);
+        for (currency, rate) in rates {
+            table = table.with_rate(currency, rate);
+        }
+
+        Ok(table)
+    }
+}
@@ -0,0 +1,50 @@
+use chrono::{TimeZone, Utc};
+use rust_decimal_macros::dec;
+
+use crate::exchange_rates::ExchangeRates;
+use crate::units::Currency;
+use crate::DataError;
+
+#[test]
+fn test_parse() {
+    let rates = ExchangeRates::parse(
+        "
+        # ECB reference rates
+        timestamp 2026-10-18T12:00:00Z
+        base EUR
+        CZK 24.5
+        USD   1.25
+        ",
+    )
+    .unwrap();
+
+    assert_eq!(
+        rates.timestamp,
+        Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap()
+    );
+    assert_eq!(rates.base, Currency::Euro);
+    assert_eq!(rates.rate(Currency::Euro), Some(dec!(1)));
+    assert_eq!(rates.rate(Currency::CzechKoruna), Some(dec!(24.5)));
+    assert_eq!(rates.rate(Currency::JapaneseYen), None);
+    assert_eq!(
+        rates.convert(dec!(2.5), Currency::UsDollar, Currency::CzechKoruna),
+        Some(dec!(49))
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    for (content, line) in [
+        ("base EUR\nCZK 24.5", 0),
+        ("timestamp 2026-10-18T12:00:00Z\nCZK 24.5", 0),
+        ("timestamp yesterday", 1),
+        ("timestamp 2026-10-18T12:00:00Z\nbase EUR\nXYZ 1", 3),
+        ("timestamp 2026-10-18T12:00:00Z\nbase EUR\nCZK abc", 3),
+        ("timestamp 2026-10-18T12:00:00Z\nbase EUR\nCZK", 3),
+    ] {
+        match ExchangeRates::parse(content) {
+            Err(DataError::InvalidExchangeRates(l, _)) => assert_eq!(l, line, "{}", content),
+            r => panic!("{:?}: unexpected {:?}", content, r),
+        }
+    }
+}
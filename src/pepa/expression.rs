@@ -3,16 +3,34 @@ use std::fmt::{Display, Formatter};
 use itertools::Itertools;
 
 use crate::{ComputeError, Decimal, string};
-use crate::functions::Function;
+use crate::complex::Complex;
+use crate::context::Context;
+use crate::datetime;
+use crate::datetime::DateTimeValue;
+use crate::functions::Fce;
 use crate::generators::Generator;
+use crate::matrix::Matrix;
 use crate::operators::{CONVERSION_CHARACTER, Operator};
-use crate::units::Unit;
+use crate::rational::Rational;
+use crate::units::{Epoch, Unit, UnitStyle};
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum NumericExpression {
     Number(Decimal),
     NumberWithUnit(Decimal, Unit),
     MultipleNumbersWithUnit(Vec<(Decimal, Option<Unit>)>),
+    DateTime(DateTimeValue),
+    /// `(1, 2, 3)`, operators and functions are applied to every element
+    List(Vec<NumericExpression>),
+    Matrix(Matrix),
+    /// `3+4i`, `(2+2i)km`, a result without an imaginary part is a number
+    Complex(Complex, Option<Unit>),
+    /// `1/3`, a result which is a finite decimal is a number
+    Rational(Rational, Option<Unit>),
+    /// values of the unknowns of an equation: `x = 4`, a name is repeated for several roots
+    Solution(Vec<(String, NumericExpression)>),
 }
 
 impl NumericExpression {
@@ -45,6 +63,12 @@ impl NumericExpression {
             NumericExpression::Number(n) => vec![(*n, None)],
             NumericExpression::NumberWithUnit(n, u) => vec![(*n, Some(*u))],
             NumericExpression::MultipleNumbersWithUnit(v) => v.clone(),
+            NumericExpression::Rational(r, u) => vec![(r.to_decimal(), *u)],
+            NumericExpression::DateTime(_)
+            | NumericExpression::List(_)
+            | NumericExpression::Matrix(_)
+            | NumericExpression::Complex(..)
+            | NumericExpression::Solution(_) => vec![],
         }
     }
 
@@ -56,8 +80,63 @@ impl NumericExpression {
         &self,
         to: &Unit,
         force_unit: bool,
+        ctx: &Context,
     ) -> Result<NumericExpression, ComputeError> {
         match self {
+            NumericExpression::Matrix(_) => Err(ComputeError::OperatorsConversionError(
+                vec![self.clone()],
+                vec![vec![Some(*to)]],
+            )),
+            NumericExpression::Rational(_, None) if !force_unit => Ok(self.clone()),
+            NumericExpression::Rational(_, Some(u)) if u == to => Ok(self.clone()),
+            NumericExpression::Rational(r, u) => {
+                NumericExpression::with_unit(r.to_decimal(), *u).convert_to(to, force_unit, ctx)
+            }
+            // polar form with the phase in the unit: (3 + 4i) in degrees
+            NumericExpression::Complex(c, u) => match (to, u) {
+                (Unit::Angle(a), _) => Ok(NumericExpression::Complex(c.to_polar(*a), *u)),
+                (_, None) if force_unit => Ok(NumericExpression::Complex(*c, Some(*to))),
+                (_, None) => Ok(self.clone()),
+                // both parts are scaled: (1 + i) km in m
+                (_, Some(u)) => {
+                    let factor = NumericExpression::NumberWithUnit(Decimal::ONE, *u)
+                        .convert_to(to, force_unit, ctx)?;
+                    match factor {
+                        NumericExpression::NumberWithUnit(f, unit) if unit == *to => Ok(c
+                            .mul(&Complex::new(f, Decimal::ZERO))?
+                            .with_unit(Some(unit))),
+                        _ => Err(ComputeError::OperatorsConversionError(
+                            vec![self.clone()],
+                            vec![vec![Some(*to)]],
+                        )),
+                    }
+                }
+            },
+            NumericExpression::List(l) => l
+                .iter()
+                .map(|n| n.convert_to(to, force_unit, ctx))
+                .collect::<Result<_, _>>()
+                .map(NumericExpression::List),
+            NumericExpression::Solution(s) => s
+                .iter()
+                .map(|(name, n)| Ok((name.clone(), n.convert_to(to, force_unit, ctx)?)))
+                .collect::<Result<_, _>>()
+                .map(NumericExpression::Solution),
+            NumericExpression::Number(_) | NumericExpression::NumberWithUnit(..)
+                if *to == Unit::Epoch(Epoch::Date) =>
+            {
+                datetime::from_epoch(self)
+                    .map(NumericExpression::DateTime)
+                    .ok_or_else(|| {
+                        ComputeError::OperatorsConversionError(
+                            vec![self.clone()],
+                            vec![vec![Some(*to)]],
+                        )
+                    })
+            }
+            NumericExpression::Number(n) if matches!(to, Unit::TimeZone(_)) => Err(
+                ComputeError::UnitConversionError(*n, string!(""), to.to_string_with_plural(n)),
+            ),
             NumericExpression::Number(n) => Ok(if force_unit {
                 NumericExpression::with_unit(*n, Some(*to))
             } else {
@@ -68,7 +147,7 @@ impl NumericExpression {
                     return Ok(self.clone());
                 }
 
-                let Some(v) = u.conversion(n, to) else {
+                let Some(v) = u.conversion(n, to, ctx) else {
                     return Err(ComputeError::UnitConversionError(
                         *n,
                         u.to_string_with_plural(n),
@@ -84,7 +163,7 @@ impl NumericExpression {
 
                 for (n, u) in values {
                     if let Some(u) = u {
-                        if let Some(c) = u.conversion(n, to) {
+                        if let Some(c) = u.conversion(n, to, ctx) {
                             res.push((c, Some(*to)))
                         };
                     } else {
@@ -103,6 +182,27 @@ impl NumericExpression {
                     Ok(NumericExpression::with_multiple_units(res))
                 }
             }
+            NumericExpression::DateTime(d) => {
+                let converted = match to {
+                    Unit::TimeZone(z) => d.in_zone(z.0, ctx.now()),
+                    Unit::Epoch(Epoch::Date) => Some(*d),
+                    Unit::Epoch(e) => {
+                        if let Some(v) = d.unix_seconds().and_then(|s| e.from_reference_unit(s)) {
+                            return Ok(NumericExpression::with_unit(v.normalize(), Some(*to)));
+                        }
+                        None
+                    }
+                    _ => None,
+                };
+
+                converted.map(NumericExpression::DateTime).ok_or_else(|| {
+                    ComputeError::InvalidDateTimeOperation(format!(
+                        "{}→{}",
+                        d,
+                        to.to_string_with_plural(&Decimal::ONE)
+                    ))
+                })
+            }
         }
     }
 }
@@ -114,26 +214,52 @@ impl Display for NumericExpression {
                 write!(f, "{}", n)
             }
             NumericExpression::NumberWithUnit(n, u) => {
-                write!(f, "{}{}", n, u.to_string_with_plural(n))
+                write!(f, "{}", u.with_value(u.format_value(n), n, UnitStyle::Symbol))
             }
             NumericExpression::MultipleNumbersWithUnit(values) => {
                 let values = values
                     .iter()
                     .map(|v| {
-                        format!(
-                            "{}{}",
-                            v.0,
-                            if let Some(u) = v.1 {
-                                u.to_string_with_plural(&v.0)
-                            } else {
-                                string!("")
-                            }
-                        )
+                        if let Some(u) = v.1 {
+                            u.with_value(u.format_value(&v.0), &v.0, UnitStyle::Symbol)
+                        } else {
+                            string!(v.0)
+                        }
                     })
                     .unique()
                     .join("|");
                 write!(f, "{}", values)
             }
+            NumericExpression::DateTime(d) => write!(f, "{}", d),
+            NumericExpression::List(l) => write!(f, "({})", l.iter().join(", ")),
+            NumericExpression::Matrix(m) => write!(f, "{}", m),
+            NumericExpression::Complex(c, None) => write!(f, "{}", c),
+            NumericExpression::Complex(c, Some(u)) => write!(
+                f,
+                "{}",
+                u.with_value(
+                    format!("({})", c.format(|n| u.format_value(n))),
+                    &c.abs().unwrap_or(Decimal::MAX),
+                    UnitStyle::Symbol
+                )
+            ),
+            NumericExpression::Rational(r, None) => write!(f, "{}", r),
+            NumericExpression::Rational(r, Some(u)) => write!(
+                f,
+                "{}",
+                u.with_value(
+                    r.format(|n| u.format_value(n), " "),
+                    &r.to_decimal(),
+                    UnitStyle::Symbol
+                )
+            ),
+            NumericExpression::Solution(s) => write!(
+                f,
+                "{}",
+                s.iter()
+                    .map(|(name, n)| format!("{} = {}", name, n))
+                    .join(", ")
+            ),
         }
     }
 }
@@ -141,10 +267,15 @@ impl Display for NumericExpression {
 #[derive(Debug, Clone)]
 pub enum ExpressionToken {
     Operator(Operator),
-    Function(Function),
+    Function(Fce),
     Generator(Generator),
     Numeric(NumericExpression),
+    Symbol(String), // resolved during computation: stock ticker, currency pair ...
     List(Vec<Expression>),
+    /// rows of the elements: [[1, 2], [3, 4]]
+    Matrix(Vec<Vec<Expression>>),
+    /// sides of an equation solved for its unknowns: 2x + 3 = 11
+    Equation(Expression, Expression),
     Expression(Expression),
     ConversionChain(Vec<Vec<Unit>>), // vector of unit conversions
 }
@@ -176,6 +307,50 @@ impl Expression {
     pub fn explain(&self) -> String {
         self.to_string()
     }
+
+    /// Names of the symbols resolved during computation: `price * qty` => `price`, `qty`
+    pub fn symbols(&self) -> Vec<String> {
+        let mut symbols = Vec::new();
+
+        for t in self.tokens.iter() {
+            match t {
+                ExpressionToken::Symbol(s) => symbols.push(s.clone()),
+                ExpressionToken::Expression(e) => symbols.extend(e.symbols()),
+                ExpressionToken::List(l) => l.iter().for_each(|e| symbols.extend(e.symbols())),
+                ExpressionToken::Matrix(rows) => rows
+                    .iter()
+                    .flatten()
+                    .for_each(|e| symbols.extend(e.symbols())),
+                ExpressionToken::Equation(l, r) => {
+                    symbols.extend(l.symbols());
+                    symbols.extend(r.symbols());
+                }
+                _ => {}
+            }
+        }
+
+        symbols
+    }
+
+    /// Result may differ on every computation: current time, random numbers, provider data.
+    pub(crate) fn is_volatile(&self) -> bool {
+        self.tokens.iter().any(|t| match t {
+            ExpressionToken::Generator(_) | ExpressionToken::Symbol(_) => true,
+            ExpressionToken::Expression(e) => e.is_volatile(),
+            ExpressionToken::List(l) => l.iter().any(|e| e.is_volatile()),
+            ExpressionToken::Matrix(rows) => rows.iter().flatten().any(|e| e.is_volatile()),
+            ExpressionToken::Equation(l, r) => l.is_volatile() || r.is_volatile(),
+            _ => false,
+        })
+    }
+}
+
+/// Written as the expression text: `5km + 3m`
+#[cfg(feature = "serde")]
+impl serde::Serialize for Expression {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
 }
 
 impl Display for Expression {
@@ -185,8 +360,9 @@ impl Display for Expression {
                 ExpressionToken::Operator(o) => write!(f, "{}", o.representation)?,
                 ExpressionToken::Numeric(n) => write!(f, "{}", n)?,
                 ExpressionToken::Expression(e) => write!(f, "({})", e)?,
-                ExpressionToken::Function(fce) => write!(f, "{}", fce.representation)?,
+                ExpressionToken::Function(fce) => write!(f, "{}", fce.name())?,
                 ExpressionToken::Generator(g) => write!(f, "{}", g.fce_name)?,
+                ExpressionToken::Symbol(s) => write!(f, "{}", s)?,
                 ExpressionToken::List(list) => {
                     write!(f, "(")?;
                     let mut first = true;
@@ -199,6 +375,14 @@ impl Display for Expression {
                     }
                     write!(f, ")")?;
                 }
+                ExpressionToken::Matrix(rows) => write!(
+                    f,
+                    "[{}]",
+                    rows.iter()
+                        .map(|r| format!("[{}]", r.iter().join(",")))
+                        .join(",")
+                )?,
+                ExpressionToken::Equation(l, r) => write!(f, "{}={}", l, r)?,
                 ExpressionToken::ConversionChain(units) => {
                     for unit in units {
                         write!(
@@ -5,12 +5,139 @@ use rust_decimal::MathematicalOps;
 use rust_decimal_macros::dec;
 
 use crate::{Decimal, string};
+use crate::context::Context;
+use crate::datetime;
+use crate::expression::NumericExpression;
+use crate::lists;
+
+type ValuesFce = fn(params: &[NumericExpression], ctx: &Context) -> Option<NumericExpression>;
+
+/// How the units of the parameters pass to the result of a function.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnitRule {
+    /// in the unit of the parameters: `min(5 km, 300 m)` => `300m`
+    Preserve,
+    /// in the unit of the parameters, absolute temperatures are not added: `sum(5 Δ°C, 9 Δ°F)`
+    Sum,
+    /// in a power of the unit of the first parameter, the second one is a number: `pow(5 km, 1)`
+    Exponent,
+    /// in the unit of the only parameter with a unit: `prod(2 km, 3)` => `6km`
+    Product,
+    /// a number of parameters without units, there are no units of areas: `ln(5)`, `sqr(3)`
+    Dimensionless,
+    /// a number of parameters without units, which are radians, or of angles: `sin(90°)` => `1`
+    Angle,
+    /// a number, the units of the parameters are not used: `count(1 km, 2 km)` => `2`
+    Number,
+}
 
 #[derive(Debug, Clone)]
 pub struct Function {
     pub representation: String,
     pub fce: fn(params: Vec<Decimal>) -> Decimal,
     pub params_validation: fn(params: &Vec<Decimal>) -> bool,
+    pub units: UnitRule,
+    /// invoked instead of `fce` when any of the parameters is not a number (date ...)
+    pub fce_values: Option<ValuesFce>,
+}
+
+/// Function of an expression token: of decimals from [`functions`] or of other values,
+/// dispatched by the name to their modules: `weekday`, `det`, `polar`
+#[derive(Debug, Clone)]
+pub enum Fce {
+    Decimal(Function),
+    Values(&'static str),
+}
+
+impl Fce {
+    pub fn name(&self) -> &str {
+        match self {
+            Fce::Decimal(f) => &f.representation,
+            Fce::Values(name) => name,
+        }
+    }
+
+    /// Function of all the values, lists are read as their elements: `sum((1, 2), 3)`.
+    /// Other functions are applied to every element: `sqrt((1, 4, 9))`
+    pub fn is_aggregate(&self) -> bool {
+        matches!(
+            self.name(),
+            "min"
+                | "max"
+                | "sum"
+                | "prod"
+                | "average"
+                | "median"
+                | "count"
+                | "len"
+                | "sort"
+                | "reverse"
+        )
+    }
+
+    /// Function of values which are not only numbers: dates, lists
+    pub fn fce_values(&self) -> Option<ValuesFce> {
+        match self {
+            Fce::Decimal(f) => f.fce_values,
+            Fce::Values(name) => values_fce(name),
+        }
+    }
+}
+
+/// Functions of values other than numbers: dates, lists, functions with a bound variable
+/// (calculus, equations), matrices, complex numbers and fractions.
+const VALUES_FUNCTIONS: [&str; 24] = [
+    "weekday",
+    "week",
+    "workdays",
+    "isworkday",
+    "len",
+    "sort",
+    "reverse",
+    "nth",
+    "integrate",
+    "derive",
+    "limit",
+    "solve",
+    "transpose",
+    "det",
+    "inverse",
+    "identity",
+    "arg",
+    "conj",
+    "re",
+    "im",
+    "polar",
+    "rectangular",
+    "fraction",
+    "mixed",
+];
+
+fn values_fce(name: &str) -> Option<ValuesFce> {
+    match name {
+        "weekday" => Some(datetime::weekday),
+        "week" => Some(datetime::iso_week),
+        "workdays" => Some(datetime::workdays_between),
+        "isworkday" => Some(datetime::is_workday),
+        "len" => Some(lists::len),
+        "sort" => Some(lists::sort),
+        "reverse" => Some(lists::reverse),
+        "nth" => Some(lists::nth),
+        // invoked by their modules
+        _ => None,
+    }
+}
+
+/// Function of the name: `sqrt`, `weekday`
+pub(super) fn function(name: &str) -> Option<Fce> {
+    if let Some(f) = functions().get(name) {
+        return Some(Fce::Decimal(f.clone()));
+    }
+
+    VALUES_FUNCTIONS
+        .iter()
+        .find(|&&f| f == name)
+        .map(|f| Fce::Values(f))
 }
 
 pub(super) fn functions() -> &'static HashMap<String, Function> {
@@ -25,51 +152,80 @@ pub(super) fn functions() -> &'static HashMap<String, Function> {
                 params_validation: |params| {
                     params.len() == 1 && !params.iter().any(|v| *v < dec!(0))
                 },
+                units: UnitRule::Dimensionless,
+                fce_values: None,
             },
             Function {
                 representation: string!("sqr"),
                 fce: |params| params[0].powu(2),
+                params_validation: |params| {
+                    params.len() == 1 && params[0].checked_powu(2).is_some()
+                },
+                units: UnitRule::Dimensionless,
+                fce_values: None,
+            },
+            Function {
+                representation: string!("abs"),
+                fce: |params| params[0].abs(),
                 params_validation: |params| params.len() == 1,
+                units: UnitRule::Preserve,
+                fce_values: None,
             },
             Function {
                 representation: string!("round"),
                 fce: |params| params[0].round(),
                 params_validation: |params| params.len() == 1,
+                units: UnitRule::Preserve,
+                fce_values: None,
             },
             Function {
                 representation: string!("trunc"),
                 fce: |params| params[0].trunc(),
                 params_validation: |params| params.len() == 1,
+                units: UnitRule::Preserve,
+                fce_values: None,
             },
             Function {
                 representation: string!("fract"),
                 fce: |params| params[0].fract(),
                 params_validation: |params| params.len() == 1,
+                units: UnitRule::Preserve,
+                fce_values: None,
             },
             Function {
                 representation: string!("floor"),
                 fce: |params| params[0].floor(),
                 params_validation: |params| params.len() == 1,
+                units: UnitRule::Preserve,
+                fce_values: None,
             },
             Function {
                 representation: string!("ceil"),
                 fce: |params| params[0].ceil(),
                 params_validation: |params| params.len() == 1,
+                units: UnitRule::Preserve,
+                fce_values: None,
             },
             Function {
                 representation: string!("sin"),
                 fce: |params| params[0].sin(),
                 params_validation: |params| params.len() == 1,
+                units: UnitRule::Angle,
+                fce_values: None,
             },
             Function {
                 representation: string!("cos"),
                 fce: |params| params[0].cos(),
                 params_validation: |params| params.len() == 1,
+                units: UnitRule::Angle,
+                fce_values: None,
             },
             Function {
                 representation: string!("tan"),
                 fce: |params| params[0].tan(),
                 params_validation: |params| params.len() == 1,
+                units: UnitRule::Angle,
+                fce_values: None,
             },
             Function {
                 representation: string!("min"),
@@ -80,6 +236,8 @@ pub(super) fn functions() -> &'static HashMap<String, Function> {
                         .unwrap()
                 },
                 params_validation: |params| !params.is_empty(),
+                units: UnitRule::Preserve,
+                fce_values: None,
             },
             Function {
                 representation: string!("max"),
@@ -90,31 +248,58 @@ pub(super) fn functions() -> &'static HashMap<String, Function> {
                         .unwrap()
                 },
                 params_validation: |params| !params.is_empty(),
+                units: UnitRule::Preserve,
+                fce_values: None,
             },
             Function {
                 representation: string!("ln"),
                 fce: |params| params[0].ln(),
-                params_validation: |params| params.len() == 1,
+                params_validation: |params| params.len() == 1 && params[0] > dec!(0),
+                units: UnitRule::Dimensionless,
+                fce_values: None,
             },
             Function {
                 representation: string!("log"),
                 fce: |params| params[0].log10(),
-                params_validation: |params| params.len() == 1,
+                params_validation: |params| params.len() == 1 && params[0] > dec!(0),
+                units: UnitRule::Dimensionless,
+                fce_values: None,
             },
             Function {
                 representation: string!("pow"),
                 fce: |params| params[0].powd(params[1]),
-                params_validation: |params| params.len() == 2,
+                params_validation: |params| {
+                    params.len() == 2 && params[0].checked_powd(params[1]).is_some()
+                },
+                units: UnitRule::Exponent,
+                fce_values: None,
             },
             Function {
                 representation: string!("sum"),
                 fce: |params| params.iter().sum(),
                 params_validation: |params| !params.is_empty(),
+                units: UnitRule::Sum,
+                fce_values: None,
+            },
+            Function {
+                representation: string!("prod"),
+                fce: |params| params.iter().product(),
+                params_validation: |params| {
+                    !params.is_empty()
+                        && params
+                            .iter()
+                            .try_fold(Decimal::ONE, |p, v| p.checked_mul(*v))
+                            .is_some()
+                },
+                units: UnitRule::Product,
+                fce_values: None,
             },
             Function {
                 representation: string!("average"),
                 fce: |params| params.iter().sum::<Decimal>() / Decimal::from(params.len()),
                 params_validation: |params| !params.is_empty(),
+                units: UnitRule::Preserve,
+                fce_values: None,
             },
             Function {
                 representation: string!("median"),
@@ -131,11 +316,22 @@ pub(super) fn functions() -> &'static HashMap<String, Function> {
                     }
                 },
                 params_validation: |params| !params.is_empty(),
+                units: UnitRule::Preserve,
+                fce_values: None,
+            },
+            Function {
+                representation: string!("leapyear"),
+                fce: |params| (datetime::is_leap_year(params[0]) as u8).into(),
+                params_validation: |params| params.len() == 1 && params[0].fract().is_zero(),
+                units: UnitRule::Number,
+                fce_values: Some(datetime::leap_year),
             },
             Function {
                 representation: string!("count"),
                 fce: |params| params.len().into(),
                 params_validation: |params| !params.is_empty(),
+                units: UnitRule::Number,
+                fce_values: None,
             },
         ] {
             functions.insert(function.representation.clone(), function);
@@ -152,6 +348,7 @@ pub(super) fn function_names() -> &'static Vec<String> {
         for (k, _) in functions().iter() {
             names.push(k.clone());
         }
+        names.extend(VALUES_FUNCTIONS.iter().map(|f| f.to_string()));
 
         // sorted reversed so sqrt is before sqr
         names.sort();
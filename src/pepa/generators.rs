@@ -1,13 +1,18 @@
 use std::collections::HashMap;
 use std::sync::OnceLock;
-use std::time::{SystemTime, UNIX_EPOCH};
+
+use chrono::{Days, NaiveDate};
+use chrono_tz::Tz;
 
 use crate::{Decimal, string};
+use crate::context::Context;
+use crate::datetime::DateTimeValue;
+use crate::expression::NumericExpression;
 
 #[derive(Debug, Clone)]
 pub struct Generator {
     pub fce_name: String,
-    pub fce: fn() -> Decimal,
+    pub fce: fn(&Context) -> NumericExpression,
 }
 
 pub fn generators() -> &'static HashMap<String, Generator> {
@@ -18,20 +23,48 @@ pub fn generators() -> &'static HashMap<String, Generator> {
         for generator in [
             Generator {
                 fce_name: string!("random()"),
-                fce: || Decimal::from_f64_retain(fastrand::f64()).unwrap(),
+                fce: |_| {
+                    NumericExpression::Number(Decimal::from_f64_retain(fastrand::f64()).unwrap())
+                },
             },
             Generator {
                 fce_name: string!("timestamp()"),
-                fce: || {
-                    SystemTime::now()
-                        .duration_since(UNIX_EPOCH)
-                        .unwrap()
-                        .as_millis()
-                        .into()
+                fce: |ctx| {
+                    let now = ctx.now().with_timezone(&Tz::UTC);
+                    NumericExpression::DateTime(DateTimeValue::Zoned(now))
+                },
+            },
+            Generator {
+                fce_name: string!("now"),
+                fce: |ctx| {
+                    let now = ctx.now().with_timezone(&ctx.local_zone().0);
+                    NumericExpression::DateTime(DateTimeValue::Zoned(now))
+                },
+            },
+            Generator {
+                fce_name: string!("today"),
+                fce: |ctx| NumericExpression::DateTime(DateTimeValue::Date(today(ctx))),
+            },
+            Generator {
+                fce_name: string!("tomorrow"),
+                fce: |ctx| {
+                    let d = today(ctx) + Days::new(1);
+                    NumericExpression::DateTime(DateTimeValue::Date(d))
+                },
+            },
+            Generator {
+                fce_name: string!("yesterday"),
+                fce: |ctx| {
+                    let d = today(ctx) - Days::new(1);
+                    NumericExpression::DateTime(DateTimeValue::Date(d))
                 },
             },
         ] {
-            let name = generator.fce_name.to_ascii_uppercase().replace("()", "");
+            // keywords (today, now ...) are used as they are
+            let name = match generator.fce_name.strip_suffix("()") {
+                Some(name) => name.to_ascii_uppercase(),
+                None => generator.fce_name.clone(),
+            };
 
             generators.insert(name, generator);
         }
@@ -39,3 +72,8 @@ pub fn generators() -> &'static HashMap<String, Generator> {
         generators
     })
 }
+
+/// Current date in the zone of the calculation.
+fn today(ctx: &Context) -> NaiveDate {
+    ctx.now().with_timezone(&ctx.local_zone().0).date_naive()
+}
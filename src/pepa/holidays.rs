@@ -0,0 +1,157 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use chrono::{Datelike, NaiveDate, TimeDelta, Weekday};
+
+use crate::{string, DataError};
+
+/// Non-working days used by the business day functions, weekends are always non-working.
+///
+/// Text format, one date per line, `#` starts a comment, the rest of the line is a name:
+/// ```text
+/// 12-24 Christmas Eve       # every year
+/// easter+1 Easter Monday    # days after Easter Sunday, every year
+/// 2026-04-06 Easter Monday  # single date
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Holidays {
+    dates: BTreeSet<NaiveDate>,
+    yearly: BTreeSet<(u32, u32)>,
+    /// days from Easter Sunday
+    easter: BTreeSet<i64>,
+}
+
+impl Holidays {
+    pub fn new() -> Holidays {
+        Default::default()
+    }
+
+    pub fn with_date(mut self, date: NaiveDate) -> Holidays {
+        self.dates.insert(date);
+        self
+    }
+
+    /// Holiday repeated every year on the same day.
+    pub fn with_yearly(mut self, month: u32, day: u32) -> Holidays {
+        self.yearly.insert((month, day));
+        self
+    }
+
+    /// Holiday moving with Easter, the offset is in days from Easter Sunday: -2 is Good Friday.
+    pub fn with_easter(mut self, offset: i64) -> Holidays {
+        self.easter.insert(offset);
+        self
+    }
+
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.dates.contains(&date)
+            || self.yearly.contains(&(date.month(), date.day()))
+            || easter_sunday(date.year())
+                .is_some_and(|easter| self.easter.contains(&(date - easter).num_days()))
+    }
+
+    pub fn is_workday(&self, date: NaiveDate) -> bool {
+        !is_weekend(date) && !self.is_holiday(date)
+    }
+
+    /// Number of working days between the dates, both included.
+    pub fn workdays(&self, from: NaiveDate, to: NaiveDate) -> i64 {
+        if from > to {
+            return 0;
+        }
+
+        // whole weeks have 5 weekdays, the rest is counted by days
+        let days = (to - from).num_days() + 1;
+        let rest = from
+            .iter_days()
+            .skip((days / 7 * 7) as usize)
+            .take_while(|d| *d <= to)
+            .filter(|d| !is_weekend(*d))
+            .count();
+
+        days / 7 * 5 + rest as i64 - self.weekday_holidays(from, to)
+    }
+
+    /// Number of holidays falling on weekdays between the dates, both included.
+    fn weekday_holidays(&self, from: NaiveDate, to: NaiveDate) -> i64 {
+        let mut holidays = self.dates.range(from..=to).copied().collect::<BTreeSet<_>>();
+
+        for year in from.year()..=to.year() {
+            let yearly = self
+                .yearly
+                .iter()
+                .filter_map(|(month, day)| NaiveDate::from_ymd_opt(year, *month, *day));
+            let easter = easter_sunday(year).into_iter().flat_map(|easter| {
+                self.easter
+                    .iter()
+                    .filter_map(move |offset| easter.checked_add_signed(TimeDelta::days(*offset)))
+            });
+
+            holidays.extend(yearly.chain(easter).filter(|d| (from..=to).contains(d)));
+        }
+
+        holidays.iter().filter(|d| !is_weekend(**d)).count() as i64
+    }
+
+    pub fn from_file(path: &Path) -> Result<Holidays, DataError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| DataError::Io(path.display().to_string(), e.to_string()))?;
+
+        Holidays::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> Result<Holidays, DataError> {
+        let mut holidays = Holidays::new();
+
+        for (i, line) in content.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let invalid = || DataError::InvalidHolidays(i + 1, string!(line));
+            let date = line.split_whitespace().next().ok_or_else(invalid)?;
+
+            if let Some(offset) = date.strip_prefix("easter") {
+                let offset = match offset {
+                    "" => 0,
+                    offset => offset.parse::<i64>().map_err(|_| invalid())?,
+                };
+                holidays = holidays.with_easter(offset);
+                continue;
+            }
+
+            if let Ok(d) = NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+                holidays = holidays.with_date(d);
+                continue;
+            }
+
+            // validated on a leap year, 02-29 is allowed
+            let d = NaiveDate::parse_from_str(&format!("2000-{}", date), "%Y-%m-%d")
+                .map_err(|_| invalid())?;
+            holidays = holidays.with_yearly(d.month(), d.day());
+        }
+
+        Ok(holidays)
+    }
+}
+
+fn is_weekend(date: NaiveDate) -> bool {
+    matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+/// Easter Sunday of the Gregorian calendar, computed by the anonymous Gregorian algorithm.
+pub fn easter_sunday(year: i32) -> Option<NaiveDate> {
+    let (a, b, c) = (year.rem_euclid(19), year.div_euclid(100), year.rem_euclid(100));
+    let (d, e) = (b.div_euclid(4), b.rem_euclid(4));
+    let f = (b + 8).div_euclid(25);
+    let g = (b - f + 1).div_euclid(3);
+    let h = (19 * a + b - d - g + 15).rem_euclid(30);
+    let (i, k) = (c / 4, c % 4);
+    let l = (32 + 2 * e + 2 * i - h - k).rem_euclid(7);
+    let m = (a + 11 * h + 22 * l) / 451;
+    let n = h + l - 7 * m + 114;
+
+    NaiveDate::from_ymd_opt(year, (n / 31) as u32, (n % 31 + 1) as u32)
+}
@@ -0,0 +1,144 @@
+use chrono::{NaiveDate, TimeDelta};
+
+use crate::holidays::{easter_sunday, Holidays};
+use crate::{Calculator, DataError, DateTimeValue};
+
+fn date(s: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+}
+
+#[test]
+fn test_parse() {
+    let holidays = Holidays::parse(
+        "
+        # Czech public holidays
+        12-24 Christmas Eve
+        02-29
+        2026-04-06 Easter Monday # 2026 only
+        easter-2 Good Friday
+        ",
+    )
+    .unwrap();
+
+    assert_eq!(
+        holidays,
+        Holidays::new()
+            .with_yearly(12, 24)
+            .with_yearly(2, 29)
+            .with_date(date("2026-04-06"))
+            .with_easter(-2)
+    );
+
+    assert!(holidays.is_holiday(date("2030-12-24")));
+    assert!(holidays.is_holiday(date("2026-04-06")));
+    assert!(!holidays.is_holiday(date("2027-04-06")));
+
+    assert!(holidays.is_workday(date("2026-12-23")));
+    assert!(!holidays.is_workday(date("2026-12-24")));
+    assert!(!holidays.is_workday(date("2026-12-26")));
+    assert!(!holidays.is_workday(date("2026-04-03")));
+    assert!(!holidays.is_workday(date("2031-04-11")));
+}
+
+#[test]
+fn test_easter() {
+    for (year, easter) in [
+        (2000, "2000-04-23"),
+        (2026, "2026-04-05"),
+        (2027, "2027-03-28"),
+        (2028, "2028-04-16"),
+        (2038, "2038-04-25"),
+        (2285, "2285-03-22"),
+    ] {
+        assert_eq!(easter_sunday(year), Some(date(easter)), "{}", year);
+    }
+}
+
+#[test]
+fn test_parse_errors() {
+    for (content, line) in [("12-32", 1), ("# comment\n2026-02-30", 2), ("Christmas", 1)] {
+        match Holidays::parse(content) {
+            Err(DataError::InvalidHolidays(l, _)) => assert_eq!(l, line, "{}", content),
+            r => panic!("{:?}: {:?}", content, r),
+        }
+    }
+}
+
+#[test]
+fn test_workdays() {
+    let mut calculator = Calculator::default();
+    calculator.set_holidays(Holidays::parse(include_str!("../../data/holidays-cz.txt")).unwrap());
+
+    for (expression, expected) in [
+        ("workdays(2026-11-01, 2026-12-31)", "41 workdays"),
+        ("workdays(2026-12-31, 2026-11-01)", "-41 workdays"),
+        ("workdays(2026-11-02, 2026-11-02)", "1 workday"),
+        ("isworkday(2026-12-24)", "0"),
+        ("isworkday(2026-12-23)", "1"),
+        ("isworkday(2026-04-06)", "0"),
+        ("isworkday(2031-04-14)", "0"),
+        ("2026-11-02 + 10 workdays", "2026-11-16"),
+        ("2026-12-23 + 1 workday", "2026-12-28"),
+        ("2026-12-28 - 1 workday", "2026-12-23"),
+        ("2026-11-14 + 0 workdays", "2026-11-16"),
+        ("2026-11-02T09:00 + 5 business days", "2026-11-09T09:00:00"),
+    ] {
+        let statement = calculator.compute(expression).unwrap();
+        match &statement.result {
+            Some(Ok(n)) => assert_eq!(n.to_string(), expected, "{}", expression),
+            r => panic!("{:?}: {:?}", expression, r),
+        }
+    }
+}
+
+#[test]
+fn test_add_workdays() {
+    let holidays = Holidays::parse(include_str!("../../data/holidays-cz.txt")).unwrap();
+
+    // compared with moving by single days
+    let by_days = |mut d: NaiveDate, workdays: i64| {
+        let step = TimeDelta::days(if workdays < 0 { -1 } else { 1 });
+        while !holidays.is_workday(d) {
+            d += step;
+        }
+        for _ in 0..workdays.abs() {
+            d += step;
+            while !holidays.is_workday(d) {
+                d += step;
+            }
+        }
+        DateTimeValue::Date(d)
+    };
+
+    for start in ["2026-12-20", "2026-12-23", "2027-03-26", "2026-11-14"] {
+        for workdays in [0, 1, 4, 5, 6, 10, 23, 250, 2600, -1, -5, -6, -250, -2600] {
+            assert_eq!(
+                DateTimeValue::Date(date(start)).add_workdays(workdays, &holidays),
+                Some(by_days(date(start), workdays)),
+                "{} + {}",
+                start,
+                workdays
+            );
+        }
+    }
+
+    let workdays = |from: &str, to: &str| holidays.workdays(date(from), date(to));
+    assert_eq!(workdays("2026-01-01", "2026-12-31"), 250);
+    assert_eq!(workdays("2026-12-24", "2026-12-26"), 0);
+    assert_eq!(workdays("2026-12-27", "2026-12-26"), 0);
+}
+
+#[test]
+fn test_workdays_are_not_days() {
+    let mut calculator = Calculator::default();
+
+    for expression in ["10 workdays in days", "1 workday + 1 day", "2 weeks in workdays"] {
+        let statement = calculator.compute(expression).unwrap();
+        assert!(
+            matches!(statement.result, Some(Err(_))),
+            "{}: {:?}",
+            expression,
+            statement.result
+        );
+    }
+}
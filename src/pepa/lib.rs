@@ -1,31 +1,94 @@
 use rust_decimal::Decimal;
 use thiserror::Error;
 
-pub use crate::units::Unit;
+pub use crate::units::{Currency, Epoch, TimeZone, Unit, UnitFamily, UnitStyle};
 
+pub use self::cache::{CachedProvider, QuoteCache};
+pub use self::complex::Complex;
+pub use self::context::Context;
+pub use self::csv::CsvTable;
+pub use self::datetime::DateTimeValue;
+pub use self::exchange_rates::ExchangeRates;
 pub use self::expression::NumericExpression;
+pub use self::holidays::Holidays;
+pub use self::locale::{Locale, Localize};
+pub use self::matrix::Matrix;
+pub use self::notebook::{Notebook, NotebookLine};
 pub use self::pepa::Calculator;
 pub use self::pepa::Statement;
+pub use self::rational::{FractionForm, Rational};
+pub use self::providers::{CacheInfo, DataProvider, FileProvider, MockProvider, Quote};
 
+mod cache;
+mod calculus;
+mod complex;
 mod compute;
 mod constants;
+mod context;
+mod csv;
+mod datetime;
+mod disambiguation;
+mod equations;
+mod exchange_rates;
 mod expression;
 mod functions;
 mod generators;
+mod holidays;
+mod lists;
+mod locale;
+mod matrix;
+mod notebook;
 mod operators;
 mod parser;
 mod pepa;
+mod providers;
+mod rational;
 mod units;
 mod utils;
 
+#[cfg(test)]
+mod cache_tests;
+#[cfg(test)]
+mod calculus_tests;
+#[cfg(test)]
+mod complex_tests;
 #[cfg(test)]
 mod compute_tests;
 #[cfg(test)]
+mod csv_tests;
+#[cfg(test)]
+mod datetime_tests;
+#[cfg(test)]
+mod disambiguation_tests;
+#[cfg(test)]
+mod equations_tests;
+#[cfg(test)]
+mod exchange_rates_tests;
+#[cfg(test)]
+mod holidays_tests;
+#[cfg(test)]
+mod lists_tests;
+#[cfg(test)]
+mod locale_tests;
+#[cfg(test)]
+mod matrix_tests;
+#[cfg(test)]
+mod notebook_tests;
+#[cfg(test)]
 mod parser_tests;
 #[cfg(test)]
+mod providers_tests;
+#[cfg(test)]
+mod rational_tests;
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests;
+#[cfg(test)]
 mod utils_tests;
 
-#[derive(Error, Debug, Clone)]
+#[derive(Error, Debug, Clone, strum_macros::IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum ParserError {
     #[error("Unbalanced parentheses: '{0}'")]
     UnbalancedParentheses(String),
@@ -39,9 +102,18 @@ pub enum ParserError {
     InvalidToken(String),
     #[error("Expression ends with an operator: '{0}")]
     ExpressionEndsWithOperator(String),
+    #[error("Invalid matrix: '{0}'")]
+    InvalidMatrix(String),
+    #[error("Assignments are not supported: '{0}'")]
+    UnsupportedAssignment(String),
+    #[error("Brackets after a value are not supported: '{0}'")]
+    UnsupportedIndexing(String),
 }
 
-#[derive(Error, Debug, Clone)]
+#[derive(Error, Debug, Clone, strum_macros::IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum ComputeError {
     #[error("Unsupported unary operator")]
     UnsupportedUnaryOperator,
@@ -55,4 +127,73 @@ pub enum ComputeError {
     UnitConversionError(Decimal, String, String),
     #[error("Unable to convert '{0:?}' to unit {1:?}")]
     OperatorsConversionError(Vec<NumericExpression>, Vec<Vec<Option<Unit>>>),
+    #[error("Unknown symbol: '{0}'")]
+    UnknownSymbol(String),
+    #[error("Data provider '{0}' failed: {1}")]
+    DataProviderError(String, DataError),
+    #[error("Invalid date operation: '{0}'")]
+    InvalidDateTimeOperation(String),
+    #[error("Invalid operation with absolute temperatures: '{0}'")]
+    InvalidTemperatureOperation(String),
+    #[error("Invalid value of column '{0}': '{1}'")]
+    InvalidColumnValue(String, String),
+    #[error("Lists of different lengths: {0} and {1}")]
+    ListLengthMismatch(usize, usize),
+    #[error("Matrix dimensions do not match for '{0}': {1} and {2}")]
+    MatrixDimensionMismatch(String, String, String),
+    #[error("Matrix {0} is not square")]
+    NotSquareMatrix(String),
+    #[error("Matrix is singular")]
+    SingularMatrix,
+    #[error("Invalid matrix value: '{0}'")]
+    InvalidMatrixValue(String),
+    #[error("Division by zero")]
+    DivisionByZero,
+    #[error("Arithmetic overflow")]
+    ArithmeticOverflow,
+    #[error("Invalid equation: '{0}'")]
+    InvalidEquation(String),
+    #[error("No unique solution of '{0}'")]
+    NoSolution(String),
+    #[error("No limit of '{0}'")]
+    NoLimit(String),
+    #[error("Integral of '{0}' does not converge")]
+    NoIntegral(String),
+    #[error("Unknown with a compound unit is not supported: '{0}'")]
+    UnsupportedUnitOfUnknown(String),
+    #[error("Invalid complex number value: '{0}'")]
+    InvalidComplexValue(String),
+    #[error("Invalid unit for function '{0}': {1}")]
+    InvalidUnitForFunction(String, String),
+}
+
+#[derive(Error, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum DataError {
+    #[error("Unable to read '{0}': {1}")]
+    Io(String, String),
+    #[error("Invalid exchange rates at line {0}: '{1}'")]
+    InvalidExchangeRates(usize, String),
+    #[error("Invalid quotes at line {0}: '{1}'")]
+    InvalidQuotes(usize, String),
+    #[error("Invalid cache entry at line {0}: '{1}'")]
+    InvalidCache(usize, String),
+    #[error("Invalid holidays at line {0}: '{1}'")]
+    InvalidHolidays(usize, String),
+    #[error("Invalid CSV at line {0}: '{1}'")]
+    InvalidCsv(usize, String),
+}
+
+/// Result was computed, but the request allowed more readings.
+#[derive(Error, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Warning {
+    #[error("Ambiguous unit '{symbol}' read as {}, also {}", .chosen.family(), disambiguation::families(.alternatives))]
+    AmbiguousUnit {
+        symbol: String,
+        chosen: Unit,
+        alternatives: Vec<Unit>,
+    },
 }
@@ -0,0 +1,131 @@
+use std::cmp::Ordering;
+
+use itertools::Itertools;
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::context::Context;
+use crate::expression::NumericExpression;
+use crate::units::Unit;
+use crate::{ComputeError, Decimal};
+
+/// Elements of the lists among the other parameters: `(1, (2, 3)), 4` => `1, 2, 3, 4`
+pub(super) fn flatten(params: Vec<NumericExpression>) -> Vec<NumericExpression> {
+    params
+        .into_iter()
+        .flat_map(|n| match n {
+            NumericExpression::List(l) => flatten(l),
+            n => vec![n],
+        })
+        .collect()
+}
+
+/// Applies the operation to the elements of the lists, other parameters are used with every element:
+/// `(1, 2) * 10` => `(10, 20)`, `(1, 2) + (10, 20)` => `(11, 22)`
+pub(super) fn broadcast(
+    params: &[NumericExpression],
+    f: impl Fn(Vec<NumericExpression>) -> Result<NumericExpression, ComputeError>,
+) -> Result<NumericExpression, ComputeError> {
+    let lengths = params
+        .iter()
+        .filter_map(|n| match n {
+            NumericExpression::List(l) => Some(l.len()),
+            _ => None,
+        })
+        .unique()
+        .collect::<Vec<_>>();
+
+    if let [a, b, ..] = lengths[..] {
+        return Err(ComputeError::ListLengthMismatch(a, b));
+    }
+
+    (0..lengths.first().copied().unwrap_or_default())
+        .map(|i| {
+            f(params
+                .iter()
+                .map(|n| match n {
+                    NumericExpression::List(l) => l[i].clone(),
+                    n => n.clone(),
+                })
+                .collect())
+        })
+        .collect::<Result<_, _>>()
+        .map(NumericExpression::List)
+}
+
+/// Joins the lists converted to the units of an ambiguous conversion: `(1, 2) m`
+pub(super) fn merge(lists: Vec<Vec<NumericExpression>>) -> NumericExpression {
+    let elements = (0..lists[0].len())
+        .map(|i| {
+            let values = lists
+                .iter()
+                .flat_map(|l| l[i].values())
+                .unique()
+                .collect::<Vec<_>>();
+
+            if values.is_empty() {
+                lists[0][i].clone()
+            } else {
+                NumericExpression::with_multiple_units(values)
+            }
+        })
+        .collect();
+
+    NumericExpression::List(elements)
+}
+
+pub(super) fn len(params: &[NumericExpression], _: &Context) -> Option<NumericExpression> {
+    Some(NumericExpression::Number(params.len().into()))
+}
+
+pub(super) fn reverse(params: &[NumericExpression], _: &Context) -> Option<NumericExpression> {
+    Some(NumericExpression::List(
+        params.iter().rev().cloned().collect(),
+    ))
+}
+
+/// Values with units are compared in the first unit: `sort(1 km, 20 m)` => `(20m, 1km)`
+pub(super) fn sort(params: &[NumericExpression], ctx: &Context) -> Option<NumericExpression> {
+    let unit = params.iter().flat_map(|n| n.units()).flatten().next();
+    let keys = params
+        .iter()
+        .map(|n| sort_key(n, unit, ctx))
+        .collect::<Option<Vec<_>>>()?;
+
+    let sorted = params
+        .iter()
+        .zip(keys)
+        .sorted_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+        .map(|(n, _)| n.clone())
+        .collect();
+
+    Some(NumericExpression::List(sorted))
+}
+
+/// Element of a list, counted from 1, negative from the end: `nth((10, 20, 30), -1)` => `30`
+pub(super) fn nth(params: &[NumericExpression], _: &Context) -> Option<NumericExpression> {
+    let [NumericExpression::List(l), NumericExpression::Number(n)] = params else {
+        return None;
+    };
+
+    let n = n.to_i64().filter(|_| n.fract().is_zero())?;
+    let index = match n {
+        1.. => n - 1,
+        ..=-1 => l.len() as i64 + n,
+        0 => return None,
+    };
+
+    usize::try_from(index).ok().and_then(|i| l.get(i)).cloned()
+}
+
+fn sort_key(n: &NumericExpression, unit: Option<Unit>, ctx: &Context) -> Option<Decimal> {
+    match n {
+        NumericExpression::Number(v) => Some(*v),
+        NumericExpression::DateTime(d) => d.unix_seconds(),
+        NumericExpression::List(_) => None,
+        // first of the ambiguous units which can be compared: 20 m
+        n => n.values().iter().find_map(|(v, u)| match (u, unit) {
+            (Some(u), Some(to)) => u.conversion(v, &to, ctx),
+            _ => Some(*v),
+        }),
+    }
+}
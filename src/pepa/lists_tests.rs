@@ -0,0 +1,116 @@
+use crate::{Calculator, ComputeError, Locale, Localize, ParserError};
+
+fn compute(expression: &str) -> Result<String, ComputeError> {
+    let mut calculator = Calculator::default();
+    let statement = calculator.compute(expression).unwrap();
+
+    match &statement.result {
+        Some(r) => r.as_ref().map(|n| n.to_string()).map_err(|e| e.clone()),
+        None => panic!("{:?}: no result", expression),
+    }
+}
+
+#[test]
+fn test_values() {
+    for (expression, expected) in [
+        ("(15, 16, 17)", "(15, 16, 17)"),
+        ("(15, 16, 17) * 2", "(30, 32, 34)"),
+        ("10 - (1, 2)", "(9, 8)"),
+        ("(1, 2) + (10, 20)", "(11, 22)"),
+        ("-(1, 2)", "(-1, -2)"),
+        ("(1, (2, 3)) * 2", "(2, (4, 6))"),
+        ("(1, 2) km", "(1km, 2km)"),
+        ("(1, 2) km + 500 m", "(1500m, 2500m)"),
+        ("(1, 2) km in m", "(1000m, 2000m)"),
+        ("(1 h, 30 min) in min", "(60.0m, 30m)"),
+        (
+            "(2026-12-24, 2026-12-31) + 1 day",
+            "(2026-12-25, 2027-01-01)",
+        ),
+    ] {
+        assert_eq!(compute(expression).unwrap(), expected, "{}", expression);
+    }
+
+    assert!(matches!(
+        compute("(1, 2) + (1, 2, 3)"),
+        Err(ComputeError::ListLengthMismatch(2, 3))
+    ));
+}
+
+#[test]
+fn test_functions() {
+    for (expression, expected) in [
+        ("sqr((1, 2, 3))", "(1, 4, 9)"),
+        ("floor((1.5, 2.7))", "(1, 2)"),
+        ("pow((1, 2, 3), 2)", "(1, 4, 9)"),
+        ("round((1.4, 2.6)) * 10", "(10, 30)"),
+        ("weekday((2026-12-24, 2026-12-26))", "(4, 6)"),
+        ("len((15, 16, 17))", "3"),
+        ("len((1, 2), 3)", "3"),
+        ("sort((3, 1, 2))", "(1, 2, 3)"),
+        ("sort(3, 1, 2)", "(1, 2, 3)"),
+        ("sort((1 km, 20 m, 3 mm))", "(3mm, 20m, 1km)"),
+        ("reverse((1, 2, 3))", "(3, 2, 1)"),
+        ("nth((10, 20, 30), 1)", "10"),
+        ("nth((10, 20, 30), -1)", "30"),
+        ("nth(sort((3, 1, 2)), 2) * 10", "20"),
+    ] {
+        assert_eq!(compute(expression).unwrap(), expected, "{}", expression);
+    }
+
+    for (expression, message) in [
+        ("nth((10, 20, 30), 4)", "(10, 20, 30), 4"),
+        ("nth((10, 20, 30), 0)", "(10, 20, 30), 0"),
+        ("weekday((2026-12-24, 5))", "(2026-12-24, 5)"),
+    ] {
+        match compute(expression) {
+            Err(ComputeError::InvalidParametersForFunction(_, p)) => assert_eq!(p, message),
+            r => panic!("{}: {:?}", expression, r),
+        }
+    }
+}
+
+#[test]
+fn test_assignment() {
+    let mut calculator = Calculator::default();
+    let statement = calculator.compute("(15, 16, 17) => x").unwrap();
+
+    assert!(matches!(
+        &statement.expression,
+        Err(ParserError::UnsupportedAssignment(_))
+    ));
+}
+
+#[test]
+fn test_aggregates() {
+    for (expression, expected) in [
+        ("sum((1, 2, 3))", "6"),
+        ("sum((1, 2), 3)", "6"),
+        ("max((1, 5), (3, 4))", "5"),
+        ("average((1, 2) * 3)", "4.50"),
+        ("median((10, 2, 38))", "10"),
+        ("count((1, 2, 3))", "3"),
+        ("sum((1, 2) km)", "3km"),
+        ("sum((1 km, 500 m))", "1500m"),
+    ] {
+        assert_eq!(compute(expression).unwrap(), expected, "{}", expression);
+    }
+}
+
+#[test]
+fn test_format() {
+    let mut calculator = Calculator::default();
+    calculator.set_locale(Locale::Cs);
+    let statement = calculator.compute("(1,5; 2) * 2").unwrap();
+
+    assert_eq!(
+        statement
+            .result
+            .as_ref()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .localize(Locale::Cs),
+        "(3,0; 4)"
+    );
+}
@@ -0,0 +1,359 @@
+use itertools::Itertools;
+use rust_decimal::Decimal;
+
+use crate::expression::NumericExpression;
+use crate::units::{UnitFamily, UnitStyle};
+use crate::{ComputeError, DataError, ParserError, Warning};
+
+/// Number format and language of the messages.
+///
+/// Unit names of all languages are always accepted: `5 metrů`, `2 hodiny`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum Locale {
+    #[default]
+    En,
+    Cs,
+}
+
+impl Locale {
+    /// Language code, optionally with a region and an encoding: `cs`, `cs_CZ.UTF-8`
+    pub fn from_code(code: &str) -> Option<Locale> {
+        let language = code
+            .split(['_', '-', '.'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        match language.as_str() {
+            "en" | "c" | "posix" => Some(Locale::En),
+            "cs" => Some(Locale::Cs),
+            _ => None,
+        }
+    }
+
+    pub fn decimal_separator(self) -> char {
+        match self {
+            Locale::En => '.',
+            Locale::Cs => ',',
+        }
+    }
+
+    /// Separator of thousands in the output, input accepts numbers without it.
+    pub fn group_separator(self) -> Option<char> {
+        match self {
+            Locale::En => None,
+            Locale::Cs => Some(' '),
+        }
+    }
+
+    /// Separator of list elements in the output: `(1.5, 2)`, `(1,5; 2)`
+    pub fn list_separator(self) -> &'static str {
+        match self.decimal_separator() {
+            ',' => "; ",
+            _ => ", ",
+        }
+    }
+
+    /// Rewrites the input to the format of the parser, a decimal comma must be followed by a digit,
+    /// lists are separated by `;` or `, `: `max(3,5; 2)` => `max(3.5, 2)`. A number has a single
+    /// decimal comma, more commas separate a list: `max(1,2,3)`
+    pub fn normalize_input(self, line: &str) -> String {
+        if self.decimal_separator() == '.' {
+            return line.to_owned();
+        }
+
+        let chars = line.chars().collect::<Vec<_>>();
+        let is_digit = |i: Option<usize>| {
+            i.and_then(|i| chars.get(i))
+                .is_some_and(char::is_ascii_digit)
+        };
+        let is_comma = |i: usize| chars[i] == ',' && is_digit(i.checked_sub(1)) && is_digit(Some(i + 1));
+        let in_number = |i: &usize| chars[*i].is_ascii_digit() || is_comma(*i);
+        let commas_of_number = |i: usize| {
+            let start = (0..i).rev().take_while(in_number).last().unwrap_or(i);
+            let end = (i..chars.len()).take_while(in_number).last().unwrap_or(i);
+            (start..=end).filter(|i| is_comma(*i)).count()
+        };
+
+        chars
+            .iter()
+            .enumerate()
+            .map(|(i, c)| match c {
+                ',' if is_comma(i) && commas_of_number(i) == 1 => '.',
+                ';' => ',',
+                c => *c,
+            })
+            .collect()
+    }
+
+    /// Separator of the whole part of a mixed number, a space would be read as a digit group:
+    /// `1 3/4`, `12 345 a 1/2`
+    pub fn mixed_separator(self) -> &'static str {
+        match self {
+            Locale::En => " ",
+            Locale::Cs => " a ",
+        }
+    }
+
+    /// Label of an error in the output: `Error: Division by zero`
+    pub fn error_label(self) -> &'static str {
+        match self {
+            Locale::En => "Error",
+            Locale::Cs => "Chyba",
+        }
+    }
+
+    /// Formats a number written by the parser format: `-1234567.5` => `-1 234 567,5`, other values are kept.
+    pub fn format_number(self, s: &str) -> String {
+        let (sign, digits) = match s.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", s),
+        };
+        let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+
+        if integer.is_empty()
+            || !integer.chars().all(|c| c.is_ascii_digit())
+            || !fraction.chars().all(|c| c.is_ascii_digit())
+        {
+            return s.to_owned();
+        }
+
+        let mut res = String::from(sign);
+        for (i, c) in integer.chars().enumerate() {
+            let rest = integer.len() - i;
+            // 4 digit numbers are not grouped: 1234, 12 345
+            if let Some(g) = self.group_separator() {
+                if i > 0 && rest % 3 == 0 && integer.len() > 4 {
+                    res.push(g);
+                }
+            }
+            res.push(c);
+        }
+
+        if digits.contains('.') {
+            res.push(self.decimal_separator());
+            res.push_str(fraction);
+        }
+
+        res
+    }
+
+    /// Result in the number format of the locale: `5km`, `5 kilometres` with [`UnitStyle::Name`]
+    pub fn format(self, n: &NumericExpression, style: UnitStyle) -> String {
+        match n {
+            NumericExpression::Number(n) => self.format_number(&n.to_string()),
+            NumericExpression::NumberWithUnit(n, u) => {
+                let value = self.format_number(&u.format_value(n));
+                u.with_value(value, n, style)
+            }
+            NumericExpression::Rational(r, None) => r.format(
+                |n| self.format_number(&n.to_string()),
+                self.mixed_separator(),
+            ),
+            NumericExpression::Rational(r, Some(u)) => {
+                let value = r.format(
+                    |n| self.format_number(&u.format_value(n)),
+                    self.mixed_separator(),
+                );
+                u.with_value(value, &r.to_decimal(), style)
+            }
+            NumericExpression::MultipleNumbersWithUnit(values) => values
+                .iter()
+                .map(|(n, u)| self.format(&NumericExpression::with_unit(*n, *u), style))
+                .unique()
+                .join("|"),
+            NumericExpression::DateTime(d) => d.to_string(),
+            NumericExpression::Matrix(m) => m.format(
+                |n| self.format_number(&n.to_string()),
+                self.list_separator(),
+            ),
+            NumericExpression::Complex(c, None) => {
+                c.format(|n| self.format_number(&n.to_string()))
+            }
+            NumericExpression::Complex(c, Some(u)) => {
+                let value = c.format(|n| self.format_number(&u.format_value(n)));
+                let magnitude = c.abs().unwrap_or(Decimal::MAX);
+                u.with_value(format!("({})", value), &magnitude, style)
+            }
+            NumericExpression::Solution(s) => s
+                .iter()
+                .map(|(name, n)| format!("{} = {}", name, self.format(n, style)))
+                .join(self.list_separator()),
+            NumericExpression::List(l) => format!(
+                "({})",
+                l.iter()
+                    .map(|n| self.format(n, style))
+                    .join(self.list_separator())
+            ),
+        }
+    }
+
+    pub fn family_name(self, family: UnitFamily) -> String {
+        match self {
+            Locale::En => family.to_string(),
+            Locale::Cs => String::from(match family {
+                UnitFamily::Temperature => "teplota",
+                UnitFamily::Time => "čas",
+                UnitFamily::Length => "délka",
+                UnitFamily::Mass => "hmotnost",
+                UnitFamily::Angle => "úhel",
+                UnitFamily::Volume => "objem",
+                UnitFamily::Speed => "rychlost",
+                UnitFamily::Currency => "měna",
+                UnitFamily::TimeZone => "časové pásmo",
+                UnitFamily::Epoch => "epocha",
+            }),
+        }
+    }
+}
+
+/// Text in the number format and the language of the locale.
+pub trait Localize {
+    fn localize(&self, locale: Locale) -> String;
+}
+
+impl Localize for NumericExpression {
+    fn localize(&self, locale: Locale) -> String {
+        locale.format(self, UnitStyle::Symbol)
+    }
+}
+
+impl Localize for ParserError {
+    fn localize(&self, locale: Locale) -> String {
+        if locale == Locale::En {
+            return self.to_string();
+        }
+
+        match self {
+            ParserError::UnbalancedParentheses(s) => format!("Nevyvážené závorky: '{}'", s),
+            ParserError::EmptyExpression => String::from("Prázdný výraz"),
+            ParserError::EmptyToken => String::from("Prázdná část výrazu"),
+            ParserError::InvalidFunctionName(s) => format!("Neplatný název funkce: '{}'", s),
+            ParserError::InvalidToken(s) => format!("Neplatná část výrazu: '{}'", s),
+            ParserError::ExpressionEndsWithOperator(s) => {
+                format!("Výraz končí operátorem: '{}'", s)
+            }
+            ParserError::InvalidMatrix(s) => format!("Neplatná matice: '{}'", s),
+            ParserError::UnsupportedAssignment(s) => {
+                format!("Přiřazení nejsou podporována: '{}'", s)
+            }
+            ParserError::UnsupportedIndexing(s) => {
+                format!("Hranaté závorky za hodnotou nejsou podporovány: '{}'", s)
+            }
+        }
+    }
+}
+
+impl Localize for ComputeError {
+    fn localize(&self, locale: Locale) -> String {
+        if locale == Locale::En {
+            return self.to_string();
+        }
+
+        match self {
+            ComputeError::UnsupportedUnaryOperator => String::from("Nepodporovaný unární operátor"),
+            ComputeError::InvalidParametersForFunction(f, p) => {
+                format!("Neplatné parametry funkce '{}': {}", f, p)
+            }
+            ComputeError::InvalidNumberOfParametersForOperator(o, n) => {
+                format!("Neplatný počet parametrů operátoru {}: {}", o, n)
+            }
+            ComputeError::InvalidExpression(s) => format!("Neplatný výraz: {}", s),
+            ComputeError::UnitConversionError(n, from, to) => format!(
+                "Nelze převést {} {} na {}",
+                locale.format_number(&n.to_string()),
+                from,
+                to
+            ),
+            ComputeError::OperatorsConversionError(n, u) => {
+                format!("Nelze převést '{:?}' na jednotku {:?}", n, u)
+            }
+            ComputeError::UnknownSymbol(s) => format!("Neznámý symbol: '{}'", s),
+            ComputeError::DataProviderError(p, e) => {
+                format!("Zdroj dat '{}' selhal: {}", p, e.localize(locale))
+            }
+            ComputeError::InvalidDateTimeOperation(s) => {
+                format!("Neplatná operace s datem: '{}'", s)
+            }
+            ComputeError::InvalidTemperatureOperation(s) => {
+                format!("Neplatná operace s absolutními teplotami: '{}'", s)
+            }
+            ComputeError::InvalidColumnValue(c, s) => {
+                format!("Neplatná hodnota sloupce '{}': '{}'", c, s)
+            }
+            ComputeError::ListLengthMismatch(a, b) => {
+                format!("Seznamy různých délek: {} a {}", a, b)
+            }
+            ComputeError::MatrixDimensionMismatch(o, a, b) => {
+                format!("Rozměry matic pro '{}' nesouhlasí: {} a {}", o, a, b)
+            }
+            ComputeError::NotSquareMatrix(d) => format!("Matice {} není čtvercová", d),
+            ComputeError::SingularMatrix => String::from("Matice je singulární"),
+            ComputeError::InvalidMatrixValue(s) => format!("Neplatná hodnota matice: '{}'", s),
+            ComputeError::DivisionByZero => String::from("Dělení nulou"),
+            ComputeError::ArithmeticOverflow => String::from("Aritmetické přetečení"),
+            ComputeError::InvalidEquation(s) => format!("Neplatná rovnice: '{}'", s),
+            ComputeError::NoSolution(s) => format!("Rovnice '{}' nemá jediné řešení", s),
+            ComputeError::NoLimit(s) => format!("Výraz '{}' nemá limitu", s),
+            ComputeError::NoIntegral(s) => format!("Integrál výrazu '{}' nekonverguje", s),
+            ComputeError::UnsupportedUnitOfUnknown(s) => {
+                format!("Neznámá se složenou jednotkou není podporována: '{}'", s)
+            }
+            ComputeError::InvalidComplexValue(s) => {
+                format!("Neplatná hodnota komplexního čísla: '{}'", s)
+            }
+            ComputeError::InvalidUnitForFunction(f, n) => {
+                format!("Neplatná jednotka pro funkci '{}': {}", f, n)
+            }
+        }
+    }
+}
+
+impl Localize for DataError {
+    fn localize(&self, locale: Locale) -> String {
+        if locale == Locale::En {
+            return self.to_string();
+        }
+
+        match self {
+            DataError::Io(path, e) => format!("Nelze načíst '{}': {}", path, e),
+            DataError::InvalidExchangeRates(line, s) => {
+                format!("Neplatné směnné kurzy na řádku {}: '{}'", line, s)
+            }
+            DataError::InvalidQuotes(line, s) => {
+                format!("Neplatné kotace na řádku {}: '{}'", line, s)
+            }
+            DataError::InvalidCache(line, s) => {
+                format!("Neplatný záznam cache na řádku {}: '{}'", line, s)
+            }
+            DataError::InvalidHolidays(line, s) => {
+                format!("Neplatné svátky na řádku {}: '{}'", line, s)
+            }
+            DataError::InvalidCsv(line, s) => format!("Neplatné CSV na řádku {}: '{}'", line, s),
+        }
+    }
+}
+
+impl Localize for Warning {
+    fn localize(&self, locale: Locale) -> String {
+        if locale == Locale::En {
+            return self.to_string();
+        }
+
+        match self {
+            Warning::AmbiguousUnit {
+                symbol,
+                chosen,
+                alternatives,
+            } => format!(
+                "Nejednoznačná jednotka '{}' čtena jako {}, také {}",
+                symbol,
+                locale.family_name(chosen.family()),
+                alternatives
+                    .iter()
+                    .map(|u| locale.family_name(u.family()))
+                    .join(", ")
+            ),
+        }
+    }
+}
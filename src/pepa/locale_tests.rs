@@ -0,0 +1,95 @@
+use crate::{Calculator, Locale, Localize};
+
+fn compute(locale: Locale, expression: &str) -> String {
+    let mut calculator = Calculator::default();
+    calculator.set_locale(locale);
+    let statement = calculator.compute(expression).unwrap();
+
+    match &statement.result {
+        Some(Ok(n)) => n.localize(locale),
+        Some(Err(e)) => e.localize(locale),
+        None => panic!("{:?}: no result", expression),
+    }
+}
+
+#[test]
+fn test_codes() {
+    assert_eq!(Locale::from_code("cs"), Some(Locale::Cs));
+    assert_eq!(Locale::from_code("cs_CZ.UTF-8"), Some(Locale::Cs));
+    assert_eq!(Locale::from_code("en-US"), Some(Locale::En));
+    assert_eq!(Locale::from_code("C"), Some(Locale::En));
+    assert_eq!(Locale::from_code("xx"), None);
+}
+
+#[test]
+fn test_normalize_input() {
+    assert_eq!(Locale::Cs.normalize_input("3,5 + 1,25"), "3.5 + 1.25");
+    assert_eq!(Locale::Cs.normalize_input("max(3,5; 2)"), "max(3.5, 2)");
+    assert_eq!(Locale::Cs.normalize_input("max(3,5, 2)"), "max(3.5, 2)");
+    assert_eq!(Locale::En.normalize_input("max(3,5)"), "max(3,5)");
+    // more commas in a number separate a list
+    assert_eq!(Locale::Cs.normalize_input("max(1,2,3)"), "max(1,2,3)");
+    assert_eq!(Locale::Cs.normalize_input("max(1,2,3; 4,5)"), "max(1,2,3, 4.5)");
+}
+
+#[test]
+fn test_format_number() {
+    assert_eq!(Locale::Cs.format_number("3.5"), "3,5");
+    assert_eq!(Locale::Cs.format_number("1234"), "1234");
+    assert_eq!(Locale::Cs.format_number("-1234567.25"), "-1 234 567,25");
+    assert_eq!(Locale::Cs.format_number("25:01:01"), "25:01:01");
+    assert_eq!(Locale::En.format_number("1234567.25"), "1234567.25");
+}
+
+#[test]
+fn test_numbers() {
+    for (expression, expected) in [
+        ("3,5 + 1", "4,5"),
+        ("3,5 km + 500 m", "4000,0m"),
+        ("max(3,5; 2)", "3,5"),
+        ("max(3,5, 2)", "3,5"),
+        ("max(1,2,3)", "3"),
+        ("pow(2,5; 2)", "6,25"),
+        ("1 000 000 * 3", "3 000 000"),
+        ("2026-12-24 + 1 day", "2026-12-25"),
+    ] {
+        assert_eq!(compute(Locale::Cs, expression), expected, "{}", expression);
+    }
+
+    assert_eq!(compute(Locale::En, "max(3,5)"), "5");
+    assert_eq!(compute(Locale::En, "3.5 km + 500 m"), "4000.0m");
+}
+
+#[test]
+fn test_unit_names() {
+    for (expression, expected) in [
+        ("5 metrů in cm", "500cm"),
+        ("2 kilometry in m", "2000m"),
+        ("2 hodiny in minuty", "120,0m"),
+        ("1 den in hodin", "24,0h"),
+        ("100 stupňů Celsia in °F", "212,0000°F"),
+        ("3 týdny in dny", "21d"),
+        ("2 tuny in kg", "2000kg"),
+    ] {
+        assert_eq!(compute(Locale::Cs, expression), expected, "{}", expression);
+    }
+}
+
+#[test]
+fn test_messages() {
+    assert_eq!(
+        compute(Locale::Cs, "unknown * 2"),
+        "Neznámý symbol: 'unknown'"
+    );
+    assert_eq!(
+        compute(Locale::En, "unknown * 2"),
+        "Unknown symbol: 'unknown'"
+    );
+
+    let mut calculator = Calculator::default();
+    let statement = calculator.compute("5m + 10m").unwrap();
+    assert_eq!(
+        statement.warnings[0].localize(Locale::Cs),
+        "Nejednoznačná jednotka 'm' čtena jako délka, také čas"
+    );
+}
@@ -0,0 +1,372 @@
+use std::fmt::{Display, Formatter};
+
+use itertools::Itertools;
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::expression::NumericExpression;
+use crate::{ComputeError, Decimal};
+
+/// Largest size of `identity(n)`, larger matrices are not reasonable to compute or show.
+const MAX_IDENTITY_SIZE: usize = 100;
+
+/// Matrix of numbers: `[[1, 2], [3, 4]]`, a literal without inner rows is a column vector: `[5, 6]`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Matrix {
+    rows: usize,
+    cols: usize,
+    values: Vec<Decimal>,
+}
+
+impl Matrix {
+    /// `None` for an empty matrix or rows of different lengths.
+    pub fn from_rows(rows: Vec<Vec<Decimal>>) -> Option<Matrix> {
+        let cols = rows.first()?.len();
+        if cols == 0 || rows.iter().any(|r| r.len() != cols) {
+            return None;
+        }
+
+        Some(Matrix {
+            rows: rows.len(),
+            cols,
+            values: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn identity(n: usize) -> Matrix {
+        Matrix::from_fn(
+            n,
+            n,
+            |i, j| if i == j { Decimal::ONE } else { Decimal::ZERO },
+        )
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Decimal {
+        self.values[row * self.cols + col]
+    }
+
+    pub fn transpose(&self) -> Matrix {
+        Matrix::from_fn(self.cols, self.rows, |i, j| self.get(j, i))
+    }
+
+    pub fn add(&self, other: &Matrix) -> Result<Matrix, ComputeError> {
+        self.check_same_dimensions("+", other)?;
+        Matrix::try_from_fn(self.rows, self.cols, |i, j| {
+            self.get(i, j).checked_add(other.get(i, j))
+        })
+    }
+
+    pub fn sub(&self, other: &Matrix) -> Result<Matrix, ComputeError> {
+        self.check_same_dimensions("-", other)?;
+        Matrix::try_from_fn(self.rows, self.cols, |i, j| {
+            self.get(i, j).checked_sub(other.get(i, j))
+        })
+    }
+
+    /// Matrix product, columns of the left matrix must match the rows of the right one.
+    pub fn mul(&self, other: &Matrix) -> Result<Matrix, ComputeError> {
+        if self.cols != other.rows {
+            return Err(self.dimension_mismatch("*", &other.dimensions()));
+        }
+
+        Matrix::try_from_fn(self.rows, other.cols, |i, j| {
+            (0..self.cols).try_fold(Decimal::ZERO, |sum, k| {
+                sum.checked_add(self.get(i, k).checked_mul(other.get(k, j))?)
+            })
+        })
+    }
+
+    pub fn scale(&self, k: Decimal) -> Result<Matrix, ComputeError> {
+        Matrix::try_from_fn(self.rows, self.cols, |i, j| self.get(i, j).checked_mul(k))
+    }
+
+    /// Integer power by repeated squaring, a negative one is a power of the inverse.
+    pub fn pow(&self, n: i64) -> Result<Matrix, ComputeError> {
+        self.check_square()?;
+
+        let mut base = if n < 0 { self.inverse()? } else { self.clone() };
+        let mut res = Matrix::identity(self.rows);
+        let mut n = n.unsigned_abs();
+
+        while n > 0 {
+            if n % 2 == 1 {
+                res = res.mul(&base)?;
+            }
+            n /= 2;
+            if n > 0 {
+                base = base.mul(&base)?;
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// Bareiss elimination, all divisions are exact: integer matrices have an exact determinant.
+    pub fn det(&self) -> Result<Decimal, ComputeError> {
+        self.check_square()?;
+
+        let n = self.rows;
+        let mut m = self.to_rows();
+        let mut sign = Decimal::ONE;
+        let mut previous = Decimal::ONE;
+
+        for k in 0..n - 1 {
+            if m[k][k].is_zero() {
+                let Some(p) = (k + 1..n).find(|i| !m[*i][k].is_zero()) else {
+                    return Ok(Decimal::ZERO);
+                };
+                m.swap(k, p);
+                sign = -sign;
+            }
+
+            for i in k + 1..n {
+                for j in k + 1..n {
+                    m[i][j] = eliminate(m[i][j], m[k][k], m[i][k], m[k][j], previous)?;
+                }
+            }
+            previous = m[k][k];
+        }
+
+        Ok((sign * m[n - 1][n - 1]).normalize())
+    }
+
+    pub fn inverse(&self) -> Result<Matrix, ComputeError> {
+        self.check_square()?;
+        self.solve(&Matrix::identity(self.rows))
+    }
+
+    /// `x` of `A x = b`, `b` may have several columns.
+    ///
+    /// Fraction-free Gauss-Jordan elimination: the intermediate values are minors of the matrices,
+    /// so the only inexact division is the final one by the determinant.
+    pub fn solve(&self, b: &Matrix) -> Result<Matrix, ComputeError> {
+        self.check_square()?;
+        if b.rows != self.rows {
+            return Err(self.dimension_mismatch("solve", &b.dimensions()));
+        }
+
+        let n = self.rows;
+        let mut a = self.to_rows();
+        let mut x = b.to_rows();
+        let mut previous = Decimal::ONE;
+
+        for k in 0..n {
+            let p = (k..n)
+                .find(|i| !a[*i][k].is_zero())
+                .ok_or(ComputeError::SingularMatrix)?;
+            a.swap(k, p);
+            x.swap(k, p);
+
+            let (pivot_a, pivot_x) = (a[k].clone(), x[k].clone());
+            for i in (0..n).filter(|i| *i != k) {
+                let factor = a[i][k];
+
+                for (v, p) in a[i].iter_mut().zip(&pivot_a) {
+                    *v = eliminate(*v, pivot_a[k], factor, *p, previous)?;
+                }
+                for (v, p) in x[i].iter_mut().zip(&pivot_x) {
+                    *v = eliminate(*v, pivot_a[k], factor, *p, previous)?;
+                }
+            }
+            previous = pivot_a[k];
+        }
+
+        // the diagonal is the determinant (up to the sign of the swaps)
+        Matrix::try_from_fn(n, b.cols, |i, j| x[i][j].checked_div(a[i][i]))
+    }
+
+    /// Rows of formatted numbers, a column vector is written as its literal: `[5, 6]`
+    pub(crate) fn format(&self, number: impl Fn(&Decimal) -> String, separator: &str) -> String {
+        if self.cols == 1 {
+            return format!("[{}]", self.values.iter().map(number).join(separator));
+        }
+
+        let rows = self
+            .values
+            .chunks(self.cols)
+            .map(|r| format!("[{}]", r.iter().map(&number).join(separator)))
+            .join(separator);
+
+        format!("[{}]", rows)
+    }
+
+    /// Values are normalized: `[[1.0, 2.50]]` => `[[1, 2.5]]`
+    fn from_fn(rows: usize, cols: usize, f: impl Fn(usize, usize) -> Decimal) -> Matrix {
+        Matrix {
+            rows,
+            cols,
+            values: (0..rows)
+                .flat_map(|i| (0..cols).map(move |j| (i, j)))
+                .map(|(i, j)| f(i, j).normalize())
+                .collect(),
+        }
+    }
+
+    fn try_from_fn(
+        rows: usize,
+        cols: usize,
+        f: impl Fn(usize, usize) -> Option<Decimal>,
+    ) -> Result<Matrix, ComputeError> {
+        let values = (0..rows)
+            .flat_map(|i| (0..cols).map(move |j| (i, j)))
+            .map(|(i, j)| f(i, j).map(|v| v.normalize()))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(ComputeError::ArithmeticOverflow)?;
+
+        Ok(Matrix { rows, cols, values })
+    }
+
+    fn to_rows(&self) -> Vec<Vec<Decimal>> {
+        self.values.chunks(self.cols).map(|r| r.to_vec()).collect()
+    }
+
+    fn dimensions(&self) -> String {
+        format!("{}×{}", self.rows, self.cols)
+    }
+
+    fn check_square(&self) -> Result<(), ComputeError> {
+        if self.rows != self.cols {
+            return Err(ComputeError::NotSquareMatrix(self.dimensions()));
+        }
+
+        Ok(())
+    }
+
+    fn check_same_dimensions(&self, operation: &str, other: &Matrix) -> Result<(), ComputeError> {
+        if self.rows != other.rows || self.cols != other.cols {
+            return Err(self.dimension_mismatch(operation, &other.dimensions()));
+        }
+
+        Ok(())
+    }
+
+    fn dimension_mismatch(&self, operation: &str, other: &str) -> ComputeError {
+        ComputeError::MatrixDimensionMismatch(
+            operation.to_owned(),
+            self.dimensions(),
+            other.to_owned(),
+        )
+    }
+}
+
+/// Step of the fraction-free elimination: `(v * pivot - factor * p) / previous`
+fn eliminate(
+    v: Decimal,
+    pivot: Decimal,
+    factor: Decimal,
+    p: Decimal,
+    previous: Decimal,
+) -> Result<Decimal, ComputeError> {
+    v.checked_mul(pivot)
+        .and_then(|v| v.checked_sub(factor.checked_mul(p)?))
+        .and_then(|v| v.checked_div(previous))
+        .ok_or(ComputeError::ArithmeticOverflow)
+}
+
+impl Display for Matrix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.format(|n| n.to_string(), ", "))
+    }
+}
+
+/// Matrix of computed elements, which must be numbers without units.
+pub(super) fn from_elements(
+    rows: Vec<Vec<NumericExpression>>,
+) -> Result<NumericExpression, ComputeError> {
+    let rows = rows
+        .into_iter()
+        .map(|r| r.into_iter().map(number).collect::<Result<Vec<_>, _>>())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Matrix::from_rows(rows)
+        .map(NumericExpression::Matrix)
+        .ok_or(ComputeError::InvalidExpression(String::from("[]")))
+}
+
+/// Operation with a matrix, numbers scale it: `[[1, 2], [3, 4]] * 2`
+pub(super) fn invoke_operator(
+    operator: char,
+    params: &[NumericExpression],
+) -> Result<NumericExpression, ComputeError> {
+    use NumericExpression::{Matrix as M, Number as N};
+
+    let res = match (operator, params) {
+        ('+', [M(a)]) => a.clone(),
+        ('-', [M(a)]) => a.scale(-Decimal::ONE)?,
+        ('+', [M(a), M(b)]) => a.add(b)?,
+        ('-', [M(a), M(b)]) => a.sub(b)?,
+        ('*', [M(a), M(b)]) => a.mul(b)?,
+        ('*', [M(a), N(k)]) | ('*', [N(k), M(a)]) => a.scale(*k)?,
+        ('/', [M(_), N(k)]) if k.is_zero() => return Err(ComputeError::DivisionByZero),
+        ('/', [M(a), N(k)]) => a.scale(Decimal::ONE / *k)?,
+        ('^', [M(a), N(n)]) => {
+            a.pow(integer(n).ok_or(ComputeError::InvalidMatrixValue(n.to_string()))?)?
+        }
+        (_, [a, b]) => {
+            if let Some(n) = [a, b].into_iter().find(|n| !matches!(n, M(_) | N(_))) {
+                return Err(ComputeError::InvalidMatrixValue(n.to_string()));
+            }
+
+            return Err(ComputeError::MatrixDimensionMismatch(
+                operator.to_string(),
+                dimensions(a),
+                dimensions(b),
+            ));
+        }
+        _ => return Err(ComputeError::UnsupportedUnaryOperator),
+    };
+
+    Ok(M(res))
+}
+
+/// Linear algebra functions, `None` if the name or the parameters are not of a matrix function.
+pub(super) fn invoke_function(
+    name: &str,
+    params: &[NumericExpression],
+) -> Option<Result<NumericExpression, ComputeError>> {
+    use NumericExpression::{Matrix as M, Number as N};
+
+    let res = match (name, params) {
+        ("transpose", [M(a)]) => Ok(M(a.transpose())),
+        ("det", [M(a)]) => a.det().map(N),
+        ("inverse", [M(a)]) => a.inverse().map(M),
+        ("identity", [N(n)]) => match integer(n)
+            .and_then(|n| usize::try_from(n).ok())
+            .filter(|n| *n > 0)?
+        {
+            size if size > MAX_IDENTITY_SIZE => Err(ComputeError::InvalidMatrixValue(n.to_string())),
+            size => Ok(M(Matrix::identity(size))),
+        },
+        ("solve", [M(a), M(b)]) => a.solve(b).map(M),
+        _ => return None,
+    };
+
+    Some(res)
+}
+
+fn number(n: NumericExpression) -> Result<Decimal, ComputeError> {
+    match n {
+        NumericExpression::Number(v) => Ok(v),
+        NumericExpression::Rational(r, None) => Ok(r.to_decimal()),
+        n => Err(ComputeError::InvalidMatrixValue(n.to_string())),
+    }
+}
+
+fn integer(n: &Decimal) -> Option<i64> {
+    n.to_i64().filter(|_| n.fract().is_zero())
+}
+
+fn dimensions(n: &NumericExpression) -> String {
+    match n {
+        NumericExpression::Matrix(m) => m.dimensions(),
+        _ => String::from("1×1"),
+    }
+}
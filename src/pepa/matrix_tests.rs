@@ -0,0 +1,182 @@
+use crate::{Calculator, ComputeError, Decimal, Locale, Localize, Matrix, ParserError};
+
+fn compute(expression: &str) -> Result<String, ComputeError> {
+    let mut calculator = Calculator::default();
+    let statement = calculator.compute(expression).unwrap();
+
+    match &statement.result {
+        Some(r) => r.as_ref().map(|n| n.to_string()).map_err(|e| e.clone()),
+        None => panic!("{:?}: no result", expression),
+    }
+}
+
+#[test]
+fn test_values() {
+    for (expression, expected) in [
+        ("[[1, 2], [3, 4]]", "[[1, 2], [3, 4]]"),
+        ("[5, 6]", "[5, 6]"),
+        ("[[1 + 1, 2 * 3]]", "[[2, 6]]"),
+        (
+            "[[1, 2], [3, 4]] + [[10, 20], [30, 40]]",
+            "[[11, 22], [33, 44]]",
+        ),
+        ("[[1, 2], [3, 4]] - [[1, 1], [1, 1]]", "[[0, 1], [2, 3]]"),
+        (
+            "[[1, 2], [3, 4]] * [[5, 6], [7, 8]]",
+            "[[19, 22], [43, 50]]",
+        ),
+        ("[[1, 2], [3, 4]] * [5, 6]", "[17, 39]"),
+        ("[[1, 2, 3]] * [1, 2, 3]", "[14]"),
+        ("2 * [[1, 2], [3, 4]]", "[[2, 4], [6, 8]]"),
+        ("[[1, 2], [3, 4]] / 2", "[[0.5, 1], [1.5, 2]]"),
+        ("-[[1, 2], [3, 4]]", "[[-1, -2], [-3, -4]]"),
+        ("[[1, 1], [0, 1]] ^ 3", "[[1, 3], [0, 1]]"),
+        (
+            "[[1, 1], [1, 0]] ^ 90",
+            "[[4660046610375530309, 2880067194370816120], [2880067194370816120, 1779979416004714189]]",
+        ),
+    ] {
+        assert_eq!(compute(expression).unwrap(), expected, "{}", expression);
+    }
+}
+
+#[test]
+fn test_functions() {
+    for (expression, expected) in [
+        (
+            "transpose([[1, 2, 3], [4, 5, 6]])",
+            "[[1, 4], [2, 5], [3, 6]]",
+        ),
+        ("transpose([1, 2])", "[[1, 2]]"),
+        ("det([[1, 2], [3, 4]])", "-2"),
+        ("det([[2, 0, 1], [1, 3, 2], [1, 1, 2]])", "6"),
+        ("det([[0, 1], [1, 0]])", "-1"),
+        ("det([[1, 2], [2, 4]])", "0"),
+        ("inverse([[1, 2], [3, 4]])", "[[-2, 1], [1.5, -0.5]]"),
+        (
+            "inverse([[2, 0], [0, 4]]) * [[2, 0], [0, 4]]",
+            "[[1, 0], [0, 1]]",
+        ),
+        ("identity(2)", "[[1, 0], [0, 1]]"),
+        ("solve([[2, 1], [1, 3]], [5, 10])", "[1, 3]"),
+        (
+            "solve([[0, 1], [1, 0]], [[1, 2], [3, 4]])",
+            "[[3, 4], [1, 2]]",
+        ),
+        ("det([[1, 2], [3, 4]]) * 10", "-20"),
+    ] {
+        assert_eq!(compute(expression).unwrap(), expected, "{}", expression);
+    }
+}
+
+#[test]
+fn test_errors() {
+    assert!(matches!(
+        compute("[[1, 2], [3, 4]] + [1, 2]"),
+        Err(ComputeError::MatrixDimensionMismatch(o, a, b)) if o == "+" && a == "2×2" && b == "2×1"
+    ));
+    assert!(matches!(
+        compute("[[1, 2, 3]] * [[1, 2]]"),
+        Err(ComputeError::MatrixDimensionMismatch(o, a, b)) if o == "*" && a == "1×3" && b == "1×2"
+    ));
+    assert!(matches!(
+        compute("[[1, 2]] + 1"),
+        Err(ComputeError::MatrixDimensionMismatch(..))
+    ));
+    assert!(matches!(
+        compute("det([[1, 2, 3], [4, 5, 6]])"),
+        Err(ComputeError::NotSquareMatrix(d)) if d == "2×3"
+    ));
+    assert!(matches!(
+        compute("inverse([[1, 2], [2, 4]])"),
+        Err(ComputeError::SingularMatrix)
+    ));
+    assert!(matches!(
+        compute("solve([[1, 2], [3, 4]], [1, 2, 3])"),
+        Err(ComputeError::MatrixDimensionMismatch(..))
+    ));
+    assert!(matches!(
+        compute("[[1 km, 2]]"),
+        Err(ComputeError::InvalidMatrixValue(_))
+    ));
+    assert!(matches!(
+        compute("[[1, 2], [3, 4]] / 0"),
+        Err(ComputeError::DivisionByZero)
+    ));
+    assert!(compute("identity(0)").is_err());
+    assert!(matches!(
+        compute("identity(100000)"),
+        Err(ComputeError::InvalidMatrixValue(_))
+    ));
+
+    let big = "[[79228162514264337593543950335, 1], [1, 1]]";
+    for expression in [
+        format!("{} * 2", big),
+        format!("-{} - {}", big, big),
+        format!("{} + {}", big, big),
+        format!("{} * {}", big, big),
+        format!("det({})", big.replace(", 1]", ", 2]")),
+        String::from("[[2, 0], [0, 2]] ^ 1000"),
+    ] {
+        assert!(
+            matches!(compute(&expression), Err(ComputeError::ArithmeticOverflow)),
+            "{}",
+            expression
+        );
+    }
+
+    let mut calculator = Calculator::default();
+    for expression in ["[[1, 2], [3]]", "[[1, 2], 3]"] {
+        let statement = calculator.compute(expression).unwrap();
+        assert!(
+            matches!(statement.expression, Err(ParserError::InvalidMatrix(_))),
+            "{}",
+            expression
+        );
+    }
+    // indexes are not supported, nth() picks an element of a list
+    for expression in ["(1, 2, 3)[1]", "[[1, 2], [3, 4]][1]", "2[1, 2]"] {
+        let statement = calculator.compute(expression).unwrap();
+        assert!(
+            matches!(
+                statement.expression,
+                Err(ParserError::UnsupportedIndexing(_))
+            ),
+            "{}",
+            expression
+        );
+    }
+}
+
+#[test]
+fn test_exact_arithmetic() {
+    let m = Matrix::from_rows(vec![
+        vec![Decimal::from(3), Decimal::from(1)],
+        vec![Decimal::from(1), Decimal::from(3)],
+    ])
+    .unwrap();
+
+    let inverse = m.inverse().unwrap();
+    assert_eq!(m.mul(&inverse).unwrap(), Matrix::identity(2));
+    assert_eq!(m.det().unwrap(), Decimal::from(8));
+    assert_eq!(m.transpose().transpose(), m);
+    assert!(Matrix::from_rows(vec![vec![Decimal::ONE], vec![]]).is_none());
+}
+
+#[test]
+fn test_format() {
+    let mut calculator = Calculator::default();
+    calculator.set_locale(Locale::Cs);
+    let statement = calculator.compute("[[1,5; 2]; [3; 4]] * 2").unwrap();
+
+    assert_eq!(
+        statement
+            .result
+            .as_ref()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .localize(Locale::Cs),
+        "[[3; 4]; [6; 8]]"
+    );
+}
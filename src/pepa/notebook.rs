@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+
+use crate::locale::{Locale, Localize};
+use crate::pepa::{Calculator, Statement};
+use crate::units::UnitStyle;
+
+/// Annotations are separated from the expression by at least two spaces: `5 km + 3 m  = 5.003km`
+const ANNOTATIONS: [&str; 2] = ["  = ", "  ! "];
+
+/// Line of a notebook without its annotation and the statement computed from it.
+#[derive(Debug, Clone)]
+pub struct NotebookLine {
+    pub text: String,
+    /// `None` for empty lines and `#`, `//` comments
+    pub statement: Option<Statement>,
+}
+
+/// Text file of calculations, evaluated line by line by one [`Calculator`].
+///
+/// Annotated notebook has the results of the lines in an aligned column:
+/// ```text
+/// # trip
+/// 350 km in m  = 350000m
+/// 2 h * 3      = 6h
+/// foo          ! Unknown symbol: 'foo'
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Notebook {
+    lines: Vec<NotebookLine>,
+    trailing_newline: bool,
+}
+
+impl Notebook {
+    /// Evaluates every line, annotations of a previous run are ignored.
+    pub fn evaluate(calculator: &Calculator, text: &str) -> Notebook {
+        let mut notebook = Notebook::default();
+        notebook.update(calculator, text);
+        notebook
+    }
+
+    /// Evaluates the new text of the notebook, unchanged lines keep their statements
+    /// unless they are volatile: `now`, `random()`, provider data.
+    pub fn update(&mut self, calculator: &Calculator, text: &str) {
+        let mut previous = HashMap::new();
+        for line in self.lines.drain(..) {
+            if let Some(s) = line.statement.filter(|s| !is_volatile(s)) {
+                previous.entry(line.text).or_insert(s);
+            }
+        }
+
+        let compute = |text: &str| {
+            previous
+                .get(text)
+                .cloned()
+                .or_else(|| calculator.compute_line(text))
+        };
+
+        self.lines = text
+            .lines()
+            .map(|line| {
+                if is_comment(line) {
+                    return NotebookLine {
+                        text: line.to_owned(),
+                        statement: None,
+                    };
+                }
+
+                let line = line.trim_end();
+                let whole = compute(line);
+                // a line computed as written has no annotation: `x  = 4` is an equation
+                match strip_annotation(line) {
+                    Some(text) if !whole.as_ref().is_some_and(is_computed) => NotebookLine {
+                        text: text.to_owned(),
+                        statement: compute(text),
+                    },
+                    _ => NotebookLine {
+                        text: line.to_owned(),
+                        statement: whole,
+                    },
+                }
+            })
+            .collect();
+        self.trailing_newline = text.ends_with('\n');
+    }
+
+    /// Exchange rates or holidays of the calculator changed, the lines using them are computed
+    /// again by the next update.
+    pub fn data_changed(&mut self) {
+        for line in self.lines.iter_mut() {
+            if line.statement.as_ref().is_some_and(uses_data) {
+                line.statement = None;
+            }
+        }
+    }
+
+    pub fn lines(&self) -> &[NotebookLine] {
+        &self.lines
+    }
+
+    /// Text of the notebook with `= result` and `! error` annotations, rerun replaces them.
+    pub fn annotate(&self, locale: Locale, style: UnitStyle) -> String {
+        let width = self
+            .lines
+            .iter()
+            .filter(|l| l.statement.is_some())
+            .map(|l| l.text.chars().count())
+            .max()
+            .unwrap_or_default();
+
+        let mut res = self
+            .lines
+            .iter()
+            .map(|l| match &l.statement {
+                Some(s) => format!("{:width$}{}", l.text, annotation(s, locale, style)),
+                None => l.text.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        if self.trailing_newline {
+            res.push('\n');
+        }
+
+        res
+    }
+}
+
+fn annotation(statement: &Statement, locale: Locale, style: UnitStyle) -> String {
+    match (&statement.expression, &statement.result) {
+        (Err(e), _) => format!("{}{}", ANNOTATIONS[1], e.localize(locale)),
+        (_, Some(Err(e))) => format!("{}{}", ANNOTATIONS[1], e.localize(locale)),
+        (_, Some(Ok(n))) => format!("{}{}", ANNOTATIONS[0], locale.format(n, style)),
+        (_, None) => String::new(),
+    }
+}
+
+fn is_volatile(statement: &Statement) -> bool {
+    !statement.quotes.is_empty() || statement.expression.as_ref().is_ok_and(|e| e.is_volatile())
+}
+
+fn uses_data(statement: &Statement) -> bool {
+    statement.exchange_rates_timestamp.is_some() || statement.holidays_used
+}
+
+fn is_computed(statement: &Statement) -> bool {
+    statement.expression.is_ok() && matches!(statement.result, Some(Ok(_)))
+}
+
+fn is_comment(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with('#') || line.starts_with("//")
+}
+
+/// Text before the last annotation, `None` for a line without one.
+fn strip_annotation(line: &str) -> Option<&str> {
+    ANNOTATIONS
+        .iter()
+        .filter_map(|a| line.rfind(a))
+        .max()
+        .map(|i| line[..i].trim_end())
+}
@@ -0,0 +1,143 @@
+use crate::{Calculator, ExchangeRates, Holidays, Locale, Notebook, UnitStyle};
+
+fn annotate(text: &str) -> String {
+    Notebook::evaluate(&Calculator::default(), text).annotate(Locale::En, UnitStyle::Symbol)
+}
+
+#[test]
+fn test_annotate() {
+    let text = "# trip\n350 km in miles\n\n// fuel\n2 * 3.5\nfoo * 2\n";
+
+    assert_eq!(
+        annotate(text),
+        "# trip\n\
+         350 km in miles  = 217.47991728306688936610196453mi\n\
+         \n\
+         // fuel\n\
+         2 * 3.5          = 7.0\n\
+         foo * 2          ! Unknown symbol: 'foo'\n"
+    );
+}
+
+#[test]
+fn test_idempotent() {
+    let text = "1 km in m\n10 * 2\n(5 + 3";
+    let annotated = annotate(text);
+
+    assert_eq!(annotate(&annotated), annotated);
+    assert!(!annotated.ends_with('\n'));
+
+    // edited line and a new line move the column
+    let edited = annotated.replace("10 * 2", "10 * 20 + 1000 * 3") + "\n5 + 5";
+    assert_eq!(
+        annotate(&edited),
+        "1 km in m           = 1000m\n\
+         10 * 20 + 1000 * 3  = 3200\n\
+         (5 + 3              ! Unbalanced parentheses: '5+3'\n\
+         5 + 5               = 10"
+    );
+}
+
+#[test]
+fn test_annotation_like_text() {
+    // comments and lines computed as written are not annotations
+    let text = "# totals  = keep this\n  // note  ! keep\nx  = 4\n2 * 3";
+    let annotated = annotate(text);
+
+    assert_eq!(
+        annotated,
+        "# totals  = keep this\n\
+         \x20 // note  ! keep\n\
+         x  = 4  = x = 4\n\
+         2 * 3   = 6"
+    );
+    assert_eq!(annotate(&annotated), annotated);
+}
+
+#[test]
+fn test_lines() {
+    let calculator = Calculator::default();
+    let notebook = Notebook::evaluate(&calculator, "# comment\n5 * 2  = 11\n");
+
+    assert_eq!(notebook.lines().len(), 2);
+    assert!(notebook.lines()[0].statement.is_none());
+    assert_eq!(notebook.lines()[1].text, "5 * 2");
+    assert_eq!(
+        notebook.lines()[1]
+            .statement
+            .as_ref()
+            .and_then(|s| s.result.clone())
+            .unwrap()
+            .unwrap()
+            .to_string(),
+        "10"
+    );
+    // statements of a notebook are not kept by the calculator, reloads do not add up
+    assert!(calculator.statements().is_empty());
+}
+
+#[test]
+fn test_update() {
+    let mut calculator = Calculator::default();
+    let mut notebook =
+        Notebook::evaluate(&calculator, "1 km in m\nisworkday(2026-12-24)\n10 * 2\n");
+    calculator.set_holidays(Holidays::new().with_yearly(12, 24));
+
+    // unchanged lines keep their statements, holidays are used again after `data_changed`
+    let text = notebook.annotate(Locale::En, UnitStyle::Symbol);
+    notebook.update(
+        &calculator,
+        &(text.replace("10 * 2", "10 * 3") + "5 + 5\n1 km in m\n"),
+    );
+    assert_eq!(
+        notebook
+            .lines()
+            .iter()
+            .map(|l| l.text.as_str())
+            .collect::<Vec<_>>(),
+        [
+            "1 km in m",
+            "isworkday(2026-12-24)",
+            "10 * 3",
+            "5 + 5",
+            "1 km in m"
+        ]
+    );
+    assert!(notebook
+        .annotate(Locale::En, UnitStyle::Symbol)
+        .starts_with(
+        "1 km in m              = 1000m\nisworkday(2026-12-24)  = 1\n10 * 3                 = 30\n"
+    ));
+
+    notebook.data_changed();
+    let text = notebook.annotate(Locale::En, UnitStyle::Symbol);
+    notebook.update(&calculator, &text);
+    assert!(notebook
+        .annotate(Locale::En, UnitStyle::Symbol)
+        .contains("isworkday(2026-12-24)  = 0\n"));
+    assert!(calculator.statements().is_empty());
+}
+
+#[test]
+fn test_exchange_rates_changed() {
+    let rates = |czk: &str| {
+        ExchangeRates::parse(&format!(
+            "timestamp 2026-10-18T12:00:00Z\nbase EUR\nCZK {}",
+            czk
+        ))
+        .unwrap()
+    };
+
+    let mut calculator = Calculator::default();
+    calculator.set_exchange_rates(rates("25"));
+    let mut notebook = Notebook::evaluate(&calculator, "2 EUR in CZK\n2 * 3");
+
+    calculator.set_exchange_rates(rates("24"));
+    notebook.data_changed();
+    notebook.update(&calculator, "2 EUR in CZK\n2 * 3");
+
+    assert_eq!(
+        notebook.annotate(Locale::En, UnitStyle::Symbol),
+        "2 EUR in CZK  = 48.00CZK\n2 * 3         = 6"
+    );
+}
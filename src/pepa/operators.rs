@@ -33,31 +33,31 @@ pub fn operators() -> &'static HashMap<char, Operator> {
                 representation: '+',
                 priority: Priority::Low,
                 unary_action: Ok,
-                binary_action: |x, y| Ok(x + y),
+                binary_action: |x, y| x.checked_add(y).ok_or(ComputeError::ArithmeticOverflow),
             },
             Operator {
                 representation: '-',
                 priority: Priority::Low,
                 unary_action: |x| Ok(-x),
-                binary_action: |x, y| Ok(x - y),
+                binary_action: |x, y| x.checked_sub(y).ok_or(ComputeError::ArithmeticOverflow),
             },
             Operator {
                 representation: '*',
                 priority: Priority::High,
                 unary_action: unsupported_unary_operator,
-                binary_action: |x, y| Ok(x * y),
+                binary_action: |x, y| x.checked_mul(y).ok_or(ComputeError::ArithmeticOverflow),
             },
             Operator {
                 representation: '/',
                 priority: Priority::High,
                 unary_action: unsupported_unary_operator,
-                binary_action: |x, y| Ok(x / y),
+                binary_action: divide,
             },
             Operator {
                 representation: '^',
                 priority: Priority::Highest,
                 unary_action: unsupported_unary_operator,
-                binary_action: |x, y| Ok(x.powd(y)),
+                binary_action: |x, y| x.checked_powd(y).ok_or(ComputeError::ArithmeticOverflow),
             },
         ] {
             operators.insert(operator.representation, operator);
@@ -67,6 +67,14 @@ pub fn operators() -> &'static HashMap<char, Operator> {
     })
 }
 
+fn divide(x: Decimal, y: Decimal) -> Result<Decimal, ComputeError> {
+    if y.is_zero() {
+        return Err(ComputeError::DivisionByZero);
+    }
+
+    x.checked_div(y).ok_or(ComputeError::ArithmeticOverflow)
+}
+
 fn unsupported_unary_operator(_: Decimal) -> Result<Decimal, ComputeError> {
     Err(ComputeError::UnsupportedUnaryOperator)
 }
@@ -1,12 +1,181 @@
+use chrono::NaiveDate;
+use chrono_tz::TZ_VARIANTS;
+
 use crate::{Decimal, ParserError};
+use crate::complex::is_imaginary_unit;
 use crate::constants::constants;
+use crate::datetime::DateTimeValue;
 use crate::expression::{Expression, ExpressionToken, NumericExpression};
-use crate::functions::{function_names, functions};
+use crate::functions::{function, function_names};
 use crate::generators::generators;
 use crate::operators::{CONVERSION_CHARACTER, operators, Priority};
-use crate::units::Unit;
+use crate::units::{Currency, Epoch, Time, Unit};
 use crate::utils::split_string_by_comma;
 
+/// Hyphen of a date literal, it is not a minus: 2026-12-24
+const DATE_SEPARATOR: char = '\u{2010}';
+
+/// Prefix of a name bound to a value, it is not a unit: `days`
+const SYMBOL_MARK: char = '\u{E000}';
+
+/// Separator of the magnitude and the phase of a complex number: `5∠90°`
+const POLAR_SIGN: char = '∠';
+
+/// Marks the hyphens of dates written without spaces, before the spaces are removed:
+/// `2026-12-24` is a date, `2030 - 12 - 24` is a subtraction.
+pub(super) fn mark_dates(line: &str) -> String {
+    let mut chars = line.chars().collect::<Vec<_>>();
+    let is_digit = |c: Option<&char>| c.is_some_and(char::is_ascii_digit);
+
+    for start in 0..chars.len().saturating_sub(9) {
+        let end = start + 10;
+        let candidate = chars[start..end].iter().collect::<String>();
+        let pattern = candidate
+            .char_indices()
+            .all(|(i, c)| if i == 4 || i == 7 { c == '-' } else { c.is_ascii_digit() });
+        let separated = (start == 0 || !chars[start - 1].is_alphanumeric())
+            && !is_digit(chars.get(end));
+
+        if pattern && separated && NaiveDate::parse_from_str(&candidate, "%Y-%m-%d").is_ok() {
+            chars[start + 4] = DATE_SEPARATOR;
+            chars[start + 7] = DATE_SEPARATOR;
+        }
+    }
+
+    chars.into_iter().collect()
+}
+
+/// Writes mixed numbers as sums before the spaces are removed: `2 1/2` => `(2+1/2)`, the whole
+/// part may have digit groups: `12 345 a 1/2`. Only proper fractions are mixed, `1 000/4` is a
+/// number with a digit group.
+pub(super) fn mark_mixed_numbers(line: &str, separator: &str) -> String {
+    let chars = line.chars().collect::<Vec<_>>();
+    let separator = separator.chars().collect::<Vec<_>>();
+    let is_digit = |i: usize| chars.get(i).is_some_and(char::is_ascii_digit);
+    let digits = |from: usize| (from..chars.len()).take_while(|i| is_digit(*i)).count();
+    let mut res = String::new();
+    let mut copied = 0;
+
+    for p in 1..chars.len() {
+        if p < copied || !is_digit(p - 1) || !chars[p..].starts_with(&separator) {
+            continue;
+        }
+
+        let numerator = p + separator.len();
+        let n = digits(numerator);
+        let denominator = numerator + n + 1;
+        let d = digits(denominator);
+        let end = denominator + d;
+        if n == 0 || d == 0 || chars[numerator] == '0' || chars[numerator + n] != '/' {
+            continue;
+        }
+        if chars
+            .get(end)
+            .is_some_and(|c| c.is_ascii_digit() || ['.', '/', '^'].contains(c))
+        {
+            continue;
+        }
+
+        let number = |from: usize, len: usize| {
+            chars[from..from + len]
+                .iter()
+                .collect::<String>()
+                .parse::<Decimal>()
+                .ok()
+        };
+        if number(numerator, n) >= number(denominator, d) {
+            continue;
+        }
+
+        // whole part with its digit groups
+        let mut start = p - 1;
+        while start > 0
+            && (is_digit(start - 1) || chars[start - 1] == ' ' && start > 1 && is_digit(start - 2))
+        {
+            start -= 1;
+        }
+        if start < copied
+            || start > 0 && (chars[start - 1].is_alphanumeric() || chars[start - 1] == '.')
+        {
+            continue;
+        }
+
+        res.extend(&chars[copied..start]);
+        res.push('(');
+        res.extend(&chars[start..p]);
+        res.push('+');
+        res.extend(&chars[numerator..end]);
+        res.push(')');
+        copied = end;
+    }
+
+    res.extend(&chars[copied..]);
+    res
+}
+
+/// Marks the names bound to values, they are symbols even if a unit has the same name:
+/// `days * rate` with a `days` column. Conversion targets are not marked: `distance in m`
+pub(super) fn mark_symbols(line: &str, names: &[&str]) -> String {
+    let chars = line.chars().collect::<Vec<_>>();
+    let is_name_char = |c: &char| c.is_alphanumeric() || *c == '_' || *c == '.';
+    let mut res = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let start = i == 0 || !is_name_char(&chars[i - 1]) && chars[i - 1] != CONVERSION_CHARACTER;
+        let word = chars[i..]
+            .iter()
+            .take_while(|c| is_name_char(c))
+            .collect::<String>();
+
+        if start && !word.is_empty() {
+            if names.contains(&word.as_str()) {
+                res.push(SYMBOL_MARK);
+            }
+            res.push_str(&word);
+            i += word.chars().count();
+        } else {
+            res.push(chars[i]);
+            i += 1;
+        }
+    }
+
+    res
+}
+
+/// Marks the single letters of an equation as unknowns, even if they are units: `t * 2 = 6`.
+/// Letters after a number stay units: `x + 30 min = 2 h`
+pub(super) fn mark_unknowns(line: &str) -> String {
+    if !line.contains('=') && !line.contains("solve(") {
+        return line.to_owned();
+    }
+
+    let chars = line.chars().collect::<Vec<_>>();
+    let is_name_char = |c: &char| c.is_alphanumeric() || *c == '_' || *c == '.';
+    let mut res = String::new();
+
+    for (i, c) in chars.iter().enumerate() {
+        let previous = chars[..i].iter().rev().find(|c| !c.is_whitespace());
+        let single = c.is_ascii_alphabetic()
+            && (i == 0 || !is_name_char(&chars[i - 1]))
+            && chars.get(i + 1).is_none_or(|c| !is_name_char(c));
+        let after_value = previous
+            .is_some_and(|p| p.is_ascii_digit() || [')', ']', CONVERSION_CHARACTER].contains(p));
+        let name = c.to_string();
+
+        if single
+            && !after_value
+            && !constants().contains_key(name.as_str())
+            && !is_imaginary_unit(&name)
+        {
+            res.push(SYMBOL_MARK);
+        }
+        res.push(*c);
+    }
+
+    res
+}
+
 pub(super) fn parse(ex: &str) -> Result<Expression, ParserError> {
     let mut expression = Expression::new();
 
@@ -14,7 +183,9 @@ pub(super) fn parse(ex: &str) -> Result<Expression, ParserError> {
 
     // replace generator fce by name only
     for (name, g) in generators().iter() {
-        ex = ex.replace(&g.fce_name, name);
+        if g.fce_name.ends_with("()") {
+            ex = ex.replace(&g.fce_name, name);
+        }
     }
 
     if ex.is_empty() {
@@ -37,6 +208,31 @@ pub(super) fn parse(ex: &str) -> Result<Expression, ParserError> {
         }
     }
 
+    // polar form of a complex number as it is shown: 5∠90°
+    let ex = polar_form(&ex);
+
+    // values are not stored to names: (15, 16, 17) => x
+    if ex.contains("=>") {
+        return Err(ParserError::UnsupportedAssignment(ex));
+    }
+
+    // equation: 2x + 3 = 11
+    if let Some(ex) = parse_equation(&ex)? {
+        return Ok(ex);
+    }
+
+    // form of a number: (3 + 4i) in polar, 0.75 in fraction
+    for form in ["polar", "rectangular", "fraction", "mixed"] {
+        if let Some(ex) = ex.strip_suffix(&format!("{}{}", CONVERSION_CHARACTER, form)) {
+            return parse(&format!("{}({})", form, ex));
+        }
+    }
+
+    // conversion of the whole expression: 2026-12-24 - today in days
+    if let Some(ex) = parse_expression_conversion(&ex)? {
+        return Ok(ex);
+    }
+
     // read while not operator
     let mut token = String::new();
 
@@ -45,6 +241,18 @@ pub(super) fn parse(ex: &str) -> Result<Expression, ParserError> {
     while let Some(c) = chars.next() {
         // process operators
         if let Some(o) = operators().get(&c) {
+            // currency pair symbol: EUR/CZK
+            if c == '/' && is_currency_pair(&token, chars.as_str()) {
+                token.push(c);
+                continue;
+            }
+
+            // time zone name: Europe/Prague
+            if c == '/' && is_time_zone(&token, chars.as_str()) {
+                token.push(c);
+                continue;
+            }
+
             if !token.is_empty() {
                 let ex = parse_token(&token)?;
                 expression.push(ex);
@@ -113,6 +321,55 @@ pub(super) fn parse(ex: &str) -> Result<Expression, ParserError> {
             continue;
         }
 
+        if c == ']' {
+            return Err(ParserError::UnbalancedParentheses(ex.to_owned()));
+        }
+
+        if c == '[' {
+            if !token.is_empty() {
+                let ex = parse_token(&token)?;
+                expression.push(ex);
+                token.clear()
+            }
+
+            // read to the matching bracket
+            let mut count = 1;
+            let mut inner = String::new();
+
+            for c in chars.by_ref() {
+                match c {
+                    '[' => count += 1,
+                    ']' => count -= 1,
+                    _ => {}
+                }
+
+                if count == 0 {
+                    break;
+                }
+                inner.push(c);
+            }
+
+            if count != 0 {
+                return Err(ParserError::UnbalancedParentheses(ex.to_owned()));
+            }
+
+            // a bracket after a value would be an index: (1, 2, 3)[1]
+            if let Some(
+                ExpressionToken::Numeric(_)
+                | ExpressionToken::Expression(_)
+                | ExpressionToken::Symbol(_)
+                | ExpressionToken::Generator(_)
+                | ExpressionToken::List(_)
+                | ExpressionToken::Matrix(_),
+            ) = expression.tokens.last()
+            {
+                return Err(ParserError::UnsupportedIndexing(ex.to_owned()));
+            }
+
+            expression.push(parse_matrix(&inner)?);
+            continue;
+        }
+
         // just append token
         token.push(c);
     }
@@ -157,7 +414,8 @@ pub(super) fn parse(ex: &str) -> Result<Expression, ParserError> {
             }
             ExpressionToken::Numeric(_) => normalized.push(e),
             ExpressionToken::Expression(e2) => {
-                if e2.tokens.len() == 1 {
+                // a list in parentheses is a single parameter: sqrt((1, 4, 9))
+                if e2.tokens.len() == 1 && !matches!(e2.tokens[0], ExpressionToken::List(_)) {
                     // unwrap
                     normalized.push(e2.tokens.first().unwrap().clone())
                 } else {
@@ -167,7 +425,10 @@ pub(super) fn parse(ex: &str) -> Result<Expression, ParserError> {
             }
             ExpressionToken::Function(_) => normalized.push(e),
             ExpressionToken::Generator(_) => normalized.push(e),
+            ExpressionToken::Symbol(_) => normalized.push(e),
             ExpressionToken::List(_) => normalized.push(e),
+            ExpressionToken::Matrix(_) => normalized.push(e),
+            ExpressionToken::Equation(..) => normalized.push(e),
             ExpressionToken::ConversionChain(_) => normalized.push(e),
         }
     }
@@ -226,6 +487,28 @@ pub(super) fn parse(ex: &str) -> Result<Expression, ParserError> {
         expression = prioritized;
     }
 
+    // a leading minus is a part of the converted number: -1700000000 as date, -20 °C in K
+    if let [ExpressionToken::Operator(minus), ExpressionToken::Expression(converted), ..] =
+        &mut expression.tokens[..]
+    {
+        if minus.representation == '-'
+            && matches!(
+                converted.tokens[..],
+                [ExpressionToken::Numeric(_), ExpressionToken::ConversionChain(_), ..]
+            )
+        {
+            let number = converted.tokens.remove(0);
+            converted.tokens.insert(
+                0,
+                ExpressionToken::Expression(Expression::from_tokens(vec![
+                    ExpressionToken::Operator(minus.clone()),
+                    number,
+                ])),
+            );
+            expression.tokens.remove(0);
+        }
+    }
+
     // prioritize conversion chains
     {
         let mut prioritized = Expression::new();
@@ -305,6 +588,36 @@ pub(super) fn parse(ex: &str) -> Result<Expression, ParserError> {
     Ok(expression)
 }
 
+/// Magnitude and phase of the polar form as a function: `5∠90°` => `polar(5,90°)`
+fn polar_form(ex: &str) -> String {
+    let mut chars = ex.chars().collect::<Vec<_>>();
+    let is_number = |c: &char| c.is_ascii_digit() || *c == '.';
+
+    while let Some(i) = chars.iter().position(|c| *c == POLAR_SIGN) {
+        let start = i - chars[..i].iter().rev().take_while(|c| is_number(c)).count();
+        let sign = usize::from(chars.get(i + 1) == Some(&'-'));
+        let digits = chars[i + 1 + sign..].iter().take_while(|c| is_number(c)).count();
+        let unit = chars[i + 1 + sign + digits..]
+            .iter()
+            .take_while(|c| c.is_alphabetic() || **c == '°')
+            .count();
+
+        if start == i || digits == 0 {
+            break;
+        }
+
+        let end = i + 1 + sign + digits + unit;
+        let polar = format!(
+            "polar({},{})",
+            chars[start..i].iter().collect::<String>(),
+            chars[i + 1..end].iter().collect::<String>()
+        );
+        chars.splice(start..end, polar.chars());
+    }
+
+    chars.into_iter().collect()
+}
+
 fn parse_token(token: &str) -> Result<ExpressionToken, ParserError> {
     if token.is_empty() {
         return Err(ParserError::EmptyToken);
@@ -317,9 +630,50 @@ fn parse_token(token: &str) -> Result<ExpressionToken, ParserError> {
         )));
     }
 
+    // duration: 01:30:15, PT1H30M
+    let (head, tail) = token
+        .split_once(CONVERSION_CHARACTER)
+        .map(|(head, _)| (head, &token[head.len()..]))
+        .unwrap_or((token, ""));
+
+    if let Some(seconds) = Time::parse_duration(head) {
+        // shown in the form it was written: PT1H30M, 01:30:15
+        let form = match head.starts_with('P') {
+            true => Time::Iso8601,
+            false => Time::Clock,
+        };
+        let ex = ExpressionToken::Numeric(NumericExpression::with_unit(
+            seconds,
+            Some(Unit::Time(form)),
+        ));
+        if tail.is_empty() {
+            return Ok(ex);
+        }
+
+        return Ok(ExpressionToken::Expression(Expression::from_tokens(vec![
+            ex,
+            parse_token(tail)?,
+        ])));
+    }
+
+    // date and time literal, optionally followed by a zone: 15:00 Prague
+    let literal = token.replace(DATE_SEPARATOR, "-");
+    if let Some((d, units)) = DateTimeValue::parse_prefix(&literal) {
+        let ex = ExpressionToken::Numeric(NumericExpression::DateTime(d));
+        if units.is_empty() {
+            return Ok(ex);
+        }
+
+        // the units are a zone of the value or conversion targets
+        return Ok(ExpressionToken::Expression(Expression::from_tokens(vec![
+            ex,
+            parse_token(&format!("{}{}", CONVERSION_CHARACTER, units))?,
+        ])));
+    }
+
     // function
-    if let Some(f) = functions().get(token) {
-        return Ok(ExpressionToken::Function((*f).clone()));
+    if let Some(f) = function(token) {
+        return Ok(ExpressionToken::Function(f));
     }
 
     // constant
@@ -338,7 +692,7 @@ fn parse_token(token: &str) -> Result<ExpressionToken, ParserError> {
     // sin cos ( PI )
     for fce_name in function_names().iter() {
         if token.starts_with(fce_name) {
-            let Some(fce) = functions().get(fce_name) else {
+            let Some(fce) = function(fce_name) else {
                 return Err(ParserError::InvalidFunctionName((*fce_name).to_owned()));
             };
 
@@ -347,7 +701,7 @@ fn parse_token(token: &str) -> Result<ExpressionToken, ParserError> {
             };
 
             return Ok(ExpressionToken::Expression(Expression::from_tokens(vec![
-                ExpressionToken::Function((*fce).clone()),
+                ExpressionToken::Function(fce),
                 ex,
             ])));
         }
@@ -358,9 +712,12 @@ fn parse_token(token: &str) -> Result<ExpressionToken, ParserError> {
     let mut number = String::new();
     let mut unit = String::new();
     let mut capturing_number = token.chars().next().unwrap().is_numeric();
-    if capturing_number {
+    if token.starts_with(CONVERSION_CHARACTER) {
+        // conversion targets have no number: in iso8601
+        unit.push_str(token);
+    } else if capturing_number {
         for c in token.chars() {
-            if capturing_number && c.is_numeric() {
+            if capturing_number && (c.is_numeric() || c == '.') {
                 number.push(c);
             } else {
                 capturing_number = false;
@@ -378,19 +735,60 @@ fn parse_token(token: &str) -> Result<ExpressionToken, ParserError> {
         }
     }
 
-    let units = unit
+    let names = unit
         .split(CONVERSION_CHARACTER)
         .filter(|&x| !x.is_empty())
-        .map(Unit::from_string)
         .collect::<Vec<_>>();
 
-    if units.is_empty() || units.iter().any(|u| u.is_empty()) {
+    // time zones are only conversion targets: 15:00 in Prague
+    let targets = unit.starts_with(CONVERSION_CHARACTER);
+    let units = names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| match i > 0 || targets {
+            _ if name.starts_with(SYMBOL_MARK) => vec![],
+            true => Unit::from_target_string(name),
+            false => Unit::from_string(name),
+        })
+        .collect::<Vec<_>>();
+
+    if units.is_empty() || units[1..].iter().any(|u| u.is_empty()) {
         return Err(ParserError::InvalidToken(token.to_owned()));
     }
 
+    // bound name: 2 days with a `days` column
+    if let Some(name) = names[0].strip_prefix(SYMBOL_MARK) {
+        if !is_symbol(name) {
+            return Err(ParserError::InvalidToken(token.to_owned()));
+        }
+
+        return quantity(
+            &number,
+            ExpressionToken::Symbol(name.to_owned()),
+            &units,
+            token,
+        );
+    }
+
+    // not a unit - a symbol resolved during computation: 10 AAPL
+    if units[0].is_empty() {
+        if !is_symbol(names[0]) || !number.is_empty() && !is_quantity_symbol(names[0]) {
+            return Err(ParserError::InvalidToken(token.to_owned()));
+        }
+
+        let ex = match generators().get(names[0]) {
+            // today in days
+            Some(g) => ExpressionToken::Generator((*g).clone()),
+            None => ExpressionToken::Symbol(names[0].to_owned()),
+        };
+
+        return quantity(&number, ex, &units, token);
+    }
+
     // number must be a numeric value
     if let Ok(n) = number.parse::<Decimal>() {
-        if units.len() == 1 {
+        // date is not a unit of the number: 1700000000 as date
+        if units.len() == 1 && units[0] != [Unit::Epoch(Epoch::Date)] {
             return Ok(ExpressionToken::Numeric(NumericExpression::with_units(
                 n,
                 units[0].clone(),
@@ -405,3 +803,193 @@ fn parse_token(token: &str) -> Result<ExpressionToken, ParserError> {
 
     Ok(ExpressionToken::ConversionChain(units))
 }
+
+/// Symbol multiplied by the number before it and converted to the units after it: `2x`, `10 AAPL→CZK`
+fn quantity(
+    number: &str,
+    symbol: ExpressionToken,
+    units: &[Vec<Unit>],
+    token: &str,
+) -> Result<ExpressionToken, ParserError> {
+    let mut ex = symbol;
+
+    if !number.is_empty() {
+        let Ok(n) = number.parse::<Decimal>() else {
+            return Err(ParserError::InvalidToken(token.to_owned()));
+        };
+
+        ex = ExpressionToken::Expression(Expression::from_tokens(vec![
+            ExpressionToken::Numeric(NumericExpression::with_unit(n, None)),
+            ExpressionToken::Operator(operators().get(&'*').unwrap().clone()),
+            ex,
+        ]));
+    }
+
+    if units.len() == 1 {
+        return Ok(ex);
+    }
+
+    Ok(ExpressionToken::Expression(Expression::from_tokens(vec![
+        ex,
+        ExpressionToken::ConversionChain(units[1..].to_vec()),
+    ])))
+}
+
+/// Rows of a matrix literal without the outer brackets: `[1, 2], [3, 4]`, items without brackets
+/// are the rows of a column vector: `5, 6`
+fn parse_matrix(ex: &str) -> Result<ExpressionToken, ParserError> {
+    let invalid = || ParserError::InvalidMatrix(format!("[{}]", ex));
+
+    let items = split_string_by_comma(ex);
+    let rows = items.iter().filter(|i| i.starts_with('[')).count();
+    let rows = if rows > 0 && rows == items.len() {
+        items
+            .iter()
+            .map(|i| {
+                let row = i.strip_prefix('[').and_then(|i| i.strip_suffix(']'));
+                let row = split_string_by_comma(row.ok_or_else(invalid)?);
+                row.iter().map(|e| parse(e)).collect()
+            })
+            .collect::<Result<Vec<Vec<_>>, _>>()?
+    } else if rows == 0 {
+        items
+            .iter()
+            .map(|e| parse(e).map(|e| vec![e]))
+            .collect::<Result<_, _>>()?
+    } else {
+        return Err(invalid());
+    };
+
+    let cols = rows.first().map(|r| r.len()).unwrap_or_default();
+    if cols == 0 || rows.iter().any(|r| r.len() != cols) {
+        return Err(invalid());
+    }
+
+    Ok(ExpressionToken::Matrix(rows))
+}
+
+/// Sides of an equation split by a `=` outside of parentheses, an equation with more of them is
+/// invalid: `a = b = c`
+fn parse_equation(ex: &str) -> Result<Option<Expression>, ParserError> {
+    let mut depth = 0;
+    let mut positions = Vec::new();
+
+    for (i, c) in ex.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            '=' if depth == 0 => positions.push(i),
+            _ => {}
+        }
+    }
+
+    let [position] = positions[..] else {
+        if positions.is_empty() {
+            return Ok(None);
+        }
+        return Err(ParserError::InvalidToken(String::from("=")));
+    };
+
+    let (left, right) = (&ex[..position], &ex[position + 1..]);
+    if left.is_empty() || right.is_empty() {
+        return Err(ParserError::EmptyExpression);
+    }
+
+    Ok(Some(Expression::from_tokens(vec![
+        ExpressionToken::Equation(parse(left)?, parse(right)?),
+    ])))
+}
+
+fn parse_expression_conversion(ex: &str) -> Result<Option<Expression>, ParserError> {
+    let mut depth = 0;
+    let mut position = None;
+
+    for (i, c) in ex.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            CONVERSION_CHARACTER if depth == 0 => {
+                position = Some(i);
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    let Some(position) = position else {
+        return Ok(None);
+    };
+
+    let (left, units) = ex.split_at(position);
+    if units
+        .chars()
+        .any(|c| c == '(' || operators().contains_key(&c))
+    {
+        return Ok(None);
+    }
+
+    let left = parse(left)?;
+    let binary_operation = left
+        .tokens
+        .iter()
+        .skip(1)
+        .any(|t| matches!(t, ExpressionToken::Operator(_)));
+
+    if !binary_operation {
+        return Ok(None);
+    }
+
+    Ok(Some(Expression::from_tokens(vec![
+        ExpressionToken::Expression(left),
+        parse_token(units)?,
+    ])))
+}
+
+fn is_symbol(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    chars.next().is_some_and(|c| c.is_alphabetic())
+        && chars.all(|c| c.is_alphanumeric() || c == '.' || c == '_' || c == '/')
+}
+
+/// Symbol multiplied by a number: an unknown `2x` or a ticker `10 AAPL`, other words after a number
+/// are misspelled units: `5 kmm`
+fn is_quantity_symbol(name: &str) -> bool {
+    name.chars().count() == 1 || !name.chars().any(char::is_lowercase)
+}
+
+fn is_time_zone(token: &str, rest: &str) -> bool {
+    let is_name_char = |c: &char| c.is_ascii_alphabetic() || *c == '_' || *c == '/';
+
+    // America/Argentina/Buenos_Aires
+    let area = token
+        .chars()
+        .rev()
+        .take_while(is_name_char)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect::<String>();
+    let city = rest
+        .chars()
+        .take_while(|c| is_name_char(c) && *c != '/')
+        .collect::<String>();
+    let name = format!("{}/{}", area, city).to_lowercase();
+
+    !area.is_empty()
+        && TZ_VARIANTS.iter().any(|tz| {
+            let tz = tz.name().to_lowercase();
+            tz == name || tz.starts_with(&(name.clone() + "/"))
+        })
+}
+
+fn is_currency_pair(token: &str, rest: &str) -> bool {
+    let from = token
+        .chars()
+        .skip(token.chars().count().saturating_sub(3))
+        .collect::<String>();
+    let to = rest.chars().take(3).collect::<String>();
+    let separated = rest.chars().nth(3).is_none_or(|c| !c.is_alphanumeric());
+
+    separated && Currency::from_code(&from).is_some() && Currency::from_code(&to).is_some()
+}
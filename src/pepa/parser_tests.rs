@@ -68,3 +68,16 @@ fn test_explain() {
         }
     }
 }
+
+#[test]
+fn test_error_kinds() {
+    let mut computer = Calculator::default();
+
+    let statement = computer.compute("(5 + 3").unwrap();
+    let error = statement.expression.as_ref().unwrap_err();
+    assert_eq!(<&str>::from(error), "unbalanced_parentheses");
+
+    let statement = computer.compute("foo * 2").unwrap();
+    let error = statement.result.as_ref().unwrap().as_ref().unwrap_err();
+    assert_eq!(<&str>::from(error), "unknown_symbol");
+}
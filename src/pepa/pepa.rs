@@ -1,22 +1,60 @@
 use std::collections::HashMap;
+use std::path::Path;
+use std::rc::Rc;
+
+use chrono::{DateTime, TimeDelta, Utc};
 
 use crate::compute::compute;
+use crate::context::Context;
+use crate::disambiguation::disambiguate;
+use crate::exchange_rates::ExchangeRates;
 use crate::expression::{Expression, NumericExpression};
+use crate::holidays::Holidays;
+use crate::locale::Locale;
 use crate::operators::CONVERSION_CHARACTER;
-use crate::parser::parse;
+use crate::parser::{mark_dates, mark_mixed_numbers, mark_symbols, mark_unknowns, parse};
+use crate::providers::{DataProvider, Quote};
 use crate::ComputeError::InvalidExpression;
-use crate::{string, ComputeError, ParserError};
+use crate::units::{TimeZone, UnitFamily};
+use crate::{string, ComputeError, DataError, ParserError, Warning};
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Statement {
     pub request: String,
     pub expression: Result<Expression, ParserError>,
     pub result: Option<Result<NumericExpression, ComputeError>>,
+    /// timestamp of the exchange rates, if the result used them
+    pub exchange_rates_timestamp: Option<DateTime<Utc>>,
+    /// the result used the holidays: `workdays()`, `isworkday()`, `5 workdays`
+    pub holidays_used: bool,
+    /// quotes of data providers used by the result
+    pub quotes: Vec<Quote>,
+    pub warnings: Vec<Warning>,
+}
+
+impl Statement {
+    /// Age of the oldest cached data used by the result.
+    pub fn data_age(&self) -> Option<TimeDelta> {
+        self.quotes
+            .iter()
+            .filter_map(|q| q.cache.as_ref())
+            .map(|c| Utc::now() - c.stored)
+            .max()
+    }
+
+    /// The result used cached data older than the cache TTL.
+    pub fn is_stale(&self) -> bool {
+        self.quotes
+            .iter()
+            .any(|q| q.cache.as_ref().is_some_and(|c| c.expired))
+    }
 }
 
 pub struct Calculator {
     statements: Vec<Statement>,
-    variables: HashMap<String, NumericExpression>,
+    context: Context,
+    locale: Locale,
 }
 
 impl Default for Calculator {
@@ -29,36 +67,108 @@ impl Calculator {
     pub fn new() -> Calculator {
         Calculator {
             statements: vec![],
-            variables: Default::default(),
+            context: Default::default(),
+            locale: Default::default(),
         }
     }
 
+    /// Resets all statements, configured data (exchange rates) are kept.
     pub fn reset(&mut self) {
+        let context = std::mem::take(&mut self.context);
+        let locale = self.locale;
         *self = Calculator::new();
+        self.context = context;
+        self.locale = locale;
+    }
+
+    pub fn set_exchange_rates(&mut self, rates: ExchangeRates) {
+        self.context.exchange_rates = Some(rates);
+    }
+
+    pub fn load_exchange_rates(&mut self, path: &Path) -> Result<(), DataError> {
+        self.set_exchange_rates(ExchangeRates::from_file(path)?);
+        Ok(())
+    }
+
+    /// Non-working days used by the business day functions.
+    pub fn set_holidays(&mut self, holidays: Holidays) {
+        self.context.holidays = holidays;
+    }
+
+    pub fn load_holidays(&mut self, path: &Path) -> Result<(), DataError> {
+        self.set_holidays(Holidays::from_file(path)?);
+        Ok(())
+    }
+
+    /// Providers are consulted in the order they were added.
+    pub fn add_provider(&mut self, provider: Rc<dyn DataProvider>) {
+        self.context.providers.push(provider);
+    }
+
+    /// Disables all data fetching, evaluation uses only local and cached data.
+    pub fn set_offline(&mut self, offline: bool) {
+        self.context.offline = offline;
+    }
+
+    /// Zone of `now`, `today` and of dates without a zone, the system zone by default.
+    pub fn set_time_zone(&mut self, zone: TimeZone) {
+        self.context.time_zone = Some(zone);
+    }
+
+    /// Fixed current time of `now`, `today` and `timestamp()`, the system clock by default.
+    pub fn set_now(&mut self, now: DateTime<Utc>) {
+        self.context.now = Some(now);
+    }
+
+    /// Families preferred for ambiguous units, the first matching one is used.
+    pub fn set_preferred_families(&mut self, families: &[UnitFamily]) {
+        self.context.preferred_families = families.to_vec();
+    }
+
+    /// Number format of the input, the output is formatted by [`crate::Localize`].
+    pub fn set_locale(&mut self, locale: Locale) {
+        self.locale = locale;
+    }
+
+    pub fn locale(&self) -> Locale {
+        self.locale
     }
 
     fn prepare(&mut self, statement: &str) {
         for line in statement
             .lines()
             .map(|line| line.trim())
-            .filter(|line| !line.is_empty())
-            .filter(|line| !line.starts_with('#'))
-            .filter(|line| !line.starts_with("//"))
+            .filter(|line| is_statement(line))
         {
-            let mut line = line.to_owned();
-            for r in [" in ", " to ", " into "] {
-                line = line.replace(r, &string!(CONVERSION_CHARACTER))
-            }
+            let statement = self.statement(line);
+            self.statements.push(statement);
+        }
+    }
+
+    fn statement(&self, line: &str) -> Statement {
+        self.statement_with(line, &[])
+    }
 
-            let compacted_line = line.split_whitespace().collect::<Vec<_>>().join("");
+    fn statement_with(&self, line: &str, names: &[&str]) -> Statement {
+        let normalized = mark_mixed_numbers(
+            &conversion_keywords(&self.locale.normalize_input(line)),
+            self.locale.mixed_separator(),
+        );
 
-            let expression = parse(&compacted_line);
-            let statement = Statement {
-                request: line.to_owned(),
-                expression,
-                result: None,
-            };
-            self.statements.push(statement);
+        let compacted_line = mark_dates(&mark_symbols(&mark_unknowns(&normalized), names))
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join("");
+
+        let expression = parse(&compacted_line);
+        Statement {
+            request: line.to_owned(),
+            expression,
+            result: None,
+            exchange_rates_timestamp: None,
+            holidays_used: false,
+            quotes: vec![],
+            warnings: vec![],
         }
     }
 
@@ -67,19 +177,128 @@ impl Calculator {
 
         for s in self.statements.iter_mut() {
             if s.result.is_none() {
-                if let Ok(e) = &s.expression {
-                    s.result = Some(compute(e));
-                } else {
-                    s.result = Some(Err(InvalidExpression(string!(s.request))));
-                }
+                compute_statement(s, &self.context);
             }
         }
 
         self.statements.last()
     }
 
+    /// Computes a line without storing its statement, `None` for empty lines and comments.
+    pub fn compute_line(&self, line: &str) -> Option<Statement> {
+        let line = line.trim();
+        if !is_statement(line) {
+            return None;
+        }
+
+        let mut statement = self.statement(line);
+        compute_statement(&mut statement, &self.context);
+        Some(statement)
+    }
+
+    /// Computes a line with the names bound to values, the statement is not stored:
+    /// `price * qty` for every row of a table.
+    pub fn compute_with(
+        &mut self,
+        line: &str,
+        variables: HashMap<String, NumericExpression>,
+    ) -> Statement {
+        let names = variables.keys().map(String::as_str).collect::<Vec<_>>();
+        let statement = self.prepare_with(line, &names);
+        self.compute_prepared(&statement, variables)
+    }
+
+    /// Parses a line once for [`Calculator::compute_prepared`], the names are symbols even if
+    /// a unit has the same name: `days * rate`
+    pub fn prepare_with(&self, line: &str, names: &[&str]) -> Statement {
+        self.statement_with(line.trim(), names)
+    }
+
+    /// Computes a prepared statement with the names bound to values, the statement is not stored.
+    pub fn compute_prepared(
+        &mut self,
+        statement: &Statement,
+        variables: HashMap<String, NumericExpression>,
+    ) -> Statement {
+        let mut statement = statement.clone();
+
+        self.context.variables = variables;
+        compute_statement(&mut statement, &self.context);
+        self.context.variables.clear();
+
+        statement
+    }
+
     pub fn prepare_statements(&mut self, statement: &str) -> Option<&Statement> {
         self.prepare(statement);
         self.statements.last()
     }
+
+    pub fn statements(&self) -> &[Statement] {
+        &self.statements
+    }
+}
+
+/// Replaces the conversion keywords between words, `as` reads better for instants:
+/// `1700000000 as date`. An `in` after a number is the inch if no target follows: `5/16 in`,
+/// `5 in in cm`
+fn conversion_keywords(line: &str) -> String {
+    const KEYWORDS: [&str; 4] = ["in", "to", "into", "as"];
+
+    let words = line.split_whitespace().collect::<Vec<_>>();
+    let mut res = String::new();
+    let mut conversion = false;
+
+    for (i, word) in words.iter().enumerate() {
+        let inch = *word == "in"
+            && i > 0
+            && words[i - 1].ends_with(|c: char| c.is_ascii_digit())
+            && words.get(i + 1).is_none_or(|next| {
+                KEYWORDS.contains(next) || next.starts_with(['+', '-', '*', '/', '^', ')', ','])
+            });
+
+        // a keyword after a keyword is the target: 5 cm in in
+        conversion =
+            i > 0 && i + 1 < words.len() && !conversion && !inch && KEYWORDS.contains(word);
+
+        if conversion {
+            res.push(CONVERSION_CHARACTER);
+        } else {
+            if i > 0 && !res.ends_with(CONVERSION_CHARACTER) {
+                res.push(' ');
+            }
+            res.push_str(word);
+        }
+    }
+
+    res
+}
+
+fn is_statement(line: &str) -> bool {
+    !line.is_empty() && !line.starts_with('#') && !line.starts_with("//")
+}
+
+fn compute_statement(s: &mut Statement, context: &Context) {
+    let Ok(e) = &s.expression else {
+        s.result = Some(Err(InvalidExpression(string!(s.request))));
+        return;
+    };
+
+    context.reset_usage();
+    s.result = Some(compute(e, context).map(|n| {
+        let (n, warning) = disambiguate(n, &context.preferred_families);
+        s.warnings.extend(warning);
+        n
+    }));
+
+    if context.exchange_rates_used() {
+        s.exchange_rates_timestamp = context.exchange_rates.as_ref().map(|r| r.timestamp);
+    }
+    s.holidays_used = context.holidays_used();
+
+    for quote in context.quotes_used() {
+        if !s.quotes.contains(&quote) {
+            s.quotes.push(quote);
+        }
+    }
 }
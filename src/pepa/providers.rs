@@ -0,0 +1,184 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+
+use crate::units::Unit;
+use crate::{string, DataError, Decimal};
+
+/// Value of a symbol (stock ticker, currency pair ...) provided by a data provider.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Quote {
+    pub symbol: String,
+    pub value: Decimal,
+    pub unit: Option<Unit>,
+    pub timestamp: DateTime<Utc>,
+    pub provider: String,
+    /// set if the quote was served from the cache
+    pub cache: Option<CacheInfo>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CacheInfo {
+    /// when the quote was stored into the cache
+    pub stored: DateTime<Utc>,
+    /// older than the cache TTL
+    pub expired: bool,
+}
+
+/// External source of values for symbols the calculator can not resolve itself,
+/// for example `AAPL` or `EUR/CZK`.
+pub trait DataProvider: Debug {
+    fn name(&self) -> String;
+
+    /// Returns `Ok(None)` if the provider does not know the symbol.
+    fn quote(&self, symbol: &str) -> Result<Option<Quote>, DataError>;
+
+    /// Quote available without any fetching, used in the offline mode.
+    fn cached_quote(&self, _symbol: &str) -> Result<Option<Quote>, DataError> {
+        Ok(None)
+    }
+}
+
+/// Provider backed by a local text file, the file is read on every request.
+///
+/// Text format, one quote per line, `#` starts a comment:
+/// ```text
+/// timestamp 2026-10-18T12:00:00Z
+/// AAPL 230.50 USD
+/// EUR/CZK 24.35 CZK
+/// ```
+#[derive(Debug, Clone)]
+pub struct FileProvider {
+    path: PathBuf,
+}
+
+impl FileProvider {
+    pub fn new(path: &Path) -> FileProvider {
+        FileProvider {
+            path: path.to_path_buf(),
+        }
+    }
+}
+
+impl DataProvider for FileProvider {
+    fn name(&self) -> String {
+        format!("file:{}", self.path.display())
+    }
+
+    fn quote(&self, symbol: &str) -> Result<Option<Quote>, DataError> {
+        let content = fs::read_to_string(&self.path)
+            .map_err(|e| DataError::Io(self.path.display().to_string(), e.to_string()))?;
+
+        let quotes = parse_quotes(&content, &self.name())?;
+
+        Ok(quotes.into_iter().find(|q| q.symbol == symbol))
+    }
+}
+
+pub fn parse_quotes(content: &str, provider: &str) -> Result<Vec<Quote>, DataError> {
+    let mut timestamp = None;
+    let mut quotes = Vec::new();
+
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let invalid = || DataError::InvalidQuotes(i + 1, string!(line));
+
+        let parts = line.split_whitespace().collect::<Vec<_>>();
+
+        if parts[0] == "timestamp" {
+            if parts.len() != 2 {
+                return Err(invalid());
+            }
+
+            let t = DateTime::parse_from_rfc3339(parts[1]).map_err(|_| invalid())?;
+            timestamp = Some(t.with_timezone(&Utc));
+            continue;
+        }
+
+        let Some(timestamp) = timestamp else {
+            return Err(DataError::InvalidQuotes(
+                i + 1,
+                string!("missing timestamp"),
+            ));
+        };
+
+        let unit = match parts.len() {
+            2 => None,
+            3 => match Unit::from_string(parts[2])[..] {
+                [unit] => Some(unit),
+                _ => return Err(invalid()),
+            },
+            _ => return Err(invalid()),
+        };
+
+        quotes.push(Quote {
+            symbol: string!(parts[0]),
+            value: parts[1].parse::<Decimal>().map_err(|_| invalid())?,
+            unit,
+            timestamp,
+            provider: string!(provider),
+            cache: None,
+        });
+    }
+
+    Ok(quotes)
+}
+
+/// In-memory provider for tests, counts the requests it served.
+#[derive(Debug, Default)]
+pub struct MockProvider {
+    quotes: HashMap<String, Quote>,
+    requests: Cell<usize>,
+}
+
+impl MockProvider {
+    pub fn new() -> MockProvider {
+        MockProvider::default()
+    }
+
+    pub fn with_quote(
+        mut self,
+        symbol: &str,
+        value: Decimal,
+        unit: Option<Unit>,
+        timestamp: DateTime<Utc>,
+    ) -> MockProvider {
+        self.quotes.insert(
+            string!(symbol),
+            Quote {
+                symbol: string!(symbol),
+                value,
+                unit,
+                timestamp,
+                provider: self.name(),
+                cache: None,
+            },
+        );
+        self
+    }
+
+    pub fn requests(&self) -> usize {
+        self.requests.get()
+    }
+}
+
+impl DataProvider for MockProvider {
+    fn name(&self) -> String {
+        string!("mock")
+    }
+
+    fn quote(&self, symbol: &str) -> Result<Option<Quote>, DataError> {
+        self.requests.set(self.requests.get() + 1);
+        Ok(self.quotes.get(symbol).cloned())
+    }
+}
@@ -0,0 +1,194 @@
+use std::env;
+use std::fs;
+use std::rc::Rc;
+
+use chrono::{DateTime, TimeZone, Utc};
+use rust_decimal_macros::dec;
+
+use crate::exchange_rates::ExchangeRates;
+use crate::providers::{parse_quotes, DataProvider, FileProvider, MockProvider};
+use crate::units::{Currency, Unit};
+use crate::{Calculator, ComputeError, DataError, ParserError};
+
+fn timestamp() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap()
+}
+
+fn mock() -> MockProvider {
+    MockProvider::new()
+        .with_quote(
+            "AAPL",
+            dec!(230),
+            Some(Unit::Currency(Currency::UsDollar)),
+            timestamp(),
+        )
+        .with_quote(
+            "USD/CZK",
+            dec!(23),
+            Some(Unit::Currency(Currency::CzechKoruna)),
+            timestamp(),
+        )
+}
+
+fn result(calculator: &mut Calculator, test: &str) -> String {
+    let statement = calculator.compute(test).unwrap();
+    match statement.result.as_ref().unwrap() {
+        Ok(n) => n.to_string(),
+        Err(e) => panic!("{}: {:?}", test, e),
+    }
+}
+
+#[test]
+fn test_mock_provider() {
+    let mut calculator = Calculator::default();
+    calculator.add_provider(Rc::new(mock()));
+
+    assert_eq!(result(&mut calculator, "AAPL"), "230.00USD");
+    assert_eq!(result(&mut calculator, "10 AAPL"), "2300.00USD");
+    assert_eq!(result(&mut calculator, "10 AAPL in CZK"), "52900.00CZK");
+    assert_eq!(result(&mut calculator, "USD/CZK"), "23.00CZK");
+    assert_eq!(result(&mut calculator, "100 CZK in USD"), "4.35USD");
+    assert_eq!(result(&mut calculator, "2 * AAPL + 40 $"), "500.00USD");
+
+    let statement = calculator.compute("10 AAPL in CZK").unwrap();
+    assert_eq!(
+        statement
+            .quotes
+            .iter()
+            .map(|q| q.symbol.as_str())
+            .collect::<Vec<_>>(),
+        vec!["AAPL", "USD/CZK"]
+    );
+    assert_eq!(statement.quotes[0].timestamp, timestamp());
+    assert_eq!(statement.quotes[0].provider, "mock");
+
+    let statement = calculator.compute("10 + 5").unwrap();
+    assert!(statement.quotes.is_empty());
+}
+
+#[test]
+fn test_exchange_rates_before_providers() {
+    let mut calculator = Calculator::default();
+    calculator.set_exchange_rates(
+        ExchangeRates::new(timestamp(), Currency::Euro)
+            .with_rate(Currency::CzechKoruna, dec!(25))
+            .with_rate(Currency::UsDollar, dec!(1)),
+    );
+    calculator.add_provider(Rc::new(mock()));
+
+    assert_eq!(result(&mut calculator, "10 AAPL in CZK"), "57500.00CZK");
+    assert_eq!(result(&mut calculator, "EUR/CZK"), "25.00CZK");
+
+    let statement = calculator.compute("10 AAPL in CZK").unwrap();
+    assert_eq!(statement.quotes.len(), 1);
+    assert_eq!(statement.exchange_rates_timestamp, Some(timestamp()));
+}
+
+#[test]
+fn test_offline() {
+    let mock = Rc::new(mock());
+
+    let mut calculator = Calculator::default();
+    calculator.add_provider(mock.clone());
+    calculator.set_offline(true);
+
+    let statement = calculator.compute("10 AAPL").unwrap();
+    assert!(matches!(
+        statement.result,
+        Some(Err(ComputeError::UnknownSymbol(_)))
+    ));
+    assert_eq!(mock.requests(), 0);
+
+    calculator.set_offline(false);
+    assert_eq!(result(&mut calculator, "10 AAPL"), "2300.00USD");
+    assert!(mock.requests() > 0);
+}
+
+#[test]
+fn test_equation_unknowns() {
+    let mock = Rc::new(mock());
+    let mut calculator = Calculator::default();
+    calculator.add_provider(mock.clone());
+
+    // single letter unknowns are not looked up
+    assert_eq!(result(&mut calculator, "2x + 3 = 11"), "x = 4");
+    assert_eq!(
+        result(&mut calculator, "solve(x + z = 10, x - z = 2)"),
+        "x = 6, z = 4"
+    );
+    assert_eq!(mock.requests(), 0);
+
+    assert_eq!(
+        result(&mut calculator, "x + AAPL = 460 USD"),
+        "x = 230.00USD"
+    );
+    assert_eq!(result(&mut calculator, "x * AAPL = 460 USD"), "x = 2");
+}
+
+#[test]
+fn test_unknown_symbol() {
+    let mut calculator = Calculator::default();
+    calculator.add_provider(Rc::new(mock()));
+
+    let statement = calculator.compute("10 MSFT").unwrap();
+    assert!(matches!(
+        statement.result,
+        Some(Err(ComputeError::UnknownSymbol(_)))
+    ));
+
+    // a misspelled unit is not a symbol
+    let statement = calculator.compute("5 kmm").unwrap();
+    assert!(matches!(
+        statement.expression,
+        Err(ParserError::InvalidToken(_))
+    ));
+}
+
+#[test]
+fn test_file_provider() {
+    let path = env::temp_dir().join(format!("pepa-quotes-{}.txt", std::process::id()));
+    fs::write(
+        &path,
+        "
+        # quotes
+        timestamp 2026-10-18T12:00:00Z
+        AAPL 230 USD
+        EUR/CZK 24.5 CZK
+        ",
+    )
+    .unwrap();
+
+    let provider = FileProvider::new(&path);
+    let quote = provider.quote("AAPL").unwrap().unwrap();
+    assert_eq!(quote.value, dec!(230));
+    assert_eq!(quote.unit, Some(Unit::Currency(Currency::UsDollar)));
+    assert_eq!(quote.timestamp, timestamp());
+    assert!(provider.quote("MSFT").unwrap().is_none());
+
+    let mut calculator = Calculator::default();
+    calculator.add_provider(Rc::new(provider));
+    assert_eq!(result(&mut calculator, "100 EUR in CZK"), "2450.00CZK");
+
+    fs::remove_file(&path).unwrap();
+
+    let statement = calculator.compute("AAPL").unwrap();
+    assert!(matches!(
+        statement.result,
+        Some(Err(ComputeError::DataProviderError(_, DataError::Io(_, _))))
+    ));
+}
+
+#[test]
+fn test_parse_quotes_errors() {
+    for (content, line) in [
+        ("AAPL 230 USD", 1),
+        ("timestamp 2026-10-18T12:00:00Z\nAAPL", 2),
+        ("timestamp 2026-10-18T12:00:00Z\nAAPL abc USD", 2),
+        ("timestamp 2026-10-18T12:00:00Z\nAAPL 230 XYZ", 2),
+    ] {
+        match parse_quotes(content, "test") {
+            Err(DataError::InvalidQuotes(l, _)) => assert_eq!(l, line, "{}", content),
+            r => panic!("{:?}: unexpected {:?}", content, r),
+        }
+    }
+}
@@ -0,0 +1,351 @@
+use std::fmt::{Display, Formatter};
+
+use itertools::Itertools;
+
+use crate::expression::NumericExpression;
+use crate::units::Unit;
+use crate::{ComputeError, Decimal};
+
+/// Largest numerator and denominator, the value must be a `Decimal`.
+const MAX: i128 = 79_228_162_514_264_337_593_543_950_335;
+
+/// How a rational number is shown, a decimal if none: `3/4`, `1 3/4`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum FractionForm {
+    Fraction,
+    Mixed,
+}
+
+/// Exact fraction of integers: `1/3`, a division which is not a finite decimal
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+    form: Option<FractionForm>,
+}
+
+impl Rational {
+    /// Reduced fraction with a positive denominator, `None` for a zero denominator or parts which
+    /// are not decimals.
+    pub fn new(numerator: i128, denominator: i128) -> Option<Rational> {
+        if denominator == 0 {
+            return None;
+        }
+
+        let divisor = gcd(numerator, denominator);
+        let sign = denominator.signum();
+        let (numerator, denominator) = (sign * numerator / divisor, sign * denominator / divisor);
+
+        if numerator.abs() > MAX || denominator > MAX {
+            return None;
+        }
+
+        Some(Rational {
+            numerator,
+            denominator,
+            form: None,
+        })
+    }
+
+    /// Exact value of the decimal: `0.75` => `3/4`
+    pub fn from_decimal(n: Decimal) -> Rational {
+        Rational::new(n.mantissa(), 10_i128.pow(n.scale()))
+            .expect("a decimal is a fraction of its mantissa")
+    }
+
+    /// Simplest fraction with the value of the decimal, which may be rounded:
+    /// `0.3333333333333333333333333333` => `1/3`
+    pub fn approximate(n: Decimal) -> Rational {
+        let exact = Rational::from_decimal(n);
+        let sign = exact.numerator.signum();
+
+        // convergents of the continued fraction
+        let (mut a, mut b) = (exact.numerator.abs(), exact.denominator);
+        let (mut h, mut previous_h) = (1_i128, 0_i128);
+        let (mut k, mut previous_k) = (0_i128, 1_i128);
+
+        while b != 0 {
+            let q = a / b;
+            let (Some(next_h), Some(next_k)) = (
+                q.checked_mul(h).and_then(|v| v.checked_add(previous_h)),
+                q.checked_mul(k).and_then(|v| v.checked_add(previous_k)),
+            ) else {
+                break;
+            };
+
+            (previous_h, h, previous_k, k) = (h, next_h, k, next_k);
+            (a, b) = (b, a - q * b);
+
+            match Rational::new(sign * h, k) {
+                Some(r) if r.to_decimal() == n => return r,
+                Some(_) => {}
+                None => break,
+            }
+        }
+
+        exact
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    pub fn with_form(self, form: FractionForm) -> Rational {
+        Rational {
+            form: Some(form),
+            ..self
+        }
+    }
+
+    pub fn to_decimal(&self) -> Decimal {
+        (decimal(self.numerator) / decimal(self.denominator)).normalize()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+
+    pub fn neg(&self) -> Rational {
+        Rational {
+            numerator: -self.numerator,
+            ..*self
+        }
+    }
+
+    /// `None` if the result is not a decimal fraction, as for all the operations.
+    pub fn add(&self, other: &Rational) -> Option<Rational> {
+        let numerator = self
+            .numerator
+            .checked_mul(other.denominator)?
+            .checked_add(other.numerator.checked_mul(self.denominator)?)?;
+        self.with(
+            other,
+            numerator,
+            self.denominator.checked_mul(other.denominator)?,
+        )
+    }
+
+    pub fn sub(&self, other: &Rational) -> Option<Rational> {
+        self.add(&other.neg())
+    }
+
+    pub fn mul(&self, other: &Rational) -> Option<Rational> {
+        self.with(
+            other,
+            self.numerator.checked_mul(other.numerator)?,
+            self.denominator.checked_mul(other.denominator)?,
+        )
+    }
+
+    pub fn div(&self, other: &Rational) -> Option<Rational> {
+        self.with(
+            other,
+            self.numerator.checked_mul(other.denominator)?,
+            self.denominator.checked_mul(other.numerator)?,
+        )
+    }
+
+    pub fn powi(&self, n: i64) -> Option<Rational> {
+        let exponent = u32::try_from(n.unsigned_abs()).ok()?;
+        let (numerator, denominator) = (
+            self.numerator.checked_pow(exponent)?,
+            self.denominator.checked_pow(exponent)?,
+        );
+
+        let res = match n < 0 {
+            true => Rational::new(denominator, numerator)?,
+            false => Rational::new(numerator, denominator)?,
+        };
+
+        Some(Rational {
+            form: self.form,
+            ..res
+        })
+    }
+
+    /// Decimal number if it is exact and no fraction form is set: `1/4` => `0.25`
+    pub fn to_numeric(self) -> NumericExpression {
+        self.with_unit(None)
+    }
+
+    /// Number of the unit, a decimal if it is exact: `1/3 km`
+    pub fn with_unit(self, unit: Option<Unit>) -> NumericExpression {
+        let n = self.to_decimal();
+        let exact = Rational::from_decimal(n);
+
+        if self.form.is_none()
+            && (exact.numerator, exact.denominator) == (self.numerator, self.denominator)
+        {
+            NumericExpression::with_unit(n, unit)
+        } else {
+            NumericExpression::Rational(self, unit)
+        }
+    }
+
+    /// Number in the locale format: `0.75`, `3/4`, `1 3/4`, the whole part of a mixed number is
+    /// followed by the separator
+    pub(crate) fn format(&self, number: impl Fn(&Decimal) -> String, separator: &str) -> String {
+        let fraction = |numerator: i128| {
+            format!(
+                "{}/{}",
+                number(&decimal(numerator)),
+                number(&decimal(self.denominator))
+            )
+        };
+
+        match self.form {
+            _ if self.denominator == 1 => number(&decimal(self.numerator)),
+            None => number(&self.to_decimal()),
+            Some(FractionForm::Fraction) => fraction(self.numerator),
+            Some(FractionForm::Mixed) => {
+                let whole = self.numerator / self.denominator;
+                let rest = (self.numerator % self.denominator).abs();
+
+                match whole {
+                    0 => fraction(self.numerator),
+                    _ => format!("{}{}{}", number(&decimal(whole)), separator, fraction(rest)),
+                }
+            }
+        }
+    }
+
+    /// Result of an operation, it has a fraction form if any of the operands has.
+    fn with(&self, other: &Rational, numerator: i128, denominator: i128) -> Option<Rational> {
+        Some(Rational {
+            form: self.form.or(other.form),
+            ..Rational::new(numerator, denominator)?
+        })
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.format(|n| n.to_string(), " "))
+    }
+}
+
+/// Exact operation with rational numbers, a division of numbers is rational: `1/3 * 3` => `1`
+///
+/// A single unit is kept, the same units are added or divided: `1/3 km * 3` => `1km`
+///
+/// `None` for other units, other values and results which are not decimal fractions, these are
+/// computed as decimals.
+pub(super) fn invoke_operator(
+    operator: char,
+    params: &[NumericExpression],
+) -> Option<Result<NumericExpression, ComputeError>> {
+    if operator != '/' && !params.iter().any(is_rational) {
+        return None;
+    }
+
+    let values = params.iter().map(rational).collect::<Option<Vec<_>>>()?;
+    let units = values.iter().map(|(_, u)| *u).collect::<Vec<_>>();
+    // temperatures have their own operators: 20 °C + 5 °C
+    if units
+        .iter()
+        .flatten()
+        .any(|u| matches!(u, Unit::Temperature(_)))
+    {
+        return None;
+    }
+
+    let unit = match (operator, &units[..]) {
+        (_, [u]) => *u,
+        ('+' | '-', [a, b]) if a == b || a.is_none() || b.is_none() => a.or(*b),
+        ('*', [a, b]) if a.is_none() || b.is_none() => a.or(*b),
+        ('/', [a, b]) if a == b => None,
+        // the number is divided, as for decimals: 1/3 km
+        ('/', [a, b]) if a.is_none() || b.is_none() => a.or(*b),
+        ('^', [None, None]) => None,
+        _ => return None,
+    };
+
+    let values = values.into_iter().map(|(r, _)| r).collect::<Vec<_>>();
+
+    let res = match (operator, &values[..]) {
+        ('+', [a]) => Some(*a),
+        ('-', [a]) => Some(a.neg()),
+        ('+', [a, b]) => a.add(b),
+        ('-', [a, b]) => a.sub(b),
+        ('*', [a, b]) => a.mul(b),
+        ('/', [_, b]) if b.is_zero() => return Some(Err(ComputeError::DivisionByZero)),
+        ('/', [a, b]) => a.div(b),
+        ('^', [a, b]) if b.denominator == 1 => a.powi(i64::try_from(b.numerator).ok()?),
+        _ => None,
+    }?;
+
+    Some(Ok(res.with_unit(unit)))
+}
+
+/// Number shown as a fraction, `None` for other functions: `fraction(0.75)` => `3/4`,
+/// `mixed(1.75)` => `1 3/4`
+pub(super) fn invoke_function(
+    name: &str,
+    params: &[NumericExpression],
+) -> Option<Result<NumericExpression, ComputeError>> {
+    let form = match name {
+        "fraction" => FractionForm::Fraction,
+        "mixed" => FractionForm::Mixed,
+        _ => return None,
+    };
+
+    let (r, unit) = match params {
+        [NumericExpression::Number(n)] => (Rational::approximate(*n), None),
+        [NumericExpression::NumberWithUnit(n, u)] => (Rational::approximate(*n), Some(*u)),
+        [NumericExpression::Rational(r, u)] => (*r, *u),
+        [NumericExpression::List(_)] => return None,
+        _ => {
+            return Some(Err(ComputeError::InvalidParametersForFunction(
+                name.to_owned(),
+                params.iter().join(", "),
+            )))
+        }
+    };
+
+    Some(Ok(NumericExpression::Rational(r.with_form(form), unit)))
+}
+
+/// Rational numbers as decimals, for the computations which are not exact: `sqrt(1/3)`
+pub(super) fn to_decimals(params: Vec<NumericExpression>) -> Vec<NumericExpression> {
+    params
+        .into_iter()
+        .map(|n| match n {
+            NumericExpression::Rational(r, u) => NumericExpression::with_unit(r.to_decimal(), u),
+            n => n,
+        })
+        .collect()
+}
+
+fn is_rational(n: &NumericExpression) -> bool {
+    matches!(n, NumericExpression::Rational(..))
+}
+
+fn rational(n: &NumericExpression) -> Option<(Rational, Option<Unit>)> {
+    match n {
+        NumericExpression::Number(v) => Some((Rational::from_decimal(*v), None)),
+        NumericExpression::NumberWithUnit(v, u) => Some((Rational::from_decimal(*v), Some(*u))),
+        NumericExpression::Rational(r, u) => Some((*r, *u)),
+        _ => None,
+    }
+}
+
+/// Integer within the range of a decimal, see [`MAX`].
+fn decimal(n: i128) -> Decimal {
+    Decimal::from_i128_with_scale(n, 0)
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a.max(1)
+}
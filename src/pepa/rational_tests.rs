@@ -0,0 +1,129 @@
+use crate::{Calculator, ComputeError, Decimal, Locale, Localize, Rational};
+
+fn compute(expression: &str) -> Result<String, ComputeError> {
+    let mut calculator = Calculator::default();
+    let statement = calculator.compute(expression).unwrap();
+
+    match &statement.result {
+        Some(r) => r.as_ref().map(|n| n.to_string()).map_err(|e| e.clone()),
+        None => panic!("{:?}: no result", expression),
+    }
+}
+
+#[test]
+fn test_exact_arithmetic() {
+    for (expression, expected) in [
+        ("1/3", "0.3333333333333333333333333333"),
+        ("1/3 * 3", "1"),
+        ("1/3 + 1/6", "0.5"),
+        ("2/3 - 1/3 - 1/3", "0"),
+        ("1 / (1/3)", "3"),
+        ("(1/3)^2 * 9", "1"),
+        ("(2/3)^-1", "1.5"),
+        ("-(1/3) * 3", "-1"),
+        ("10/4", "2.5"),
+        ("6/3", "2"),
+        ("(1/7, 2/7) * 7", "(1, 2)"),
+        // a single unit is kept
+        ("1/3 km * 3", "1km"),
+        ("1 km / 3 * 3", "1km"),
+        ("1/3 km + 2/3 km", "1km"),
+        ("(1/3 km) / (1/6 km)", "2"),
+        ("10 EUR / 3 * 3", "10.00EUR"),
+        // other operations are decimal
+        ("1/3 + i", "0.3333333333333333333333333333+i"),
+        ("[[1/3, 1]]", "[[0.3333333333333333333333333333, 1]]"),
+    ] {
+        assert_eq!(compute(expression).unwrap(), expected, "{}", expression);
+    }
+
+    assert!(matches!(
+        compute("(1/3) / 0"),
+        Err(ComputeError::DivisionByZero)
+    ));
+}
+
+#[test]
+fn test_fraction_forms() {
+    for (expression, expected) in [
+        ("0.75 in fraction", "3/4"),
+        ("1/3 in fraction", "1/3"),
+        ("(1/3)^2 in fraction", "1/9"),
+        ("0.3333333333333333333333333333 in fraction", "1/3"),
+        ("1.75 in mixed", "1 3/4"),
+        ("-1.75 in mixed", "-1 3/4"),
+        ("7/4 in mixed", "1 3/4"),
+        ("0.5 in mixed", "1/2"),
+        ("2 in fraction", "2"),
+        ("fraction(1/3) + 1", "4/3"),
+        ("mixed((0.5, 1.25))", "(1/2, 1 1/4)"),
+        ("0.3125 inch in fraction", "5/16 inches"),
+        ("(1.75 inch + 5/16 inch) in mixed", "2 1/16 inches"),
+        ("1/3 km + 1/3 km in fraction", "2/3km"),
+        // the inch after a number
+        ("5/16 in in fraction", "5/16 inches"),
+        ("5/16 in + 1 in in mixed", "1 5/16 inches"),
+        ("5 in in cm", "12.70cm"),
+        // mixed numbers in the input
+        ("2 1/2 + 1", "3.5"),
+        ("-2 1/2", "-2.5"),
+        ("3 * 2 1/2", "7.5"),
+        ("5 1/16 in in mixed", "5 1/16 inches"),
+        ("1 000 1/2", "1000.5"),
+        ("1 000/4", "250"),
+    ] {
+        assert_eq!(compute(expression).unwrap(), expected, "{}", expression);
+    }
+
+    assert!(matches!(
+        compute("fraction(i)"),
+        Err(ComputeError::InvalidParametersForFunction(..))
+    ));
+}
+
+#[test]
+fn test_values() {
+    let third = Rational::new(2, 6).unwrap();
+
+    assert_eq!((third.numerator(), third.denominator()), (1, 3));
+    assert_eq!(
+        third.mul(&Rational::new(3, 1).unwrap()),
+        Rational::new(1, 1)
+    );
+    assert_eq!(Rational::new(1, -2), Rational::new(-1, 2));
+    assert!(Rational::new(1, 0).is_none());
+    assert_eq!(
+        Rational::from_decimal(Decimal::new(75, 2)),
+        Rational::new(3, 4).unwrap()
+    );
+}
+
+#[test]
+fn test_format() {
+    let mut calculator = Calculator::default();
+    calculator.set_locale(Locale::Cs);
+    let statement = calculator.compute("12345,5 in mixed").unwrap();
+
+    assert_eq!(
+        statement
+            .result
+            .as_ref()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .localize(Locale::Cs),
+        "12 345 a 1/2"
+    );
+
+    let statement = calculator.compute("12 345 a 1/2 + 1").unwrap();
+    assert_eq!(
+        statement
+            .result
+            .as_ref()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .to_string(),
+        "12346.5"
+    );
+}
@@ -0,0 +1,97 @@
+use serde_json::json;
+
+use crate::units::{Length, Prefix, Time};
+use crate::{
+    Calculator, ComputeError, Currency, DateTimeValue, NumericExpression, ParserError, Unit,
+};
+
+#[test]
+fn test_units() {
+    for (unit, expected) in [
+        (
+            Unit::Length(Length::Meter(Some(Prefix::Kilo))),
+            json!({"length": {"meter": "kilo"}}),
+        ),
+        (
+            Unit::Time(Time::Second(None)),
+            json!({"time": {"second": null}}),
+        ),
+        (Unit::Time(Time::Minute), json!({"time": "minute"})),
+        (
+            Unit::Currency(Currency::CzechKoruna),
+            json!({"currency": "czech_koruna"}),
+        ),
+        (
+            Unit::TimeZone(crate::TimeZone(chrono_tz::Tz::Europe__Prague)),
+            json!({"time_zone": "Europe/Prague"}),
+        ),
+    ] {
+        let value = serde_json::to_value(unit).unwrap();
+        assert_eq!(value, expected);
+        assert_eq!(serde_json::from_value::<Unit>(value).unwrap(), unit);
+    }
+}
+
+#[test]
+fn test_values() {
+    let n = NumericExpression::NumberWithUnit(
+        "0.1000000000000000000000000001".parse().unwrap(),
+        Unit::Time(Time::Hour),
+    );
+    let value = serde_json::to_value(&n).unwrap();
+
+    assert_eq!(
+        value,
+        json!({"number_with_unit": ["0.1000000000000000000000000001", {"time": "hour"}]})
+    );
+    assert_eq!(
+        serde_json::from_value::<NumericExpression>(value).unwrap(),
+        n
+    );
+
+    let d = NumericExpression::DateTime(
+        DateTimeValue::parse("2026-12-24T15:00")
+            .unwrap()
+            .in_zone(chrono_tz::Tz::Europe__Prague, chrono::Utc::now())
+            .unwrap(),
+    );
+    let value = serde_json::to_value(&d).unwrap();
+
+    assert_eq!(
+        value,
+        json!({"date_time": "2026-12-24T15:00:00 Europe/Prague"})
+    );
+    assert_eq!(
+        serde_json::from_value::<NumericExpression>(value).unwrap(),
+        d
+    );
+}
+
+#[test]
+fn test_errors() {
+    let e = ComputeError::UnknownSymbol(String::from("XYZ"));
+    let value = serde_json::to_value(&e).unwrap();
+    assert_eq!(value, json!({"unknown_symbol": "XYZ"}));
+    assert!(matches!(
+        serde_json::from_value::<ComputeError>(value).unwrap(),
+        ComputeError::UnknownSymbol(s) if s == "XYZ"
+    ));
+
+    let value = serde_json::to_value(ParserError::EmptyExpression).unwrap();
+    assert_eq!(value, json!("empty_expression"));
+}
+
+#[test]
+fn test_statement() {
+    let mut calculator = Calculator::default();
+    let statement = calculator.compute("1.5 km + 500 m").unwrap();
+    let value = serde_json::to_value(statement).unwrap();
+
+    assert_eq!(value["request"], json!("1.5 km + 500 m"));
+    assert_eq!(value["expression"], json!({"Ok": "1.5km+500m"}));
+    assert_eq!(
+        value["result"],
+        json!({"Ok": {"number_with_unit": ["2000.0", {"length": {"meter": null}}]}})
+    );
+    assert_eq!(value["warnings"], json!([]));
+}
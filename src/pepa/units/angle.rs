@@ -5,13 +5,15 @@ use rust_decimal_macros::dec;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
-use crate::units::Prefix;
 use crate::units::{Abbreviations, Unit};
 use crate::utils::Pluralize;
-use crate::{make_abbreviations, make_abbreviations_with_prefixes, pluralize, string};
+use crate::{make_abbreviations, pluralize, string};
 
-#[derive(Debug, Clone, Eq, Copy, PartialEq, EnumIter, Hash)]
+#[derive(Debug, Clone, Eq, Copy, PartialEq, EnumIter, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Angle {
+    #[default]
     Radian,
     Degree,
     Gradian,
@@ -28,6 +30,16 @@ impl Angle {
         }
     }
 
+    /// Full name: `1 radian`, `5 degrees`
+    pub fn to_name_with_plural(self, v: &Decimal) -> String {
+        match self {
+            Angle::Radian => pluralize!("radian", v),
+            Angle::Degree => pluralize!("degree", v),
+            Angle::Gradian => pluralize!("gradian", v),
+            Angle::Turn => pluralize!("turn", v),
+        }
+    }
+
     pub fn abbreviations() -> Abbreviations {
         let mut case_sensitive = HashMap::new();
         let mut case_insensitive = HashMap::new();
@@ -45,7 +57,11 @@ impl Angle {
                         l.to_unit(),
                         // case insensitive
                         "radian",
-                        "radians"
+                        "radians",
+                        // Czech
+                        "radián",
+                        "radiány",
+                        "radiánů"
                     ));
                 }
                 Angle::Degree => {
@@ -59,7 +75,11 @@ impl Angle {
                         l.to_unit(),
                         // case insensitive
                         "degree",
-                        "degrees"
+                        "degrees",
+                        // Czech
+                        "stupeň",
+                        "stupně",
+                        "stupňů"
                     ));
                 }
                 Angle::Gradian => {
@@ -124,9 +144,3 @@ impl Angle {
         Unit::Angle(self)
     }
 }
-
-impl Default for Angle {
-    fn default() -> Self {
-        Angle::Radian
-    }
-}
@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use crate::units::{Abbreviations, Unit};
+use crate::utils::Pluralize;
+use crate::{make_abbreviations, pluralize, string};
+
+#[derive(Debug, Clone, Eq, Copy, PartialEq, EnumIter, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Currency {
+    #[default]
+    UsDollar,
+    Euro,
+    CzechKoruna,
+    BritishPound,
+    SwissFranc,
+    JapaneseYen,
+    PolishZloty,
+    HungarianForint,
+    SwedishKrona,
+    NorwegianKrone,
+    DanishKrone,
+    CanadianDollar,
+    AustralianDollar,
+    ChineseYuan,
+}
+
+impl Currency {
+    /// ISO 4217 alphabetic code
+    pub fn code(self) -> &'static str {
+        match self {
+            Currency::UsDollar => "USD",
+            Currency::Euro => "EUR",
+            Currency::CzechKoruna => "CZK",
+            Currency::BritishPound => "GBP",
+            Currency::SwissFranc => "CHF",
+            Currency::JapaneseYen => "JPY",
+            Currency::PolishZloty => "PLN",
+            Currency::HungarianForint => "HUF",
+            Currency::SwedishKrona => "SEK",
+            Currency::NorwegianKrone => "NOK",
+            Currency::DanishKrone => "DKK",
+            Currency::CanadianDollar => "CAD",
+            Currency::AustralianDollar => "AUD",
+            Currency::ChineseYuan => "CNY",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Currency> {
+        Currency::iter().find(|c| c.code() == code)
+    }
+
+    /// ISO 4217 minor unit - number of decimal places used for rounding of money
+    pub fn minor_units(self) -> u32 {
+        match self {
+            Currency::JapaneseYen => 0,
+            _ => 2,
+        }
+    }
+
+    pub fn to_string_with_plural(self, _: &Decimal) -> String {
+        string!(self.code())
+    }
+
+    /// Full name: `1 euro`, `5 US dollars`
+    pub fn to_name_with_plural(self, v: &Decimal) -> String {
+        match self {
+            Currency::UsDollar => pluralize!("US dollar", v),
+            Currency::Euro => pluralize!("euro", v),
+            Currency::CzechKoruna => pluralize!("Czech koruna", "Czech korunas", v),
+            Currency::BritishPound => pluralize!("British pound", v),
+            Currency::SwissFranc => pluralize!("Swiss franc", v),
+            Currency::JapaneseYen => string!("Japanese yen"),
+            Currency::PolishZloty => pluralize!("Polish zloty", "Polish zlotys", v),
+            Currency::HungarianForint => pluralize!("Hungarian forint", v),
+            Currency::SwedishKrona => pluralize!("Swedish krona", "Swedish kronor", v),
+            Currency::NorwegianKrone => pluralize!("Norwegian krone", "Norwegian kroner", v),
+            Currency::DanishKrone => pluralize!("Danish krone", "Danish kroner", v),
+            Currency::CanadianDollar => pluralize!("Canadian dollar", v),
+            Currency::AustralianDollar => pluralize!("Australian dollar", v),
+            Currency::ChineseYuan => string!("Chinese yuan"),
+        }
+    }
+
+    pub fn abbreviations() -> Abbreviations {
+        let mut case_sensitive = HashMap::new();
+        let mut case_insensitive = HashMap::new();
+
+        for c in Currency::iter() {
+            case_sensitive.extend(make_abbreviations!(
+                c.to_unit(),
+                // case sensitive
+                c.code()
+            ));
+
+            case_insensitive.extend(make_abbreviations!(
+                c.to_unit(),
+                // case insensitive
+                c.code().to_lowercase()
+            ));
+
+            match c {
+                Currency::UsDollar => {
+                    case_sensitive.extend(make_abbreviations!(
+                        c.to_unit(),
+                        // case sensitive
+                        "$",
+                        "US$"
+                    ));
+
+                    case_insensitive.extend(make_abbreviations!(
+                        c.to_unit(),
+                        // case insensitive
+                        "dollar",
+                        "dollars",
+                        "usdollar",
+                        "usdollars"
+                    ));
+                }
+                Currency::Euro => {
+                    case_sensitive.extend(make_abbreviations!(
+                        c.to_unit(),
+                        // case sensitive
+                        "€"
+                    ));
+
+                    case_insensitive.extend(make_abbreviations!(
+                        c.to_unit(),
+                        // case insensitive
+                        "euro",
+                        "euros"
+                    ));
+                }
+                Currency::CzechKoruna => {
+                    case_sensitive.extend(make_abbreviations!(
+                        c.to_unit(),
+                        // case sensitive
+                        "Kč"
+                    ));
+
+                    case_insensitive.extend(make_abbreviations!(
+                        c.to_unit(),
+                        // case insensitive
+                        "kč",
+                        "koruna",
+                        "koruny",
+                        "korun",
+                        "czechkoruna",
+                        "czechkorunas"
+                    ));
+                }
+                Currency::BritishPound => {
+                    case_sensitive.extend(make_abbreviations!(
+                        c.to_unit(),
+                        // case sensitive
+                        "£"
+                    ));
+
+                    case_insensitive.extend(make_abbreviations!(
+                        c.to_unit(),
+                        // case insensitive
+                        "sterling",
+                        "poundsterling",
+                        "poundssterling"
+                    ));
+                }
+                Currency::SwissFranc => {
+                    case_insensitive.extend(make_abbreviations!(
+                        c.to_unit(),
+                        // case insensitive
+                        "franc",
+                        "francs",
+                        "swissfranc",
+                        "swissfrancs"
+                    ));
+                }
+                Currency::JapaneseYen => {
+                    case_sensitive.extend(make_abbreviations!(
+                        c.to_unit(),
+                        // case sensitive
+                        "¥"
+                    ));
+
+                    case_insensitive.extend(make_abbreviations!(
+                        c.to_unit(),
+                        // case insensitive
+                        "yen"
+                    ));
+                }
+                Currency::PolishZloty => {
+                    case_sensitive.extend(make_abbreviations!(
+                        c.to_unit(),
+                        // case sensitive
+                        "zł"
+                    ));
+
+                    case_insensitive.extend(make_abbreviations!(
+                        c.to_unit(),
+                        // case insensitive
+                        "zloty",
+                        "zlotys"
+                    ));
+                }
+                Currency::HungarianForint => {
+                    case_sensitive.extend(make_abbreviations!(
+                        c.to_unit(),
+                        // case sensitive
+                        "Ft"
+                    ));
+
+                    case_insensitive.extend(make_abbreviations!(
+                        c.to_unit(),
+                        // case insensitive
+                        "forint",
+                        "forints"
+                    ));
+                }
+                Currency::ChineseYuan => {
+                    case_insensitive.extend(make_abbreviations!(
+                        c.to_unit(),
+                        // case insensitive
+                        "yuan",
+                        "renminbi"
+                    ));
+                }
+                Currency::SwedishKrona
+                | Currency::NorwegianKrone
+                | Currency::DanishKrone
+                | Currency::CanadianDollar
+                | Currency::AustralianDollar => {}
+            };
+        }
+
+        Abbreviations {
+            case_sensitive,
+            case_insensitive,
+        }
+    }
+
+    pub fn to_unit(self) -> Unit {
+        Unit::Currency(self)
+    }
+}
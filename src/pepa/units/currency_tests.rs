@@ -0,0 +1,77 @@
+use chrono::{TimeZone, Utc};
+use rust_decimal_macros::dec;
+
+use crate::exchange_rates::ExchangeRates;
+use crate::units::{Currency, Unit};
+use crate::Calculator;
+
+fn calculator() -> Calculator {
+    let mut calculator = Calculator::default();
+    calculator.set_exchange_rates(
+        ExchangeRates::new(
+            Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap(),
+            Currency::Euro,
+        )
+        .with_rate(Currency::CzechKoruna, dec!(24.5))
+        .with_rate(Currency::UsDollar, dec!(1.25))
+        .with_rate(Currency::JapaneseYen, dec!(160)),
+    );
+
+    calculator
+}
+
+#[test]
+fn test_currency() {
+    let tests = [
+        ("10 EUR in CZK", "245.00CZK"),
+        ("10 € to Kč", "245.00CZK"),
+        ("10$ in euro", "8.00EUR"),
+        ("$10", "10.00USD"),
+        ("100 CZK + 1 EUR", "5.08EUR"),
+        ("1 EUR + 100 CZK", "124.50CZK"),
+        ("1 EUR in JPY", "160JPY"),
+        ("1/3 USD", "0.33USD"),
+        ("2 USD / 3", "0.67USD"),
+        ("5 USD + 5 $", "10.00USD"),
+    ];
+
+    for test in tests {
+        let mut calculator = calculator();
+        let statement = calculator.compute(test.0).unwrap();
+        let result = statement.result.as_ref().unwrap().as_ref().unwrap();
+
+        assert_eq!(result.to_string(), test.1, "{}", test.0);
+    }
+}
+
+#[test]
+fn test_exchange_rates_timestamp() {
+    let mut calculator = calculator();
+
+    let statement = calculator.compute("10 EUR in CZK").unwrap();
+    assert_eq!(
+        statement.exchange_rates_timestamp,
+        Some(Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap())
+    );
+
+    let statement = calculator.compute("10 EUR + 5 EUR").unwrap();
+    assert_eq!(statement.exchange_rates_timestamp, None);
+
+    let statement = calculator.compute("10 km").unwrap();
+    assert_eq!(statement.exchange_rates_timestamp, None);
+}
+
+#[test]
+fn test_currency_without_rates() {
+    let mut calculator = Calculator::default();
+
+    let statement = calculator.compute("10 EUR in CZK").unwrap();
+    assert!(matches!(statement.result, Some(Err(_))));
+
+    let statement = calculator.compute("10 EUR + 5 €").unwrap();
+    let result = statement.result.as_ref().unwrap().as_ref().unwrap();
+    assert_eq!(
+        result.values(),
+        vec![(dec!(15), Some(Unit::Currency(Currency::Euro)))]
+    );
+}
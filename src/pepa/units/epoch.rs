@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+use rust_decimal_macros::dec;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use crate::units::{Abbreviations, Unit};
+use crate::{make_abbreviations, string, Decimal};
+
+/// Number counted from an epoch, converted to and from date values.
+///
+/// Reference unit is the Unix time, seconds since 1970-01-01T00:00:00Z.
+#[derive(Debug, Clone, Eq, Copy, PartialEq, EnumIter, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Epoch {
+    #[default]
+    Unix,
+    UnixMillis,
+    UnixMicros,
+    UnixNanos,
+    /// days since 1899-12-30, the Excel and LibreOffice serial date
+    Excel,
+    /// seconds since 1900-01-01T00:00:00Z
+    Ntp,
+    /// conversion target only: the number as a date
+    Date,
+}
+
+impl Epoch {
+    pub fn abbreviations() -> Abbreviations {
+        let case_sensitive = HashMap::new();
+        let mut case_insensitive = HashMap::new();
+
+        for e in Epoch::iter() {
+            match e {
+                Epoch::Unix => {
+                    case_insensitive.extend(make_abbreviations!(
+                        e.to_unit(),
+                        // case insensitive
+                        "unix",
+                        "epoch",
+                        "unixtime",
+                        "unixs"
+                    ));
+                }
+                Epoch::UnixMillis => {
+                    case_insensitive.extend(make_abbreviations!(
+                        e.to_unit(),
+                        // case insensitive
+                        "unixms",
+                        "unixmillis"
+                    ));
+                }
+                Epoch::UnixMicros => {
+                    case_insensitive.extend(make_abbreviations!(
+                        e.to_unit(),
+                        // case insensitive
+                        "unixus",
+                        "unixµs",
+                        "unixmicros"
+                    ));
+                }
+                Epoch::UnixNanos => {
+                    case_insensitive.extend(make_abbreviations!(
+                        e.to_unit(),
+                        // case insensitive
+                        "unixns",
+                        "unixnanos"
+                    ));
+                }
+                Epoch::Excel => {
+                    case_insensitive.extend(make_abbreviations!(
+                        e.to_unit(),
+                        // case insensitive
+                        "excel",
+                        "serialdate"
+                    ));
+                }
+                Epoch::Ntp => {
+                    case_insensitive.extend(make_abbreviations!(
+                        e.to_unit(),
+                        // case insensitive
+                        "ntp"
+                    ));
+                }
+                Epoch::Date => {
+                    case_insensitive.extend(make_abbreviations!(
+                        e.to_unit(),
+                        // case insensitive
+                        "date",
+                        "datetime",
+                        "instant"
+                    ));
+                }
+            };
+        }
+
+        Abbreviations {
+            case_sensitive,
+            case_insensitive,
+        }
+    }
+
+    pub fn to_reference_unit(self, v: Decimal) -> Option<Decimal> {
+        match self {
+            Epoch::Unix => Some(v),
+            Epoch::UnixMillis => Some(v / dec!(1_000)),
+            Epoch::UnixMicros => Some(v / dec!(1_000_000)),
+            Epoch::UnixNanos => Some(v / dec!(1_000_000_000)),
+            Epoch::Excel => Some((v - dec!(25569)) * dec!(86400)),
+            Epoch::Ntp => Some(v - dec!(2208988800)),
+            Epoch::Date => None,
+        }
+    }
+
+    pub fn from_reference_unit(self, v: Decimal) -> Option<Decimal> {
+        match self {
+            Epoch::Unix => Some(v),
+            Epoch::UnixMillis => Some(v * dec!(1_000)),
+            Epoch::UnixMicros => Some(v * dec!(1_000_000)),
+            Epoch::UnixNanos => Some(v * dec!(1_000_000_000)),
+            Epoch::Excel => Some(v / dec!(86400) + dec!(25569)),
+            Epoch::Ntp => Some(v + dec!(2208988800)),
+            Epoch::Date => None,
+        }
+    }
+
+    /// Epoch numbers are shown as plain numbers.
+    pub fn to_string_with_plural(self, _: &Decimal) -> String {
+        string!("")
+    }
+
+    pub fn to_unit(self) -> Unit {
+        Unit::Epoch(self)
+    }
+}
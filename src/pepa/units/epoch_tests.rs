@@ -0,0 +1,124 @@
+use chrono::{TimeZone, Utc};
+use rust_decimal_macros::dec;
+
+use crate::units::{Epoch, Unit};
+use crate::{Calculator, Decimal};
+
+fn compute(expression: &str) -> String {
+    let mut calculator = Calculator::default();
+    let statement = calculator.compute(expression).unwrap();
+
+    match &statement.result {
+        Some(Ok(n)) => n.to_string(),
+        r => panic!("{:?}: {:?}", expression, r),
+    }
+}
+
+#[test]
+fn test_names() {
+    for (name, epoch) in [
+        ("unix", Epoch::Unix),
+        ("Epoch", Epoch::Unix),
+        ("unixms", Epoch::UnixMillis),
+        ("unixns", Epoch::UnixNanos),
+        ("excel", Epoch::Excel),
+        ("NTP", Epoch::Ntp),
+        ("date", Epoch::Date),
+    ] {
+        assert_eq!(
+            Unit::from_string(name),
+            vec![Unit::Epoch(epoch)],
+            "{}",
+            name
+        );
+    }
+}
+
+#[test]
+fn test_to_date() {
+    let tests = [
+        ("1700000000 as date", "2023-11-14T22:13:20 UTC"),
+        ("1700000000 to date", "2023-11-14T22:13:20 UTC"),
+        (
+            "(1700000000 + 1 / 4) as date",
+            "2023-11-14T22:13:20.250 UTC",
+        ),
+        ("1700000000000 unixms as date", "2023-11-14T22:13:20 UTC"),
+        ("1700000000000000 unixus as date", "2023-11-14T22:13:20 UTC"),
+        (
+            "1700000000000000000 unixns as date",
+            "2023-11-14T22:13:20 UTC",
+        ),
+        ("1700000000000 ms as date", "2023-11-14T22:13:20 UTC"),
+        ("45000 excel as date", "2023-03-15T00:00:00 UTC"),
+        ("3908793600 ntp as date", "2023-11-12T16:00:00 UTC"),
+        (
+            "1700000000 as date in Prague",
+            "2023-11-14T23:13:20 Europe/Prague",
+        ),
+        // before the epoch
+        ("-1700000000 as date", "1916-02-18T01:46:40 UTC"),
+        ("-1700000000000 unixms as date", "1916-02-18T01:46:40 UTC"),
+        ("-0.5 as date", "1969-12-31T23:59:59.500 UTC"),
+    ];
+
+    for (expression, expected) in tests {
+        assert_eq!(compute(expression), expected, "{}", expression);
+    }
+}
+
+#[test]
+fn test_from_date() {
+    let tests = [
+        ("2026-10-18T12:00Z in unix", "1792324800"),
+        ("2026-10-18T12:00Z as unixms", "1792324800000"),
+        ("2026-10-18T12:00Z as unixns", "1792324800000000000"),
+        ("2026-10-18T14:00 Prague in unix", "1792324800"),
+        // without a zone taken as UTC
+        ("2026-10-18T12:00 in unix", "1792324800"),
+        ("2026-10-18 in excel", "46313"),
+        ("2026-10-18T18:00 in excel", "46313.75"),
+        ("2026-10-18 in ntp", "4001270400"),
+        ("1792324800000 unixms in unix", "1792324800"),
+        ("46313 excel in unix", "1792281600"),
+        ("2026-10-18T12:00Z", "2026-10-18T12:00:00 UTC"),
+        ("1916-02-18T01:46:40Z in unix", "-1700000000"),
+    ];
+
+    for (expression, expected) in tests {
+        assert_eq!(compute(expression), expected, "{}", expression);
+    }
+}
+
+#[test]
+fn test_timestamp() {
+    let mut calculator = Calculator::default();
+    calculator.set_now(Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap());
+
+    let mut result = |expression: &str| {
+        let statement = calculator.compute(expression).unwrap();
+        statement.result.as_ref().unwrap().as_ref().unwrap().to_string()
+    };
+
+    assert_eq!(result("timestamp()"), "2026-10-18T12:00:00 UTC");
+    assert_eq!(
+        result("timestamp() in unix").parse::<Decimal>().unwrap(),
+        dec!(1792324800)
+    );
+    assert_eq!(
+        result("timestamp() + 1 s in unixms").parse::<Decimal>().unwrap(),
+        dec!(1792324801000)
+    );
+}
+
+#[test]
+fn test_errors() {
+    for expression in ["15:00 in unix", "5 km as date", "5 excel in km"] {
+        let mut calculator = Calculator::default();
+        let statement = calculator.compute(expression).unwrap();
+
+        if let Some(Ok(n)) = &statement.result {
+            panic!("{:?} = {} expected to fail", expression, n);
+        }
+    }
+}
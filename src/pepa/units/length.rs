@@ -10,6 +10,8 @@ use crate::utils::Pluralize;
 use crate::{make_abbreviations, make_abbreviations_with_prefixes, pluralize, string};
 
 #[derive(Debug, Clone, Eq, Copy, PartialEq, EnumIter, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Length {
     Meter(Option<Prefix>),
 
@@ -59,6 +61,32 @@ impl Length {
         }
     }
 
+    /// Full name: `1 metre`, `5 kilometres`
+    pub fn to_name_with_plural(self, v: &Decimal) -> String {
+        match self {
+            Length::Meter(None) => pluralize!("metre", v),
+            Length::Meter(Some(p)) => string!(p.name()) + &pluralize!("metre", v),
+            Length::AstronomicalUnit => pluralize!("astronomical unit", v),
+            Length::LightYear => pluralize!("light year", v),
+            Length::Parsec(None) => pluralize!("parsec", v),
+            Length::Parsec(Some(p)) => string!(p.name()) + &pluralize!("parsec", v),
+            Length::Thou => string!("thou"),
+            Length::Barleycorn => pluralize!("barleycorn", v),
+            Length::Inch => pluralize!("inch", "inches", v),
+            Length::Foot => pluralize!("foot", "feet", v),
+            Length::Yard => pluralize!("yard", v),
+            Length::Mile => pluralize!("mile", v),
+            Length::Pole => pluralize!("pole", v),
+            Length::Rod => pluralize!("rod", v),
+            Length::Furlong => pluralize!("furlong", v),
+            Length::Chain => pluralize!("chain", v),
+            Length::Fathom => pluralize!("fathom", v),
+            Length::NauticalMile => pluralize!("nautical mile", v),
+            Length::League => pluralize!("league", v),
+            Length::NauticalLeague => pluralize!("nautical league", v),
+        }
+    }
+
     pub fn abbreviations() -> Abbreviations {
         let mut case_sensitive = HashMap::new();
         let mut case_insensitive = HashMap::new();
@@ -78,7 +106,11 @@ impl Length {
                         "meter",
                         "metre",
                         "meters",
-                        "metres"
+                        "metres",
+                        // Czech
+                        "metr",
+                        "metry",
+                        "metrů"
                     ));
                 }
                 Length::Parsec(_) => {
@@ -113,7 +145,11 @@ impl Length {
                         l.to_unit(),
                         // case insensitive
                         "lightyear",
-                        "lightyears"
+                        "lightyears",
+                        // Czech
+                        "světelnýrok",
+                        "světelnéroky",
+                        "světelnýchlet"
                     ));
                 }
                 Length::Thou => {
@@ -139,18 +175,22 @@ impl Length {
                     ));
                 }
                 Length::Inch => {
-                    // conflict wit "in" (conversion) keyword
-                    // case_sensitive.extend(make_abbreviations!(
-                    //     l.to_unit(),
-                    //     // case sensitive
-                    //     "in"
-                    // ));
+                    // the conversion keyword unless it follows a number: 5/16 in
+                    case_sensitive.extend(make_abbreviations!(
+                        l.to_unit(),
+                        // case sensitive
+                        "in"
+                    ));
 
                     case_insensitive.extend(make_abbreviations!(
                         l.to_unit(),
                         // case insensitive
                         "inch",
-                        "inches"
+                        "inches",
+                        // Czech
+                        "palec",
+                        "palce",
+                        "palců"
                     ));
                 }
                 Length::Foot => {
@@ -166,7 +206,10 @@ impl Length {
                         "foot",
                         "feet",
                         "feets",
-                        "foots"
+                        "foots",
+                        // Czech
+                        "stopa",
+                        "stopy"
                     ));
                 }
                 Length::Yard => {
@@ -194,7 +237,9 @@ impl Length {
                         l.to_unit(),
                         // case insensitive
                         "mile",
-                        "miles"
+                        "miles",
+                        // Czech
+                        "míle"
                     ));
                 }
                 Length::League => {
@@ -272,7 +317,9 @@ impl Length {
                         l.to_unit(),
                         // case insensitive
                         "nauticalmile",
-                        "nauticalmiles"
+                        "nauticalmiles",
+                        // Czech
+                        "námořnímíle"
                     ));
                 }
 
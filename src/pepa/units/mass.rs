@@ -11,6 +11,8 @@ use crate::utils::Pluralize;
 use crate::{make_abbreviations, make_abbreviations_with_prefixes, pluralize, string};
 
 #[derive(Debug, Clone, Eq, Copy, PartialEq, EnumIter, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Mass {
     Gram(Option<Prefix>),
     Tonne(Option<Prefix>),
@@ -44,6 +46,25 @@ impl Mass {
         }
     }
 
+    /// Full name: `1 gram`, `5 kilograms`
+    pub fn to_name_with_plural(self, v: &Decimal) -> String {
+        match self {
+            Mass::Gram(None) => pluralize!("gram", v),
+            Mass::Gram(Some(p)) => string!(p.name()) + &pluralize!("gram", v),
+            Mass::Tonne(None) => pluralize!("tonne", v),
+            Mass::Tonne(Some(p)) => string!(p.name()) + &pluralize!("tonne", v),
+            Mass::DekaGram => pluralize!("dekagram", v),
+            Mass::LongTon => pluralize!("long ton", v),
+            Mass::ShortTon => pluralize!("short ton", v),
+            Mass::Pound => pluralize!("pound", v),
+            Mass::Ounce => pluralize!("ounce", v),
+            Mass::Slug => pluralize!("slug", v),
+            Mass::Grain => pluralize!("grain", v),
+            Mass::TroyPound => pluralize!("troy pound", v),
+            Mass::TroyOunce => pluralize!("troy ounce", v),
+        }
+    }
+
     pub fn abbreviations() -> Abbreviations {
         let mut case_sensitive = HashMap::new();
         let mut case_insensitive = HashMap::new();
@@ -65,7 +86,10 @@ impl Mass {
                         "gramme",
                         "grammes",
                         "gramm",
-                        "gramms"
+                        "gramms",
+                        // Czech
+                        "gramy",
+                        "gramů"
                     ));
                 }
                 Mass::Tonne(_) => {
@@ -79,7 +103,11 @@ impl Mass {
                         Mass::Tonne,
                         // case insensitive
                         "tonne",
-                        "tonnes"
+                        "tonnes",
+                        // Czech
+                        "tuna",
+                        "tuny",
+                        "tun"
                     ));
                 }
                 Mass::DekaGram => {
@@ -132,7 +160,10 @@ impl Mass {
                         "libra",
                         "libras",
                         "libre",
-                        "libres"
+                        "libres",
+                        // Czech
+                        "libry",
+                        "liber"
                     ));
                 }
                 Mass::Ounce => {
@@ -146,7 +177,9 @@ impl Mass {
                         l.to_unit(),
                         // case insensitive
                         "ounce",
-                        "ounces"
+                        "ounces",
+                        // Czech
+                        "unce"
                     ));
                 }
                 Mass::Slug => {
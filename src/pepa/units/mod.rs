@@ -1,14 +1,27 @@
+pub use self::angle::Angle;
+pub use self::currency::Currency;
+pub use self::epoch::Epoch;
 pub use self::length::Length;
 pub use self::mass::Mass;
 pub use self::prefix::Prefix;
+pub use self::speed::Speed;
 pub use self::temperature::Temperature;
 pub use self::time::Time;
+pub use self::time_zone::TimeZone;
 pub use self::unit::Abbreviations;
 pub use self::unit::Unit;
+pub use self::unit::UnitFamily;
+pub use self::unit::UnitStyle;
 
 mod angle;
 #[cfg(test)]
 mod angle_tests;
+mod currency;
+#[cfg(test)]
+mod currency_tests;
+mod epoch;
+#[cfg(test)]
+mod epoch_tests;
 mod length;
 #[cfg(test)]
 mod length_tests;
@@ -18,12 +31,18 @@ mod mass_tests;
 mod prefix;
 #[cfg(test)]
 mod prefix_tests;
+mod speed;
+#[cfg(test)]
+mod speed_tests;
 mod temperature;
 #[cfg(test)]
 mod temperature_tests;
 mod time;
 #[cfg(test)]
 mod time_tests;
+mod time_zone;
+#[cfg(test)]
+mod time_zone_tests;
 mod unit;
 #[cfg(test)]
 mod units_tests;
@@ -7,6 +7,8 @@ use strum_macros::EnumIter;
 use crate::Decimal;
 
 #[derive(Debug, Clone, Eq, Copy, PartialEq, EnumIter, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Prefix {
     Quetta,
     Ronna,
@@ -65,6 +67,11 @@ impl Prefix {
         }
     }
 
+    /// Full name: `kilo`
+    pub fn name(self) -> &'static str {
+        self.abbreviations()[0]
+    }
+
     pub fn abbreviations(self) -> Vec<&'static str> {
         match self {
             Prefix::Quetta => vec!["quetta", "Q"],
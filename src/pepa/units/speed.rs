@@ -0,0 +1,56 @@
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+
+use crate::units::{Abbreviations, Length, Time, Unit};
+
+/// Length per time, the result of their division: `150 km / 3 h` => `50km/h`
+#[derive(Debug, Clone, Eq, Copy, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Speed {
+    pub length: Length,
+    pub time: Time,
+}
+
+impl Speed {
+    /// `None` for the times which are not fixed durations: workdays, clock formats
+    pub fn new(length: Length, time: Time) -> Option<Speed> {
+        match time {
+            Time::Workday | Time::Clock | Time::Iso8601 => None,
+            _ => Some(Speed { length, time }),
+        }
+    }
+
+    pub fn to_string_with_plural(self, v: &Decimal) -> String {
+        format!(
+            "{}/{}",
+            self.length.to_string_with_plural(v),
+            self.time.to_string_with_plural(&Decimal::ONE)
+        )
+    }
+
+    /// Full name: `50 kilometres per hour`
+    pub fn to_name_with_plural(self, v: &Decimal) -> String {
+        format!(
+            "{} per {}",
+            self.length.to_name_with_plural(v),
+            self.time.to_name_with_plural(&Decimal::ONE)
+        )
+    }
+
+    /// Speeds are not parsed yet.
+    pub fn abbreviations() -> Abbreviations {
+        Abbreviations {
+            case_sensitive: HashMap::new(),
+            case_insensitive: HashMap::new(),
+        }
+    }
+
+    pub fn reference_unit_multiplier(self) -> Decimal {
+        self.length.reference_unit_multiplier() / self.time.reference_unit_multiplier()
+    }
+
+    pub fn to_unit(self) -> Unit {
+        Unit::Speed(self)
+    }
+}
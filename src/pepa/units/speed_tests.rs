@@ -0,0 +1,26 @@
+use rust_decimal_macros::dec;
+
+use crate::units::unit::test_units;
+use crate::units::{Length, Prefix, Speed, Time, Unit};
+
+#[test]
+fn test_speed() {
+    let kmh = Unit::Speed(Speed {
+        length: Length::Meter(Some(Prefix::Kilo)),
+        time: Time::Hour,
+    });
+
+    test_units("150 km / 3 h", &[(dec!(50), Some(kmh))]);
+    test_units(
+        "(150 km / 3 h) * 30 min",
+        &[(
+            dec!(25),
+            Some(Unit::Length(Length::Meter(Some(Prefix::Kilo)))),
+        )],
+    );
+    test_units(
+        "100 km / (150 km / 3 h)",
+        &[(dec!(2), Some(Unit::Time(Time::Hour)))],
+    );
+    test_units("10 metres / 1 s + 150 km / 3 h", &[(dec!(86), Some(kmh))]);
+}
@@ -5,14 +5,20 @@ use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
 use crate::units::{Abbreviations, Unit};
-use crate::{make_abbreviations, string, Decimal};
+use crate::utils::Pluralize;
+use crate::{make_abbreviations, pluralize, string, Decimal};
 
 #[derive(Debug, Clone, Eq, Copy, PartialEq, EnumIter, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Temperature {
     DegreesCelsius,
     DegreesFahrenheit,
     #[default]
     Kelvin,
+    /// difference of temperatures: `20 °C - 15 °C` => `5 Δ°C`
+    DegreesCelsiusDifference,
+    DegreesFahrenheitDifference,
 }
 
 impl Temperature {
@@ -29,7 +35,11 @@ impl Temperature {
                         "°c",
                         "celsius",
                         "degreescelsius",
-                        "degreecelsius"
+                        "degreecelsius",
+                        // Czech
+                        "stupeňcelsia",
+                        "stupněcelsia",
+                        "stupňůcelsia"
                     ));
                 }
                 Temperature::DegreesFahrenheit => {
@@ -42,7 +52,11 @@ impl Temperature {
                         "degreesfahrenheit",
                         "degreefahrenheit",
                         "degreesfahrenheits",
-                        "degreefahrenheits"
+                        "degreefahrenheits",
+                        // Czech
+                        "stupeňfahrenheita",
+                        "stupněfahrenheita",
+                        "stupňůfahrenheita"
                     ));
                 }
                 Temperature::Kelvin => {
@@ -59,6 +73,28 @@ impl Temperature {
                         "kelvins"
                     ));
                 }
+                Temperature::DegreesCelsiusDifference => {
+                    case_insensitive.extend(make_abbreviations!(
+                        t.to_unit(),
+                        // case insensitive
+                        "δ°c",
+                        "deltacelsius",
+                        "celsiusdifference",
+                        "degreescelsiusdifference",
+                        "degreecelsiusdifference"
+                    ));
+                }
+                Temperature::DegreesFahrenheitDifference => {
+                    case_insensitive.extend(make_abbreviations!(
+                        t.to_unit(),
+                        // case insensitive
+                        "δ°f",
+                        "deltafahrenheit",
+                        "fahrenheitdifference",
+                        "degreesfahrenheitdifference",
+                        "degreefahrenheitdifference"
+                    ));
+                }
             };
         }
 
@@ -68,31 +104,90 @@ impl Temperature {
         }
     }
 
+    /// Absolute temperature on a scale with an offset, kelvins are both absolute and a difference.
+    pub fn is_absolute(self) -> bool {
+        matches!(
+            self,
+            Temperature::DegreesCelsius | Temperature::DegreesFahrenheit
+        )
+    }
+
+    pub fn is_difference(self) -> bool {
+        matches!(
+            self,
+            Temperature::DegreesCelsiusDifference | Temperature::DegreesFahrenheitDifference
+        )
+    }
+
+    /// Unit of a difference of the temperatures: `°C` => `Δ°C`, `K` => `K`
+    pub fn difference(self) -> Temperature {
+        match self {
+            Temperature::DegreesCelsius | Temperature::DegreesCelsiusDifference => {
+                Temperature::DegreesCelsiusDifference
+            }
+            Temperature::DegreesFahrenheit | Temperature::DegreesFahrenheitDifference => {
+                Temperature::DegreesFahrenheitDifference
+            }
+            Temperature::Kelvin => Temperature::Kelvin,
+        }
+    }
+
     pub fn to_reference_unit(self, v: Decimal) -> Decimal {
         match self {
             Temperature::DegreesCelsius => v + dec!(273.15),
             Temperature::DegreesFahrenheit => {
                 dec!(273.15) + (v - dec!(32.0)) * (dec!(5.0) / dec!(9.0))
             }
-            Temperature::Kelvin => v,
+            Temperature::Kelvin | Temperature::DegreesCelsiusDifference => v,
+            Temperature::DegreesFahrenheitDifference => v * (dec!(5.0) / dec!(9.0)),
         }
     }
 
+    #[allow(clippy::wrong_self_convention)]
     pub fn from_reference_unit(self, v: Decimal) -> Decimal {
         match self {
             Temperature::DegreesCelsius => v - dec!(273.15),
             Temperature::DegreesFahrenheit => {
                 (v - dec!(273.15)) * (dec!(9.0) / dec!(5.0)) + dec!(32.0)
             }
-            Temperature::Kelvin => v,
+            Temperature::Kelvin | Temperature::DegreesCelsiusDifference => v,
+            Temperature::DegreesFahrenheitDifference => v * (dec!(9.0) / dec!(5.0)),
         }
     }
 
+    /// Absolute temperatures are converted with the offsets of the scales, differences only by
+    /// the size of a degree: `0 °C` => `32 °F`, `10 Δ°C` => `18 Δ°F`
+    pub fn conversion(self, v: Decimal, to: Temperature) -> Option<Decimal> {
+        if (self.is_absolute() && to.is_difference()) || (self.is_difference() && to.is_absolute())
+        {
+            return None;
+        }
+
+        Some(to.from_reference_unit(self.to_reference_unit(v)))
+    }
+
     pub fn to_string_with_plural(self, _: &Decimal) -> String {
         match self {
             Temperature::DegreesCelsius => string!("°C"),
             Temperature::DegreesFahrenheit => string!("°F"),
             Temperature::Kelvin => string!("K"),
+            Temperature::DegreesCelsiusDifference => string!("Δ°C"),
+            Temperature::DegreesFahrenheitDifference => string!("Δ°F"),
+        }
+    }
+
+    /// Full name: `1 degree Celsius`, `5 kelvins`
+    pub fn to_name_with_plural(self, v: &Decimal) -> String {
+        match self {
+            Temperature::DegreesCelsius => pluralize!("degree", v) + " Celsius",
+            Temperature::DegreesFahrenheit => pluralize!("degree", v) + " Fahrenheit",
+            Temperature::Kelvin => pluralize!("kelvin", v),
+            Temperature::DegreesCelsiusDifference => {
+                pluralize!("degree", v) + " Celsius difference"
+            }
+            Temperature::DegreesFahrenheitDifference => {
+                pluralize!("degree", v) + " Fahrenheit difference"
+            }
         }
     }
 
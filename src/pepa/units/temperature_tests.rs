@@ -2,6 +2,17 @@ use rust_decimal_macros::dec;
 
 use crate::units::unit::test_units;
 use crate::units::{Temperature, Unit};
+use crate::{Calculator, ComputeError};
+
+fn compute_error(expression: &str) -> ComputeError {
+    let mut calculator = Calculator::default();
+    let statement = calculator.compute(expression).unwrap();
+
+    match &statement.result {
+        Some(Err(e)) => e.clone(),
+        r => panic!("{:?} = {:?} expected to fail", expression, r),
+    }
+}
 
 #[test]
 fn test_celsius() {
@@ -11,7 +22,12 @@ fn test_celsius() {
     test_units("celsius((33 + 3) kelvins)", &[(dec!(-237.15), unit)]);
     test_units("celsius(33 + 3 kelvins)", &[(dec!(-237.15), unit)]);
     test_units("celsius(55)", &[(dec!(55.0), unit)]);
-    test_units("pow(55 celsius, 2)", &[(dec!(3025.0), unit)]);
+    test_units("pow(55 celsius, 1)", &[(dec!(55.0), unit)]);
+    // there are no squared temperatures
+    assert!(matches!(
+        compute_error("pow(55 celsius, 2)"),
+        ComputeError::InvalidUnitForFunction(..)
+    ));
     test_units("55 celsius", &[(dec!(55.0), unit)]);
     test_units("celsius 55", &[(dec!(55.0), unit)]);
     test_units("55°C", &[(dec!(55.0), unit)]);
@@ -20,27 +36,58 @@ fn test_celsius() {
     test_units("55 degrees celsius", &[(dec!(55.0), unit)]);
     test_units("10 * 5 celsius", &[(dec!(50.0), unit)]);
     test_units("10 * celsius(5 + 5)", &[(dec!(100.0), unit)]);
-    test_units("15 fahrenheits + 3 celsius", &[(dec!(-6.44), unit)]);
-    test_units(
+    test_units("3 celsius + 9 Δ°F", &[(dec!(8), unit)]);
+    test_units("2 * 10 celsius + 3 kelvins + 9 Δ°F", &[(dec!(28), unit)]);
+
+    // absolute temperatures are not added
+    for expression in [
+        "15 fahrenheits + 3 celsius",
         "15 fahrenheits + 3 kelvins + 2 * 20 celsius",
-        &[(dec!(33.56), unit)],
-    );
+    ] {
+        assert!(
+            matches!(
+                compute_error(expression),
+                ComputeError::InvalidTemperatureOperation(_)
+            ),
+            "{}",
+            expression
+        );
+    }
 }
 
 #[test]
 fn test_kelvin() {
     let unit = Some(Unit::Temperature(Temperature::Kelvin));
 
-    test_units("kelvin(pow(55 celsius, 2))", &[(dec!(3298.15), unit)]);
+    test_units("kelvin(pow(55 celsius, 1))", &[(dec!(328.15), unit)]);
+    assert!(matches!(
+        compute_error("kelvin(pow(55 celsius, 2))"),
+        ComputeError::InvalidUnitForFunction(..)
+    ));
 }
 
 #[test]
 fn test_fahrenheit() {
     let unit = Some(Unit::Temperature(Temperature::DegreesFahrenheit));
 
-    test_units("10 celsius + pow(5,2) Fahrenheit", &[(dec!(75), unit)]);
-    test_units("10 celsius + (pow(5,2) Fahrenheit)", &[(dec!(75), unit)]);
-    test_units("10 celsius + Fahrenheit(pow(5,2)) ", &[(dec!(75), unit)]);
+    test_units("10 Δ°C + pow(5,2) Fahrenheit", &[(dec!(43), unit)]);
+    test_units("10 Δ°C + (pow(5,2) Fahrenheit)", &[(dec!(43), unit)]);
+    test_units("10 Δ°C + Fahrenheit(pow(5,2)) ", &[(dec!(43), unit)]);
+
+    for expression in [
+        "10 celsius + pow(5,2) Fahrenheit",
+        "10 celsius + (pow(5,2) Fahrenheit)",
+        "10 celsius + Fahrenheit(pow(5,2)) ",
+    ] {
+        assert!(
+            matches!(
+                compute_error(expression),
+                ComputeError::InvalidTemperatureOperation(_)
+            ),
+            "{}",
+            expression
+        );
+    }
 }
 
 #[test]
@@ -63,7 +110,67 @@ fn test_list_fce() {
         &[(dec!(-93.15), unit)],
     );
     test_units(
-        "sum(5 Celsius, 180 Kelvin, 10 Fahrenheit, 10 Celsius)",
-        &[(dec!(-90.37), unit)],
+        "average(5 Celsius, 180 Kelvin, 10 Celsius) into celsius",
+        &[(dec!(-26.05), unit)],
+    );
+    test_units(
+        "sum(18 Δ°F, 180 Kelvin, 5 Δ°C)",
+        &[(
+            dec!(195),
+            Some(Unit::Temperature(Temperature::DegreesCelsiusDifference)),
+        )],
+    );
+    assert!(matches!(
+        compute_error("sum(5 Celsius, 180 Kelvin, 10 Fahrenheit, 10 Celsius)"),
+        ComputeError::InvalidUnitForFunction(..)
+    ));
+}
+
+#[test]
+fn test_differences() {
+    let celsius = Some(Unit::Temperature(Temperature::DegreesCelsius));
+    let difference = Some(Unit::Temperature(Temperature::DegreesCelsiusDifference));
+    let kelvin = Some(Unit::Temperature(Temperature::Kelvin));
+
+    test_units("-50 celsius + 50 kelvin", &[(dec!(0), celsius)]);
+    test_units("20 °C + 5 Δ°C", &[(dec!(25), celsius)]);
+    test_units("5 Δ°C + 20 °C", &[(dec!(25), celsius)]);
+    test_units("20 °C - 5 K", &[(dec!(15), celsius)]);
+    test_units("20 °C - 5 °C", &[(dec!(15), difference)]);
+    test_units("20 °C - 50 °F", &[(dec!(10), difference)]);
+    // kelvins of the minuend are an absolute temperature
+    test_units("300 K - 20 °C", &[(dec!(6.85), difference)]);
+    test_units(
+        "300 K - 50 °F",
+        &[(
+            dec!(30.33),
+            Some(Unit::Temperature(Temperature::DegreesFahrenheitDifference)),
+        )],
     );
+    test_units("9 Δ°F + 5 Δ°C", &[(dec!(10), difference)]);
+    test_units("18 Δ°F in Δ°C", &[(dec!(10), difference)]);
+    test_units("10 K in Δ°C", &[(dec!(10), difference)]);
+    test_units("0 °C in kelvin", &[(dec!(273.15), kelvin)]);
+    test_units("-20 °C in kelvin", &[(dec!(253.15), kelvin)]);
+
+    for expression in [
+        "20 °C + 5 °C",
+        "10 celsius + 25 fahrenheit",
+        "5 Δ°C - 20 °C",
+        "sum(5 celsius, 180 kelvin)",
+        "10 Δ°C in °C",
+        "10 °C in Δ°C",
+    ] {
+        compute_error(expression);
+    }
+    for expression in ["20 °C - 300 K", "-500 °F + 10 Δ°F"] {
+        assert!(
+            matches!(
+                compute_error(expression),
+                ComputeError::InvalidTemperatureOperation(_)
+            ),
+            "{}",
+            expression
+        );
+    }
 }
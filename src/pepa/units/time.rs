@@ -5,14 +5,41 @@ use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
 use crate::units::{Abbreviations, Prefix, Unit};
-use crate::{make_abbreviations, make_abbreviations_with_prefixes, string, Decimal};
+use crate::utils::Pluralize;
+use crate::{make_abbreviations, make_abbreviations_with_prefixes, pluralize, string, Decimal};
 
 #[derive(Debug, Clone, Eq, Copy, PartialEq, EnumIter, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Time {
     Second(Option<Prefix>),
     Minute,
     Hour,
     Day,
+    /// working day, added to a date it skips weekends and holidays, it is not a fixed duration
+    /// and it is not converted to other units
+    Workday,
+    Week,
+    Fortnight,
+    /// average Gregorian month, 1/12 of the Gregorian year (30.436875 days)
+    Month,
+    /// calendar year, as a duration the average Gregorian year (365.2425 days),
+    /// added to a date it moves the date to the same day of the year
+    Year,
+    /// 365.25 days, used in astronomy and by the light year
+    JulianYear,
+    /// 365.2425 days, average year of the 400 years Gregorian cycle
+    GregorianYear,
+    /// 10 calendar years
+    Decade,
+    /// 100 calendar years
+    Century,
+    /// 1000 calendar years
+    Millennium,
+    /// seconds formatted as `25:01:01`
+    Clock,
+    /// seconds formatted as ISO 8601 duration `P1DT1H1M1S`
+    Iso8601,
 }
 
 impl Time {
@@ -35,7 +62,11 @@ impl Time {
                         "second",
                         "seconds",
                         "sec",
-                        "secs"
+                        "secs",
+                        // Czech
+                        "sekunda",
+                        "sekundy",
+                        "sekund"
                     ));
                 }
                 Time::Minute => {
@@ -51,7 +82,11 @@ impl Time {
                         "minute",
                         "minutes",
                         "min",
-                        "mins"
+                        "mins",
+                        // Czech
+                        "minuta",
+                        "minuty",
+                        "minut"
                     ));
                 }
                 Time::Hour => {
@@ -66,7 +101,12 @@ impl Time {
                         // case insensitive
                         "hours",
                         "hour",
-                        "hrs"
+                        "hrs",
+                        // Czech
+                        "hodina",
+                        "hodiny",
+                        "hodin",
+                        "hod"
                     ));
                 }
                 Time::Day => {
@@ -81,7 +121,141 @@ impl Time {
                         // case insensitive
                         "d",
                         "day",
-                        "days"
+                        "days",
+                        // Czech
+                        "den",
+                        "dny",
+                        "dní",
+                        "dnů"
+                    ));
+                }
+                Time::Workday => {
+                    case_insensitive.extend(make_abbreviations!(
+                        t.to_unit(),
+                        // case insensitive
+                        "workday",
+                        "workdays",
+                        "businessday",
+                        "businessdays",
+                        // Czech
+                        "pracovníden",
+                        "pracovnídny",
+                        "pracovnídní",
+                        "pracovníchdnů"
+                    ));
+                }
+                Time::Week => {
+                    case_insensitive.extend(make_abbreviations!(
+                        t.to_unit(),
+                        // case insensitive
+                        "week",
+                        "weeks",
+                        "wk",
+                        "wks",
+                        // Czech
+                        "týden",
+                        "týdny",
+                        "týdnů"
+                    ));
+                }
+                Time::Fortnight => {
+                    case_insensitive.extend(make_abbreviations!(
+                        t.to_unit(),
+                        // case insensitive
+                        "fortnight",
+                        "fortnights"
+                    ));
+                }
+                Time::Month => {
+                    case_insensitive.extend(make_abbreviations!(
+                        t.to_unit(),
+                        // case insensitive
+                        "month",
+                        "months",
+                        "mo",
+                        "mos",
+                        // Czech
+                        "měsíc",
+                        "měsíce",
+                        "měsíců"
+                    ));
+                }
+                Time::Year => {
+                    case_insensitive.extend(make_abbreviations!(
+                        t.to_unit(),
+                        // case insensitive
+                        "year",
+                        "years",
+                        "yr",
+                        "yrs",
+                        "calendaryear",
+                        "calendaryears",
+                        // Czech
+                        "rok",
+                        "roky",
+                        "roků",
+                        "let"
+                    ));
+                }
+                Time::JulianYear => {
+                    case_insensitive.extend(make_abbreviations!(
+                        t.to_unit(),
+                        // case insensitive
+                        "julianyear",
+                        "julianyears"
+                    ));
+                }
+                Time::GregorianYear => {
+                    case_insensitive.extend(make_abbreviations!(
+                        t.to_unit(),
+                        // case insensitive
+                        "gregorianyear",
+                        "gregorianyears"
+                    ));
+                }
+                Time::Decade => {
+                    case_insensitive.extend(make_abbreviations!(
+                        t.to_unit(),
+                        // case insensitive
+                        "decade",
+                        "decades",
+                        // Czech
+                        "desetiletí"
+                    ));
+                }
+                Time::Century => {
+                    case_insensitive.extend(make_abbreviations!(
+                        t.to_unit(),
+                        // case insensitive
+                        "century",
+                        "centuries",
+                        // Czech
+                        "století"
+                    ));
+                }
+                Time::Millennium => {
+                    case_insensitive.extend(make_abbreviations!(
+                        t.to_unit(),
+                        // case insensitive
+                        "millennium",
+                        "millennia",
+                        "millenniums",
+                        // Czech
+                        "tisíciletí"
+                    ));
+                }
+                Time::Clock => {
+                    case_insensitive.extend(make_abbreviations!(
+                        t.to_unit(),
+                        // case insensitive
+                        "hh:mm:ss"
+                    ));
+                }
+                Time::Iso8601 => {
+                    case_insensitive.extend(make_abbreviations!(
+                        t.to_unit(),
+                        // case insensitive
+                        "iso8601"
                     ));
                 }
             };
@@ -100,19 +274,223 @@ impl Time {
             Time::Minute => dec!(60.0),
             Time::Hour => dec!(60.0) * dec!(60.0),
             Time::Day => dec!(24.0) * dec!(60.0) * dec!(60.0),
+            Time::Workday => Time::Day.reference_unit_multiplier(),
+            Time::Week => dec!(7) * Time::Day.reference_unit_multiplier(),
+            Time::Fortnight => dec!(14) * Time::Day.reference_unit_multiplier(),
+            Time::Month => Time::GregorianYear.reference_unit_multiplier() / dec!(12),
+            Time::Year | Time::GregorianYear => {
+                dec!(365.2425) * Time::Day.reference_unit_multiplier()
+            }
+            Time::JulianYear => dec!(365.25) * Time::Day.reference_unit_multiplier(),
+            Time::Decade => dec!(10) * Time::Year.reference_unit_multiplier(),
+            Time::Century => dec!(100) * Time::Year.reference_unit_multiplier(),
+            Time::Millennium => dec!(1000) * Time::Year.reference_unit_multiplier(),
+            Time::Clock | Time::Iso8601 => dec!(1),
+        }
+    }
+
+    /// Number of calendar months, for units added to dates by the calendar rules.
+    pub fn calendar_months(self) -> Option<Decimal> {
+        match self {
+            Time::Month => Some(dec!(1)),
+            Time::Year => Some(dec!(12)),
+            Time::Decade => Some(dec!(120)),
+            Time::Century => Some(dec!(1200)),
+            Time::Millennium => Some(dec!(12000)),
+            _ => None,
         }
     }
 
-    pub fn to_string_with_plural(self, _: &Decimal) -> String {
+    pub fn to_string_with_plural(self, v: &Decimal) -> String {
         match self {
             Time::Second(None) => string!("s"),
             Time::Second(Some(p)) => string!(p) + "s",
             Time::Minute => string!("m"),
             Time::Hour => string!("h"),
             Time::Day => string!("d"),
+            // calendar units have no symbols
+            Time::Workday
+            | Time::Week
+            | Time::Fortnight
+            | Time::Month
+            | Time::Year
+            | Time::JulianYear
+            | Time::GregorianYear
+            | Time::Decade
+            | Time::Century
+            | Time::Millennium => self.to_name_with_plural(v),
+            Time::Clock | Time::Iso8601 => string!(""),
+        }
+    }
+
+    /// Full name: `1 hour`, `5 milliseconds`
+    pub fn to_name_with_plural(self, v: &Decimal) -> String {
+        match self {
+            Time::Second(None) => pluralize!("second", v),
+            Time::Second(Some(p)) => string!(p.name()) + &pluralize!("second", v),
+            Time::Minute => pluralize!("minute", v),
+            Time::Hour => pluralize!("hour", v),
+            Time::Day => pluralize!("day", v),
+            Time::Workday => pluralize!("workday", v),
+            Time::Week => pluralize!("week", v),
+            Time::Fortnight => pluralize!("fortnight", v),
+            Time::Month => pluralize!("month", v),
+            Time::Year => pluralize!("year", v),
+            Time::JulianYear => pluralize!("julian year", v),
+            Time::GregorianYear => pluralize!("gregorian year", v),
+            Time::Decade => pluralize!("decade", v),
+            Time::Century => pluralize!("century", "centuries", v),
+            Time::Millennium => pluralize!("millennium", "millennia", v),
+            Time::Clock | Time::Iso8601 => string!(""),
+        }
+    }
+
+    /// Formats seconds of the formatting units: `hh:mm:ss`, `iso8601`
+    pub fn format_value(self, v: &Decimal) -> Option<String> {
+        let sign = if v.is_sign_negative() && !v.is_zero() {
+            "-"
+        } else {
+            ""
+        };
+        let v = v.abs();
+        let seconds = (v % dec!(60)).normalize();
+        let minutes = (v / dec!(60)).trunc() % dec!(60);
+        let hours = (v / dec!(3600)).trunc();
+
+        match self {
+            Time::Clock => {
+                let seconds = if seconds < dec!(10) {
+                    format!("0{}", seconds)
+                } else {
+                    string!(seconds)
+                };
+
+                Some(format!("{}{:02}:{:02}:{}", sign, hours, minutes, seconds))
+            }
+            Time::Iso8601 => {
+                let days = (hours / dec!(24)).trunc();
+                let hours = hours % dec!(24);
+
+                let mut res = format!("{}P", sign);
+                if !days.is_zero() {
+                    res += &format!("{}D", days);
+                }
+
+                if !(hours.is_zero() && minutes.is_zero() && seconds.is_zero()) || days.is_zero() {
+                    res += "T";
+                }
+                for (n, designator) in [(hours, 'H'), (minutes, 'M')] {
+                    if !n.is_zero() {
+                        res += &format!("{}{}", n, designator);
+                    }
+                }
+                if !seconds.is_zero() || res.ends_with('T') {
+                    res += &format!("{}S", seconds);
+                }
+
+                Some(res)
+            }
+            _ => None,
         }
     }
 
+    /// Parses a duration to seconds: `01:30:15`, ISO 8601 `PT1H30M`, `P3DT4H`
+    ///
+    /// ISO months and years are the average Gregorian ones.
+    pub fn parse_duration(s: &str) -> Option<Decimal> {
+        if let Some(iso) = s.strip_prefix('P') {
+            return Time::parse_iso8601(iso);
+        }
+
+        let [hours, minutes, seconds] = s.split(':').collect::<Vec<_>>()[..] else {
+            return None;
+        };
+
+        let digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+        let (whole_seconds, fraction) = seconds.split_once('.').unwrap_or((seconds, "0"));
+
+        if !digits(hours)
+            || minutes.len() != 2
+            || !digits(minutes)
+            || whole_seconds.len() != 2
+            || !digits(whole_seconds)
+            || !digits(fraction)
+        {
+            return None;
+        }
+
+        let minutes = minutes.parse::<Decimal>().ok()?;
+        let seconds = seconds.parse::<Decimal>().ok()?;
+        if minutes >= dec!(60) || seconds >= dec!(60) {
+            return None;
+        }
+
+        hours
+            .parse::<Decimal>()
+            .ok()?
+            .checked_mul(dec!(3600))?
+            .checked_add(minutes * dec!(60) + seconds)
+    }
+
+    fn parse_iso8601(s: &str) -> Option<Decimal> {
+        let (date, time) = match s.split_once('T') {
+            Some((date, time)) if !time.is_empty() => (date, time),
+            Some(_) => return None,
+            None => (s, ""),
+        };
+
+        let mut total = Decimal::ZERO;
+        let mut components = 0;
+
+        for (part, units) in [
+            (
+                date,
+                [
+                    ('Y', Time::Year),
+                    ('M', Time::Month),
+                    ('W', Time::Week),
+                    ('D', Time::Day),
+                ]
+                .as_slice(),
+            ),
+            (
+                time,
+                [
+                    ('H', Time::Hour),
+                    ('M', Time::Minute),
+                    ('S', Time::Second(None)),
+                ]
+                .as_slice(),
+            ),
+        ] {
+            let mut number = String::new();
+            let mut units = units.iter();
+
+            for c in part.chars() {
+                if c.is_ascii_digit() || c == '.' || c == ',' {
+                    number.push(if c == ',' { '.' } else { c });
+                    continue;
+                }
+
+                // designators must be in order
+                let (_, unit) = units.by_ref().find(|(d, _)| *d == c)?;
+                let value = number
+                    .parse::<Decimal>()
+                    .ok()?
+                    .checked_mul(unit.reference_unit_multiplier())?;
+                total = total.checked_add(value)?;
+                number.clear();
+                components += 1;
+            }
+
+            if !number.is_empty() {
+                return None;
+            }
+        }
+
+        (components > 0).then_some(total)
+    }
+
     pub fn to_unit(self) -> Unit {
         Unit::Time(self)
     }
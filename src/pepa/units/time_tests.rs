@@ -2,6 +2,7 @@ use rust_decimal_macros::dec;
 
 use crate::units::unit::test_units;
 use crate::units::{Time, Unit};
+use crate::Calculator;
 
 #[test]
 fn test_time() {
@@ -16,3 +17,126 @@ fn test_time() {
         &[(dec!(121.75), unit)],
     );
 }
+
+#[test]
+fn test_long_units() {
+    test_units(
+        "3 weeks in days",
+        &[(dec!(21), Some(Unit::Time(Time::Day)))],
+    );
+    test_units(
+        "2 fortnights in weeks",
+        &[(dec!(4), Some(Unit::Time(Time::Week)))],
+    );
+    test_units(
+        "2 years in hours",
+        &[(dec!(17531.64), Some(Unit::Time(Time::Hour)))],
+    );
+    test_units(
+        "1 julian year in days",
+        &[(dec!(365.25), Some(Unit::Time(Time::Day)))],
+    );
+    test_units(
+        "1 gregorian year in days",
+        &[(dec!(365.2425), Some(Unit::Time(Time::Day)))],
+    );
+    test_units(
+        "1 month in days",
+        &[(dec!(30.436875), Some(Unit::Time(Time::Day)))],
+    );
+    test_units(
+        "1 year in months",
+        &[(dec!(12), Some(Unit::Time(Time::Month)))],
+    );
+    test_units(
+        "1 millennium in centuries",
+        &[(dec!(10), Some(Unit::Time(Time::Century)))],
+    );
+    test_units(
+        "3 decades in years",
+        &[(dec!(30), Some(Unit::Time(Time::Year)))],
+    );
+}
+
+#[test]
+fn test_long_units_output() {
+    // calendar units have no symbols, they are written by their names
+    for (expression, expected) in [
+        ("3 weeks", "3 weeks"),
+        ("1 wk + 1 wk", "2 weeks"),
+        ("1 mo", "1 month"),
+        ("2 yrs", "2 years"),
+        ("20 years in decades", "2 decades"),
+        ("1 julian year", "1 julian year"),
+    ] {
+        let mut calculator = Calculator::default();
+        let statement = calculator.compute(expression).unwrap();
+        let result = statement.result.as_ref().unwrap().as_ref().unwrap();
+
+        assert_eq!(result.to_string(), expected, "{}", expression);
+    }
+
+    // one letter names are left to variables and unknowns
+    assert!(Unit::from_string("a").is_empty());
+    assert!(Unit::from_string("y").is_empty());
+}
+
+#[test]
+fn test_parse_duration() {
+    for (s, seconds) in [
+        ("01:30:15", Some(dec!(5415))),
+        ("100:00:00", Some(dec!(360000))),
+        ("00:00:01.5", Some(dec!(1.5))),
+        ("PT1H30M", Some(dec!(5400))),
+        ("P3DT4H", Some(dec!(273600))),
+        ("P1W", Some(dec!(604800))),
+        ("PT0.5S", Some(dec!(0.5))),
+        ("PT1,5M", Some(dec!(90))),
+        ("P1Y", Some(dec!(31556952))),
+        ("01:60:00", None),
+        ("01:30", None),
+        ("1:2:3", None),
+        ("P", None),
+        ("PT", None),
+        ("P1H", None),
+        ("PT1M1H", None),
+        ("P1D2", None),
+        // out of range
+        ("P9999999999999999999999999999D", None),
+        ("P130000000000000000000000W900000000000000000000000D", None),
+        ("79228162514264337593543950335:00:00", None),
+    ] {
+        assert_eq!(Time::parse_duration(s), seconds, "{}", s);
+    }
+}
+
+#[test]
+fn test_duration_formats() {
+    let tests = [
+        ("90061 s in hh:mm:ss", "25:01:01"),
+        ("90061 s in iso8601", "P1DT1H1M1S"),
+        ("PT1H30M in minutes", "90.0m"),
+        ("P3DT4H in hours", "76.0h"),
+        ("01:30:15 + 45 s in hh:mm:ss", "01:31:00"),
+        ("(3 / 2) h in hh:mm:ss", "01:30:00"),
+        ("(9 / 4) s in hh:mm:ss", "00:00:02.25"),
+        ("2 days in iso8601", "P2D"),
+        ("0 s in iso8601", "PT0S"),
+        ("-90 min in hh:mm:ss", "-01:30:00"),
+        ("2026-12-24T18:00 + PT1H30M", "2026-12-24T19:30:00"),
+        // durations are shown in the form they were written
+        ("PT1H30M", "PT1H30M"),
+        ("PT1H30M * 2", "PT3H"),
+        ("PT1H30M in iso8601", "PT1H30M"),
+        ("01:30:15", "01:30:15"),
+        ("01:30:15 in iso8601", "PT1H30M15S"),
+    ];
+
+    for (expression, expected) in tests {
+        let mut calculator = Calculator::default();
+        let statement = calculator.compute(expression).unwrap();
+        let result = statement.result.as_ref().unwrap().as_ref().unwrap();
+
+        assert_eq!(result.to_string(), expected, "{}", expression);
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use chrono_tz::{Tz, TZ_VARIANTS};
+use rust_decimal::Decimal;
+
+use crate::string;
+use crate::units::{Abbreviations, Unit};
+
+/// Time zone from the bundled IANA tz database, a conversion target of date and time values.
+#[derive(Debug, Clone, Eq, Copy, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimeZone(pub Tz);
+
+/// Areas of the canonical zones, their cities can be used without the area: `Prague`, `New York`
+const AREAS: [&str; 9] = [
+    "Africa",
+    "America",
+    "Antarctica",
+    "Asia",
+    "Atlantic",
+    "Australia",
+    "Europe",
+    "Indian",
+    "Pacific",
+];
+
+impl TimeZone {
+    /// Zone of a name or of a city: `Europe/Prague`, `prague`, `NewYork`, `utc`
+    pub fn from_name(name: &str) -> Option<TimeZone> {
+        let abbreviations = TimeZone::abbreviations();
+
+        abbreviations
+            .case_sensitive
+            .get(name)
+            .or_else(|| abbreviations.case_insensitive.get(&name.to_lowercase()))
+            .and_then(|u| match u {
+                Unit::TimeZone(z) => Some(*z),
+                _ => None,
+            })
+    }
+
+    fn abbreviations() -> &'static Abbreviations {
+        static MEM: OnceLock<Abbreviations> = OnceLock::new();
+        MEM.get_or_init(TimeZone::make_abbreviations)
+    }
+
+    fn make_abbreviations() -> Abbreviations {
+        let mut case_sensitive = HashMap::new();
+        let mut case_insensitive = HashMap::new();
+
+        for tz in TZ_VARIANTS {
+            let unit = TimeZone(tz).to_unit();
+            let name = tz.name();
+
+            case_sensitive.insert(string!(name), unit);
+
+            let Some((area, city)) = name.split_once('/') else {
+                continue;
+            };
+
+            case_insensitive.insert(name.to_lowercase(), unit);
+
+            if AREAS.contains(&area) {
+                // America/Argentina/Buenos_Aires
+                let city = city.rsplit('/').next().unwrap_or(city).to_lowercase();
+
+                case_insensitive
+                    .entry(city.replace('_', ""))
+                    .or_insert(unit);
+                case_insensitive.entry(city).or_insert(unit);
+            }
+        }
+
+        for name in ["utc", "gmt"] {
+            case_insensitive.insert(string!(name), TimeZone(Tz::UTC).to_unit());
+        }
+
+        Abbreviations {
+            case_sensitive,
+            case_insensitive,
+        }
+    }
+
+    pub fn to_string_with_plural(self, _: &Decimal) -> String {
+        string!(self.0.name())
+    }
+
+    pub fn to_unit(self) -> Unit {
+        Unit::TimeZone(self)
+    }
+
+    /// Zone of the system, UTC if it can not be determined.
+    pub fn local() -> TimeZone {
+        TimeZone(
+            iana_time_zone::get_timezone()
+                .ok()
+                .and_then(|name| name.parse::<Tz>().ok())
+                .unwrap_or(Tz::UTC),
+        )
+    }
+}
+
+impl Default for TimeZone {
+    fn default() -> Self {
+        TimeZone(Tz::UTC)
+    }
+}
@@ -0,0 +1,160 @@
+use chrono::{DateTime, TimeZone as _, Utc};
+use chrono_tz::Tz;
+
+use crate::units::{TimeZone, Unit};
+use crate::Calculator;
+
+fn compute(expression: &str) -> String {
+    compute_at(expression, Utc::now())
+}
+
+fn compute_at(expression: &str, now: DateTime<Utc>) -> String {
+    let mut calculator = Calculator::default();
+    calculator.set_now(now);
+    let statement = calculator.compute(expression).unwrap();
+
+    match &statement.result {
+        Some(Ok(n)) => n.to_string(),
+        r => panic!("{:?}: {:?}", expression, r),
+    }
+}
+
+#[test]
+fn test_names() {
+    for (name, tz) in [
+        ("Europe/Prague", Tz::Europe__Prague),
+        ("prague", Tz::Europe__Prague),
+        ("NewYork", Tz::America__New_York),
+        ("new_york", Tz::America__New_York),
+        ("Buenos_Aires", Tz::America__Argentina__Buenos_Aires),
+        ("UTC", Tz::UTC),
+        ("gmt", Tz::UTC),
+    ] {
+        assert_eq!(TimeZone::from_name(name), Some(TimeZone(tz)), "{}", name);
+        assert_eq!(
+            Unit::from_target_string(name),
+            vec![Unit::TimeZone(TimeZone(tz))],
+            "{}",
+            name
+        );
+    }
+
+    // zones are not units of numbers
+    assert!(Unit::from_string("Lima").is_empty());
+}
+
+#[test]
+fn test_conversions() {
+    let tests = [
+        (
+            "2026-12-24T15:00 Prague",
+            "2026-12-24T15:00:00 Europe/Prague",
+        ),
+        (
+            "2026-12-24T15:00 Prague in New York",
+            "2026-12-24T09:00:00 America/New_York",
+        ),
+        (
+            "2026-12-24T15:00 Europe/Prague to America/New_York",
+            "2026-12-24T09:00:00 America/New_York",
+        ),
+        ("2026-12-24T15:00 Prague in UTC", "2026-12-24T14:00:00 UTC"),
+        // summer time
+        ("2026-07-01T15:00 Prague in UTC", "2026-07-01T13:00:00 UTC"),
+        // US already on summer time, Europe not yet
+        (
+            "2026-03-15T15:00 Prague in New York",
+            "2026-03-15T10:00:00 America/New_York",
+        ),
+        (
+            "2026-12-24 Tokyo in Prague",
+            "2026-12-23T16:00:00 Europe/Prague",
+        ),
+        // DST starts at 02:00 on 2026-03-29
+        (
+            "2026-03-29T01:30 Prague + 1 hour",
+            "2026-03-29T03:30:00 Europe/Prague",
+        ),
+        ("2026-03-30 Prague - 2026-03-29 Prague in hours", "23h"),
+        (
+            "2026-12-24T15:00 Prague - 2026-12-24T15:00 London in hours",
+            "-1h",
+        ),
+    ];
+
+    for (expression, expected) in tests {
+        assert_eq!(compute(expression), expected, "{}", expression);
+    }
+}
+
+#[test]
+fn test_time_of_day() {
+    assert_eq!(compute("15:00"), "15:00:00");
+    assert_eq!(compute("15:00 + 90 minutes"), "16:30:00");
+    assert_eq!(compute("23:00 + 2 hours"), "01:00:00");
+    assert_eq!(compute("18:00 - 15:30"), "9000s");
+
+    // 6 hours in winter, 5 hours when only one of the zones has summer time
+    for ((y, m, d), expected) in [
+        ((2026, 1, 15), "09:00:00 America/New_York"),
+        ((2026, 3, 20), "10:00:00 America/New_York"),
+        ((2026, 7, 1), "09:00:00 America/New_York"),
+    ] {
+        let now = Utc.with_ymd_and_hms(y, m, d, 12, 0, 0).unwrap();
+        assert_eq!(compute_at("15:00 Prague in New York", now), expected);
+        assert_eq!(compute_at("15:00 Prague in UTC in New York", now), expected);
+    }
+
+    assert_eq!(compute("15:00 UTC in UTC"), "15:00:00 UTC");
+}
+
+#[test]
+fn test_now() {
+    assert!(compute("now in UTC").ends_with(" UTC"));
+    assert!(compute("now in Tokyo").ends_with(" Asia/Tokyo"));
+}
+
+#[test]
+fn test_errors() {
+    for expression in [
+        // skipped by the DST change
+        "2026-03-29T02:30 Prague",
+        "(2 + 3) in UTC",
+        "5 Lima",
+        "Lima",
+    ] {
+        let mut calculator = Calculator::default();
+        let statement = calculator.compute(expression).unwrap();
+
+        if let Some(Ok(n)) = &statement.result {
+            panic!("{:?} = {} expected to fail", expression, n);
+        }
+    }
+}
+
+#[test]
+fn test_values_without_zone() {
+    let mut calculator = Calculator::default();
+    calculator.set_time_zone(TimeZone(Tz::Europe__Prague));
+
+    for (expression, expected) in [
+        ("2026-12-24T15:00 Prague - 2026-12-24T15:00", "0s"),
+        ("2026-12-24T15:00 London - 2026-12-24", "57600s"),
+    ] {
+        let statement = calculator.compute(expression).unwrap();
+        match &statement.result {
+            Some(Ok(n)) => assert_eq!(n.to_string(), expected, "{}", expression),
+            r => panic!("{:?}: {:?}", expression, r),
+        }
+    }
+
+    for expression in ["now - today", "now - 2026-01-01"] {
+        let statement = calculator.compute(expression).unwrap();
+        assert!(
+            matches!(statement.result, Some(Ok(_))),
+            "{}: {:?}",
+            expression,
+            statement.result
+        );
+    }
+}
@@ -1,15 +1,28 @@
-use rust_decimal_macros::dec;
+use rust_decimal::RoundingStrategy;
 use std::collections::HashMap;
 
 use strum::IntoEnumIterator;
-use strum_macros::EnumIter;
+use strum_macros::{EnumDiscriminants, EnumIter};
 
+use crate::context::Context;
 use crate::units::angle::Angle;
+use crate::units::epoch::Epoch;
 use crate::units::volume::Volume;
-use crate::units::{Length, Mass, Temperature, Time};
-use crate::{string, Calculator, Decimal, NumericExpression};
+use crate::units::{Currency, Length, Mass, Speed, Temperature, Time, TimeZone};
+use crate::Decimal;
+#[cfg(test)]
+use crate::{Calculator, NumericExpression};
+#[cfg(test)]
+use rust_decimal_macros::dec;
 
-#[derive(Debug, Clone, Eq, Copy, PartialEq, EnumIter, Hash)]
+#[derive(Debug, Clone, Eq, Copy, PartialEq, EnumIter, EnumDiscriminants, Hash)]
+#[strum_discriminants(
+    name(UnitFamily),
+    derive(Hash, EnumIter, strum_macros::Display, strum_macros::EnumString),
+    strum(serialize_all = "snake_case", ascii_case_insensitive)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Unit {
     Temperature(Temperature),
     Time(Time),
@@ -17,6 +30,18 @@ pub enum Unit {
     Mass(Mass),
     Angle(Angle),
     Volume(Volume),
+    Speed(Speed),
+    Currency(Currency),
+    TimeZone(TimeZone),
+    Epoch(Epoch),
+}
+
+/// How units are written in the output: `5km` or `5 kilometres`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum UnitStyle {
+    #[default]
+    Symbol,
+    Name,
 }
 
 pub struct Abbreviations {
@@ -24,10 +49,6 @@ pub struct Abbreviations {
     pub case_insensitive: HashMap<String, Unit>,
 }
 
-trait UnitDefinition {
-    fn abbreviations() -> Abbreviations; TODO
-}
-
 impl Unit {
     fn abbreviations(&self) -> Abbreviations {
         match self {
@@ -37,15 +58,27 @@ impl Unit {
             Unit::Mass(_) => Mass::abbreviations(),
             Unit::Angle(_) => Angle::abbreviations(),
             Unit::Volume(_) => Volume::abbreviations(),
+            Unit::Speed(_) => Speed::abbreviations(),
+            Unit::Currency(_) => Currency::abbreviations(),
+            // zones are not units of numbers, see [`Unit::from_target_string`]
+            Unit::TimeZone(_) => Abbreviations {
+                case_sensitive: HashMap::new(),
+                case_insensitive: HashMap::new(),
+            },
+            Unit::Epoch(_) => Epoch::abbreviations(),
         }
     }
 
+    pub fn family(&self) -> UnitFamily {
+        UnitFamily::from(self)
+    }
+
     pub fn from_string(name: &str) -> Vec<Unit> {
         let mut res = Vec::new();
 
         for u in Unit::iter() {
             let abbreviations = u.abbreviations();
-            if let Some(u) = abbreviations.case_sensitive.get(&string!(name)) {
+            if let Some(u) = abbreviations.case_sensitive.get(name) {
                 res.push(*u);
             }
         }
@@ -64,13 +97,48 @@ impl Unit {
         res
     }
 
-    pub fn conversion(&self, v: &Decimal, to: &Unit) -> Option<Decimal> {
+    /// Units of a conversion target, these include time zones: `15:00 in Prague`
+    pub fn from_target_string(name: &str) -> Vec<Unit> {
+        match Unit::from_string(name) {
+            units if units.is_empty() => TimeZone::from_name(name)
+                .map(|z| vec![z.to_unit()])
+                .unwrap_or_default(),
+            units => units,
+        }
+    }
+
+    /// Unambiguous text form of the unit, the family and the symbol: `time:m`, `length:m`
+    pub fn id(&self) -> String {
+        format!("{}:{}", self.family(), self.to_string_with_plural(&Decimal::ONE))
+    }
+
+    /// Unit of the text form written by [`Unit::id`].
+    pub fn from_id(id: &str) -> Option<Unit> {
+        let (family, symbol) = id.split_once(':')?;
+        let family = family.parse::<UnitFamily>().ok()?;
+        if family == UnitFamily::TimeZone {
+            return TimeZone::from_name(symbol).map(TimeZone::to_unit);
+        }
+
+        let abbreviations = Unit::iter().find(|u| u.family() == family)?.abbreviations();
+
+        abbreviations
+            .case_sensitive
+            .values()
+            .chain(abbreviations.case_insensitive.values())
+            .find(|u| u.to_string_with_plural(&Decimal::ONE) == symbol)
+            .copied()
+    }
+
+    pub fn conversion(&self, v: &Decimal, to: &Unit, ctx: &Context) -> Option<Decimal> {
         match self {
             Unit::Temperature(from) => match to {
-                Unit::Temperature(to) => Some(to.from_reference_unit(from.to_reference_unit(*v))),
+                Unit::Temperature(to) => from.conversion(*v, *to),
                 _ => None,
             },
             Unit::Time(from) => match to {
+                // working days depend on the holidays
+                Unit::Time(to) if (*from == Time::Workday) != (*to == Time::Workday) => None,
                 Unit::Time(to) => {
                     Some(v * from.reference_unit_multiplier() / to.reference_unit_multiplier())
                 }
@@ -100,6 +168,22 @@ impl Unit {
                 }
                 _ => None,
             },
+            Unit::Speed(from) => match to {
+                Unit::Speed(to) => {
+                    Some(v * from.reference_unit_multiplier() / to.reference_unit_multiplier())
+                }
+                _ => None,
+            },
+            Unit::Currency(from) => match to {
+                Unit::Currency(to) => ctx.convert_currency(*v, *from, *to),
+                _ => None,
+            },
+            // only date and time values are converted between zones
+            Unit::TimeZone(_) => None,
+            Unit::Epoch(from) => match to {
+                Unit::Epoch(to) => to.from_reference_unit(from.to_reference_unit(*v)?),
+                _ => None,
+            },
         }
     }
 
@@ -111,6 +195,56 @@ impl Unit {
             Unit::Mass(m) => m.to_string_with_plural(n),
             Unit::Angle(a) => a.to_string_with_plural(n),
             Unit::Volume(a) => a.to_string_with_plural(n),
+            Unit::Speed(s) => s.to_string_with_plural(n),
+            Unit::Currency(c) => c.to_string_with_plural(n),
+            Unit::TimeZone(z) => z.to_string_with_plural(n),
+            Unit::Epoch(e) => e.to_string_with_plural(n),
+        }
+    }
+
+    /// Full name of the unit: `kilometres`, symbols are kept for units without a name.
+    pub fn to_name_with_plural(&self, n: &Decimal) -> String {
+        match self {
+            Unit::Temperature(t) => t.to_name_with_plural(n),
+            Unit::Time(t) => t.to_name_with_plural(n),
+            Unit::Length(l) => l.to_name_with_plural(n),
+            Unit::Mass(m) => m.to_name_with_plural(n),
+            Unit::Angle(a) => a.to_name_with_plural(n),
+            Unit::Speed(s) => s.to_name_with_plural(n),
+            Unit::Currency(c) => c.to_name_with_plural(n),
+            Unit::Volume(_) | Unit::TimeZone(_) | Unit::Epoch(_) => self.to_string_with_plural(n),
+        }
+    }
+
+    pub fn to_string_with_style(&self, n: &Decimal, style: UnitStyle) -> String {
+        match style {
+            UnitStyle::Symbol => self.to_string_with_plural(n),
+            UnitStyle::Name => self.to_name_with_plural(n),
+        }
+    }
+
+    /// Value followed by the unit, units written by their names are separated by a space:
+    /// `5km`, `3 weeks`, `5 kilometres` with [`UnitStyle::Name`]
+    pub fn with_value(&self, value: String, n: &Decimal, style: UnitStyle) -> String {
+        let unit = self.to_string_with_style(n, style);
+
+        if !unit.is_empty() && (style == UnitStyle::Name || unit == self.to_name_with_plural(n)) {
+            format!("{} {}", value, unit)
+        } else {
+            format!("{}{}", value, unit)
+        }
+    }
+
+    /// Formats the value with precision appropriate to the unit, money is rounded to its minor units.
+    pub fn format_value(&self, n: &Decimal) -> String {
+        match self {
+            Unit::Currency(c) => format!(
+                "{:.*}",
+                c.minor_units() as usize,
+                n.round_dp_with_strategy(c.minor_units(), RoundingStrategy::MidpointAwayFromZero)
+            ),
+            Unit::Time(t) => t.format_value(n).unwrap_or_else(|| n.to_string()),
+            _ => n.to_string(),
         }
     }
 }
@@ -1,7 +1,8 @@
 use rust_decimal_macros::dec;
 
 use crate::units::unit::test_units;
-use crate::units::{Length, Prefix, Time, Unit};
+use crate::units::{Length, Prefix, Time, Unit, UnitStyle};
+use crate::{Calculator, ComputeError, Locale};
 
 #[test]
 fn test_none() {
@@ -10,40 +11,23 @@ fn test_none() {
 
 #[test]
 fn test_functions() {
-    test_units("(5 h) ^ 2", &[(dec!(25), Some(Unit::Time(Time::Hour)))]);
+    test_units("(5 h) ^ 1", &[(dec!(5), Some(Unit::Time(Time::Hour)))]);
     test_units(
-        "(5 m) ^ 2",
-        &[
-            (dec!(25), Some(Unit::Time(Time::Minute))),
-            (dec!(25), Some(Unit::Length(Length::Meter(None)))),
-        ],
-    );
-    test_units(
-        "(5 m) ^ 2 + 1m",
-        &[
-            (dec!(26), Some(Unit::Time(Time::Minute))),
-            (dec!(26), Some(Unit::Length(Length::Meter(None)))),
-        ],
-    );
-    test_units(
-        "((5 m) ^ 2 + 1km) to meters",
-        &[(dec!(1025), Some(Unit::Length(Length::Meter(None))))],
+        "(5 m) ^ 1 + 1km",
+        &[(
+            dec!(1.005),
+            Some(Unit::Length(Length::Meter(Some(Prefix::Kilo)))),
+        )],
     );
 
     test_units(
         "min(5 m, 4m, 1)",
-        &[
-            (dec!(1), Some(Unit::Time(Time::Minute))),
-            (dec!(1), Some(Unit::Length(Length::Meter(None)))),
-        ],
+        &[(dec!(1), Some(Unit::Length(Length::Meter(None))))],
     );
 
     test_units(
         "min(5 m, 4m)",
-        &[
-            (dec!(4), Some(Unit::Time(Time::Minute))),
-            (dec!(4), Some(Unit::Length(Length::Meter(None)))),
-        ],
+        &[(dec!(4), Some(Unit::Length(Length::Meter(None))))],
     );
 
     test_units(
@@ -56,10 +40,7 @@ fn test_functions() {
 
     test_units(
         "max(5 m, 4m, 6m)",
-        &[
-            (dec!(6), Some(Unit::Time(Time::Minute))),
-            (dec!(6), Some(Unit::Length(Length::Meter(None)))),
-        ],
+        &[(dec!(6), Some(Unit::Length(Length::Meter(None))))],
     );
 
     test_units(
@@ -70,10 +51,50 @@ fn test_functions() {
         )],
     );
 
-    test_units(
-        "pow(60 m, 1 hour)",
-        &[(dec!(1), Some(Unit::Time(Time::Hour)))],
-    );
+    // an exponent has no unit
+    let mut calculator = Calculator::default();
+    let statement = calculator.compute("pow(60 m, 1 hour)").unwrap();
+    assert!(matches!(
+        statement.result,
+        Some(Err(ComputeError::InvalidUnitForFunction(..)))
+    ));
+}
+
+#[test]
+fn test_function_units() {
+    let km = Some(Unit::Length(Length::Meter(Some(Prefix::Kilo))));
+
+    test_units("round(5.5 km)", &[(dec!(6), km)]);
+    test_units("pow(5 km, 1)", &[(dec!(5), km)]);
+    test_units("prod(2 km, 3)", &[(dec!(6), km)]);
+    test_units("sin(90 degrees)", &[(dec!(1), None)]);
+    test_units("cos(0.5 turns)", &[(dec!(-1), None)]);
+    test_units("count(1 km, 2 km)", &[(dec!(2), None)]);
+
+    // there are no units of areas
+    for expression in [
+        "sqr(3 km)",
+        "sqrt(16 km)",
+        "pow(5 km, 4 km)",
+        "pow(5 km, 2)",
+        "(5 km) ^ 2",
+        "2 ^ (3 km)",
+        "prod(2 km, 3 km)",
+        "ln(5 km)",
+        "sin(5 km)",
+    ] {
+        let mut calculator = Calculator::default();
+        let statement = calculator.compute(expression).unwrap();
+
+        assert!(
+            matches!(
+                statement.result,
+                Some(Err(ComputeError::InvalidUnitForFunction(..)))
+            ),
+            "{}",
+            expression
+        );
+    }
 }
 
 #[test]
@@ -107,18 +128,12 @@ fn test_multi_units() {
 
     test_units(
         "5 + 5m",
-        &[
-            (dec!(10), Some(Unit::Time(Time::Minute))),
-            (dec!(10), Some(Unit::Length(Length::Meter(None)))),
-        ],
+        &[(dec!(10), Some(Unit::Length(Length::Meter(None))))],
     );
 
     test_units(
         "5m + 5m",
-        &[
-            (dec!(10), Some(Unit::Time(Time::Minute))),
-            (dec!(10), Some(Unit::Length(Length::Meter(None)))),
-        ],
+        &[(dec!(10), Some(Unit::Length(Length::Meter(None))))],
     );
 
     test_units(
@@ -131,25 +146,57 @@ fn test_multi_units() {
 
     test_units(
         "(33 + 3) m + 15",
-        &[
-            (dec!(51), Some(Unit::Time(Time::Minute))),
-            (dec!(51), Some(Unit::Length(Length::Meter(None)))),
-        ],
+        &[(dec!(51), Some(Unit::Length(Length::Meter(None))))],
     );
 
     test_units(
         "(33m + 3m) m + 15m",
-        &[
-            (dec!(51), Some(Unit::Time(Time::Minute))),
-            (dec!(51), Some(Unit::Length(Length::Meter(None)))),
-        ],
+        &[(dec!(51), Some(Unit::Length(Length::Meter(None))))],
     );
 
     test_units(
         "(33m + 3m) m + 3 + 15m + 1",
-        &[
-            (dec!(55), Some(Unit::Time(Time::Minute))),
-            (dec!(55), Some(Unit::Length(Length::Meter(None)))),
-        ],
+        &[(dec!(55), Some(Unit::Length(Length::Meter(None))))],
+    );
+}
+
+#[test]
+fn test_names() {
+    for (expression, expected) in [
+        ("5 km", "5 kilometres"),
+        ("1 km", "1 kilometre"),
+        ("1 h", "1 hour"),
+        ("1.5 h", "1.5 hours"),
+        ("0 s", "0 seconds"),
+        ("-1 day", "-1 day"),
+        ("250 ms", "250 milliseconds"),
+        ("3 feet", "3 feet"),
+        ("2 centuries", "2 centuries"),
+        ("100 celsius", "100 degrees Celsius"),
+        ("1 kelvin", "1 kelvin"),
+        ("2 kg", "2 kilograms"),
+        ("12.5 EUR", "12.50 euros"),
+        ("1 USD", "1.00 US dollar"),
+        ("90 min in hh:mm:ss", "01:30:00"),
+        ("55", "55"),
+    ] {
+        let mut calculator = Calculator::default();
+        let statement = calculator.compute(expression).unwrap();
+        let Some(Ok(n)) = &statement.result else {
+            panic!("{:?}: {:?}", expression, statement.result);
+        };
+
+        assert_eq!(
+            Locale::En.format(n, UnitStyle::Name),
+            expected,
+            "{}",
+            expression
+        );
+    }
+
+    assert_eq!(
+        Unit::Length(Length::Meter(Some(Prefix::Kilo)))
+            .to_string_with_style(&dec!(5), UnitStyle::Symbol),
+        "km"
     );
 }
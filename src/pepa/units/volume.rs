@@ -1,16 +1,15 @@
 use std::collections::HashMap;
 
 use rust_decimal::Decimal;
-use rust_decimal_macros::dec;
-use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
+use crate::string;
+use crate::units::Prefix;
 use crate::units::{Abbreviations, Unit};
-use crate::units::{Length, Prefix};
-use crate::utils::Pluralize;
-use crate::{make_abbreviations, make_abbreviations_with_prefixes, pluralize, string};
 
 #[derive(Debug, Clone, Eq, Copy, PartialEq, EnumIter, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Volume {
     CubicMeter(Option<Prefix>),
     Litre(Option<Prefix>),
@@ -52,36 +51,26 @@ impl Volume {
             Volume::Pint => string!("pt"),
             Volume::TeaSpoon => string!("tsp"),
             Volume::TableSpoon => string!("tbsp"),
-            Volume::Quart => {}
-            Volume::Gallon => {}
-            Volume::Barrel => {}
-            Volume::Cord => {}
-            Volume::Peck => {}
-            Volume::Bushel => {}
-            Volume::Hogshead => {}
+            Volume::Quart => string!("qt"),
+            Volume::Gallon => string!("gal"),
+            Volume::Barrel => string!("bbl"),
+            Volume::Cord => string!("cd"),
+            Volume::Peck => string!("pk"),
+            Volume::Bushel => string!("bu"),
+            Volume::Hogshead => string!("hhd"),
         }
     }
 
+    /// Volumes are not parsed yet.
     pub fn abbreviations() -> Abbreviations {
-        let mut case_sensitive = HashMap::new();
-        let mut case_insensitive = HashMap::new();
-
-        for l in Volume::iter() {
-            match l {
-                _ => {}
-            };
-        }
-
         Abbreviations {
-            case_sensitive,
-            case_insensitive,
+            case_sensitive: HashMap::new(),
+            case_insensitive: HashMap::new(),
         }
     }
 
     pub fn reference_unit_multiplier(self) -> Decimal {
-        match self {
-            _ => dec!(1),
-        }
+        Decimal::ONE
     }
 
     pub fn to_unit(self) -> Unit {
@@ -91,6 +80,6 @@ impl Volume {
 
 impl Default for Volume {
     fn default() -> Self {
-        CubicMeter(None)
+        Volume::CubicMeter(None)
     }
 }
@@ -2,7 +2,7 @@ use rust_decimal_macros::dec;
 
 use crate::units::angle::Angle;
 use crate::units::unit::test_units;
-use crate::units::{Length, Unit};
+use crate::units::Unit;
 
 #[test]
 fn test_angle() {
@@ -1,6 +1,4 @@
-use crate::{Calculator, NumericExpression, Unit};
 use rust_decimal::Decimal;
-use rust_decimal_macros::dec;
 
 pub(super) fn split_string_by_comma(s: &str) -> Vec<String> {
     let s = s.trim();
@@ -19,11 +17,11 @@ pub(super) fn split_string_by_comma(s: &str) -> Vec<String> {
 
     for c in s.chars() {
         match c {
-            '(' => {
+            '(' | '[' => {
                 parentheses_count += 1;
                 token.push(c);
             }
-            ')' => {
+            ')' | ']' => {
                 parentheses_count -= 1;
                 token.push(c);
             }
@@ -126,7 +124,7 @@ macro_rules! make_abbreviations_with_prefixes {
     }};
 }
 
-pub(super) fn flatten_lines<T: Clone>(lines: &Vec<Vec<T>>) -> Vec<Vec<T>> {
+pub(super) fn flatten_lines<T: Clone>(lines: &[Vec<T>]) -> Vec<Vec<T>> {
     let mut ret = Vec::new();
     if lines.is_empty() {
         return ret;
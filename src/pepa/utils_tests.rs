@@ -1,3 +1,4 @@
+use crate::locale::Locale;
 use crate::pluralize;
 use crate::utils::{flatten_lines, split_string_by_comma};
 use crate::utils::Pluralize;
@@ -20,6 +21,12 @@ fn test_split() {
         vec!["(test)", "pow(2, pow(1,1))"]
     );
 
+    // decimal comma locale
+    assert_eq!(
+        split_string_by_comma(&Locale::Cs.normalize_input("3,5; pow(2,5; 2)")),
+        vec!["3.5", "pow(2.5, 2)"]
+    );
+
     assert!(split_string_by_comma(",,,").is_empty());
     assert!(split_string_by_comma(" ").is_empty());
 }
@@ -36,15 +43,15 @@ fn test_pluralize() {
 #[test]
 fn test_flatten_lines() {
     assert_eq!(
-        flatten_lines(&vec![vec!["a"], vec!["b1", "b2"]]),
+        flatten_lines(&[vec!["a"], vec!["b1", "b2"]]),
         vec![vec!["a", "b1"], vec!["a", "b2"]]
     );
     assert_eq!(
-        flatten_lines(&vec![vec!["a1", "a2"], vec!["b"]]),
+        flatten_lines(&[vec!["a1", "a2"], vec!["b"]]),
         vec![vec!["a1", "b"], vec!["a2", "b"]]
     );
     assert_eq!(
-        flatten_lines(&vec![vec!["a1", "a2"], vec!["b1", "b2"], vec!["c"]]),
+        flatten_lines(&[vec!["a1", "a2"], vec!["b1", "b2"], vec!["c"]]),
         vec![
             vec!["a1", "b1", "c"],
             vec!["a1", "b2", "c"],